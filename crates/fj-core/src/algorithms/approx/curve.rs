@@ -2,18 +2,19 @@
 
 use std::collections::BTreeMap;
 
-use fj_math::Point;
+use fj_interop::ext::ArrayExt;
+use fj_math::{Circle, Line, Point, Scalar};
 
 use crate::{
     geometry::{
-        CurveBoundary, Geometry, GlobalPath, HalfEdgeGeom, SurfaceGeom,
-        SurfacePath,
+        CurveBoundary, Geometry, GlobalPath, HalfEdgeGeom, RevolvedCurve,
+        SurfaceGeom, SurfacePath, SweptCurve,
     },
     storage::Handle,
     topology::{Curve, Surface},
 };
 
-use super::{Approx, ApproxPoint, Tolerance};
+use super::{path::PathApproxParams, Approx, ApproxPoint, Tolerance};
 
 impl Approx for (&Handle<Curve>, &HalfEdgeGeom, &Handle<Surface>) {
     type Approximation = CurveApprox;
@@ -51,19 +52,56 @@ fn approx_curve(
     tolerance: impl Into<Tolerance>,
     geometry: &Geometry,
 ) -> CurveApprox {
-    // There are different cases of varying complexity. Circles are the hard
-    // part here, as they need to be approximated, while lines don't need to be.
+    // There are different cases of varying complexity. Circles and splines are
+    // the hard part here, as they need to be approximated, while lines don't
+    // need to be.
     //
     // This will probably all be unified eventually, as `SurfacePath` and
     // `GlobalPath` grow APIs that are better suited to implementing this code
     // in a more abstract way.
-    let points = match (path, surface.u) {
-        (SurfacePath::Circle(_), GlobalPath::Circle(_)) => {
+    let points = match (path, surface) {
+        (
+            SurfacePath::Circle(_),
+            SurfaceGeom::Swept(SweptCurve {
+                u: GlobalPath::Circle(_),
+                ..
+            })
+            | SurfaceGeom::Revolved(_),
+        ) => {
+            todo!(
+                "Approximating a circle on a curved surface not supported yet."
+            )
+        }
+        (
+            SurfacePath::Spline(_),
+            SurfaceGeom::Swept(SweptCurve {
+                u: GlobalPath::Circle(_) | GlobalPath::Spline(_),
+                ..
+            })
+            | SurfaceGeom::Revolved(_),
+        ) => {
+            todo!(
+                "Approximating a spline on a curved surface not supported yet."
+            )
+        }
+        (
+            SurfacePath::Circle(_),
+            SurfaceGeom::Swept(SweptCurve {
+                u: GlobalPath::Spline(_),
+                ..
+            }),
+        ) => {
             todo!(
                 "Approximating a circle on a curved surface not supported yet."
             )
         }
-        (SurfacePath::Circle(_), GlobalPath::Line(_)) => {
+        (
+            SurfacePath::Circle(_) | SurfacePath::Spline(_),
+            SurfaceGeom::Swept(SweptCurve {
+                u: GlobalPath::Line(_),
+                ..
+            }),
+        ) => {
             (path, boundary)
                 .approx_with_cache(tolerance, &mut (), geometry)
                 .into_iter()
@@ -90,13 +128,13 @@ fn approx_curve(
                 })
                 .collect()
         }
-        (SurfacePath::Line(line), _) => {
+        (SurfacePath::Line(line), SurfaceGeom::Swept(SweptCurve { u, .. })) => {
             let range_u =
                 CurveBoundary::from(boundary.inner.map(|point_curve| {
                     [path.point_from_path_coords(point_curve).u]
                 }));
 
-            let approx_u = (surface.u, range_u).approx_with_cache(
+            let approx_u = (u.clone(), range_u).approx_with_cache(
                 tolerance,
                 &mut (),
                 geometry,
@@ -113,6 +151,17 @@ fn approx_curve(
 
             points
         }
+        (SurfacePath::Line(line), SurfaceGeom::Revolved(revolved)) => {
+            approx_line_on_revolved_surface(line, revolved, boundary, tolerance)
+                .map(|point_curve| {
+                    let point_surface =
+                        path.point_from_path_coords(point_curve);
+                    let point_global =
+                        surface.point_from_surface_coords(point_surface);
+                    (point_curve, point_global)
+                })
+                .collect()
+        }
     };
 
     let points = points
@@ -124,6 +173,94 @@ fn approx_curve(
     CurveApprox { points }
 }
 
+/// Approximate a line on a surface of revolution
+///
+/// Such a line can be curved along both the revolution and the profile. The
+/// points are distributed evenly along the line, densely enough for whichever
+/// of the two requires more of them.
+fn approx_line_on_revolved_surface(
+    line: &Line<2>,
+    surface: &RevolvedCurve,
+    boundary: CurveBoundary<Point<1>>,
+    tolerance: impl Into<Tolerance>,
+) -> impl Iterator<Item = Point<1>> {
+    let [start, end] = boundary
+        .inner
+        .map(|point| line.point_from_line_coords(point));
+
+    let increments =
+        revolved_surface_increments(surface, [start.v, end.v], tolerance);
+    let num_segments = [end.u - start.u, end.v - start.v]
+        .zip_ext(increments)
+        .map(|(distance, increment)| {
+            increment.map_or(Scalar::ONE, |increment| {
+                (distance.abs() / increment).ceil()
+            })
+        })
+        .into_iter()
+        .fold(Scalar::ONE, Scalar::max)
+        .into_u64();
+
+    let [min, max] = boundary.inner;
+    (1..num_segments).map(move |i| {
+        let t = Scalar::from_u64(i) / Scalar::from_u64(num_segments);
+        min + (max - min) * t
+    })
+}
+
+/// Compute the increments for approximating a surface of revolution
+///
+/// Returns the increments along u and v that are required for the part of the
+/// surface between the provided profile coordinates. An increment is `None`, if
+/// the surface doesn't need to be approximated in that direction.
+pub(super) fn revolved_surface_increments(
+    surface: &RevolvedCurve,
+    range_v: [Scalar; 2],
+    tolerance: impl Into<Tolerance>,
+) -> [Option<Scalar>; 2] {
+    let tolerance = tolerance.into();
+    let scale = surface.radial.magnitude();
+
+    let increment_for_radius = |radius: Scalar| {
+        // Near the axis, the circle of revolution degenerates to a point, and
+        // there's nothing to approximate.
+        if radius <= tolerance.inner() {
+            return None;
+        }
+
+        let circle =
+            Circle::from_center_and_radius(Point::<2>::origin(), radius);
+        Some(PathApproxParams::for_circle(&circle, tolerance).increment())
+    };
+
+    let max_radius = match &surface.profile {
+        SurfacePath::Circle(circle) => {
+            circle.center().u.abs() + circle.radius()
+        }
+        SurfacePath::Spline(spline) => {
+            let aabb = spline.aabb();
+            aabb.min.u.abs().max(aabb.max.u.abs())
+        }
+        SurfacePath::Line(profile) => range_v
+            .map(|v| profile.point_from_line_coords([v]).u.abs())
+            .into_iter()
+            .fold(Scalar::ZERO, Scalar::max),
+    };
+
+    let increment_u = increment_for_radius(max_radius * scale);
+    let increment_v = match &surface.profile {
+        SurfacePath::Circle(circle) => {
+            increment_for_radius(circle.radius() * scale)
+        }
+        SurfacePath::Spline(_) => {
+            todo!("Approximating spline profiles is not supported yet.")
+        }
+        SurfacePath::Line(_) => None,
+    };
+
+    [increment_u, increment_v]
+}
+
 /// Approximation of [`Curve`], within a specific boundary
 #[derive(Clone)]
 pub struct CurveApprox {
@@ -195,10 +332,16 @@ mod tests {
         let surface = core.layers.topology.surfaces.xz_plane();
         let (path, boundary) =
             SurfacePath::line_from_points([[1., 1.], [2., 1.]]);
-        let curve =
-            Curve::from_path_and_surface(path, surface.clone(), &mut core);
+        let curve = Curve::from_path_and_surface(
+            path.clone(),
+            surface.clone(),
+            &mut core,
+        );
         let boundary = CurveBoundary::from(boundary);
-        let half_edge = HalfEdgeGeom { path, boundary };
+        let half_edge = HalfEdgeGeom {
+            path: path.clone(),
+            boundary,
+        };
 
         let tolerance = 1.;
         let approx = (&curve, &half_edge, &surface)
@@ -218,10 +361,16 @@ mod tests {
         );
         let (path, boundary) =
             SurfacePath::line_from_points([[1., 1.], [2., 1.]]);
-        let curve =
-            Curve::from_path_and_surface(path, surface.clone(), &mut core);
+        let curve = Curve::from_path_and_surface(
+            path.clone(),
+            surface.clone(),
+            &mut core,
+        );
         let boundary = CurveBoundary::from(boundary);
-        let half_edge = HalfEdgeGeom { path, boundary };
+        let half_edge = HalfEdgeGeom {
+            path: path.clone(),
+            boundary,
+        };
 
         let tolerance = 1.;
         let approx = (&curve, &half_edge, &surface)
@@ -235,21 +384,28 @@ mod tests {
         let mut core = Core::new();
 
         let global_path = GlobalPath::circle_from_radius(1.);
-        let surface = Surface::from_uv(global_path, [0., 0., 1.], &mut core);
+        let surface =
+            Surface::from_uv(global_path.clone(), [0., 0., 1.], &mut core);
         let path = SurfacePath::line_from_points_with_coords([
             ([0.], [0., 1.]),
             ([TAU], [TAU, 1.]),
         ]);
-        let curve =
-            Curve::from_path_and_surface(path, surface.clone(), &mut core);
+        let curve = Curve::from_path_and_surface(
+            path.clone(),
+            surface.clone(),
+            &mut core,
+        );
         let boundary = CurveBoundary::from([[0.], [TAU]]);
-        let half_edge = HalfEdgeGeom { path, boundary };
+        let half_edge = HalfEdgeGeom {
+            path: path.clone(),
+            boundary,
+        };
 
         let tolerance = 1.;
         let approx = (&curve, &half_edge, &surface)
             .approx(tolerance, &core.layers.geometry);
 
-        let expected_approx = (global_path, boundary)
+        let expected_approx = (global_path.clone(), boundary)
             .approx(tolerance, &core.layers.geometry)
             .into_iter()
             .map(|(point_local, _)| {
@@ -271,10 +427,16 @@ mod tests {
 
         let surface = core.layers.topology.surfaces.xz_plane();
         let path = SurfacePath::circle_from_center_and_radius([0., 0.], 1.);
-        let curve =
-            Curve::from_path_and_surface(path, surface.clone(), &mut core);
+        let curve = Curve::from_path_and_surface(
+            path.clone(),
+            surface.clone(),
+            &mut core,
+        );
         let boundary = CurveBoundary::from([[0.], [TAU]]);
-        let half_edge = HalfEdgeGeom { path, boundary };
+        let half_edge = HalfEdgeGeom {
+            path: path.clone(),
+            boundary,
+        };
 
         let tolerance = 1.;
         let approx = (&curve, &half_edge, &surface)
@@ -2,18 +2,21 @@
 //!
 //! See [`FaceApprox`].
 
-use std::{collections::BTreeSet, ops::Deref};
+use std::{collections::BTreeSet, iter, ops::Deref};
+
+use fj_math::{Aabb, Point, Scalar};
 
 use crate::{
-    geometry::Geometry,
+    algorithms::triangulate::polygon::Polygon,
+    geometry::{Geometry, RevolvedCurve, SurfaceGeom},
     storage::Handle,
     topology::{Face, Handedness, ObjectSet},
     validation::ValidationConfig,
 };
 
 use super::{
-    cycle::CycleApprox, edge::HalfEdgeApproxCache, Approx, ApproxPoint,
-    Tolerance,
+    curve::revolved_surface_increments, cycle::CycleApprox,
+    edge::HalfEdgeApproxCache, Approx, ApproxPoint, Tolerance,
 };
 
 impl Approx for &ObjectSet<Face> {
@@ -77,18 +80,15 @@ impl Approx for Handle<Face> {
     ) -> Self::Approximation {
         let tolerance = tolerance.into();
 
-        // Curved faces whose curvature is not fully defined by their edges
-        // are not supported yet. For that reason, we can fully ignore `face`'s
-        // `surface` field and just pass the edges to `Self::for_edges`.
-        //
-        // An example of a curved face that is supported, is the cylinder. Its
-        // curvature is fully defined be the edges (circles) that border it. The
-        // circle approximations are sufficient to triangulate the surface.
+        // The curvature of swept surfaces is fully defined by the edges that
+        // border them. An example of that is the cylinder, whose curvature is
+        // defined by the circles at either end. The approximations of the
+        // edges are sufficient to triangulate the surface.
         //
-        // An example of a curved face that is currently not supported, and thus
-        // doesn't need to be handled here, is a sphere. A spherical face would
-        // would need to provide its own approximation, as the edges that bound
-        // it have nothing to do with its curvature.
+        // This is not the case for surfaces of revolution. An example is a
+        // sphere, where the edges that bound a face can have nothing to do
+        // with its curvature. Those need an approximation of the surface
+        // itself, within the edges.
 
         let exterior = (self.region().exterior().deref(), self.surface())
             .approx_with_cache(tolerance, cache, geometry);
@@ -100,11 +100,19 @@ impl Approx for Handle<Face> {
             interiors.insert(cycle);
         }
 
+        let inner = match geometry.of_surface(self.surface()) {
+            SurfaceGeom::Swept(_) => BTreeSet::new(),
+            SurfaceGeom::Revolved(surface) => approx_revolved_surface(
+                surface, &exterior, &interiors, tolerance,
+            ),
+        };
+
         let coord_handedness = self.coord_handedness(geometry);
         FaceApprox {
             face: self,
             exterior,
             interiors,
+            inner,
             coord_handedness,
         }
     }
@@ -122,6 +130,12 @@ pub struct FaceApprox {
     /// Approximations of the interior cycles
     pub interiors: BTreeSet<CycleApprox>,
 
+    /// Points that approximate the surface within the cycles
+    ///
+    /// These are only required for surfaces, whose curvature is not defined
+    /// by the cycles that bound them.
+    pub inner: BTreeSet<ApproxPoint<2>>,
+
     /// The handedness of the approximated face's front-side coordinate system
     pub coord_handedness: Handedness,
 }
@@ -137,6 +151,93 @@ impl FaceApprox {
             points.extend(cycle_approx.points());
         }
 
+        points.extend(self.inner.iter().copied());
+
         points
     }
 }
+
+/// Approximate a surface of revolution within the provided cycles
+///
+/// Creates a grid of points in surface coordinates, keeping those that are
+/// within the cycles and not too close to them.
+fn approx_revolved_surface(
+    surface: &RevolvedCurve,
+    exterior: &CycleApprox,
+    interiors: &BTreeSet<CycleApprox>,
+    tolerance: Tolerance,
+) -> BTreeSet<ApproxPoint<2>> {
+    let cycles = iter::once(exterior)
+        .chain(interiors)
+        .map(|cycle| {
+            cycle
+                .points()
+                .into_iter()
+                .map(|point| point.local_form)
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    let aabb = Aabb::<2>::from_points(cycles.iter().flatten().copied());
+
+    let [Some(increment_u), Some(increment_v)] = revolved_surface_increments(
+        surface,
+        [aabb.min.v, aabb.max.v],
+        tolerance,
+    ) else {
+        return BTreeSet::new();
+    };
+
+    let [(coords_u, step_u), (coords_v, step_v)] = [
+        (aabb.min.u, aabb.max.u, increment_u),
+        (aabb.min.v, aabb.max.v, increment_v),
+    ]
+    .map(|(min, max, increment)| {
+        let num_steps = ((max - min) / increment).ceil().into_u64();
+        let step = (max - min) / Scalar::from_u64(num_steps);
+
+        let coords = (1..num_steps)
+            .map(|i| min + step * Scalar::from_u64(i))
+            .collect::<Vec<_>>();
+
+        (coords, step)
+    });
+
+    // Points that are too close to the cycles would result in degenerate
+    // triangles.
+    let margin = step_u.min(step_v) / Scalar::from(4.);
+
+    let polygon = Polygon::new()
+        .with_exterior(cycles[0].iter().copied())
+        .with_interiors(cycles[1..].iter().map(|cycle| cycle.iter().copied()));
+    let segments = cycles
+        .iter()
+        .flat_map(|cycle| cycle.windows(2).map(|points| [points[0], points[1]]))
+        .collect::<Vec<_>>();
+
+    coords_u
+        .iter()
+        .flat_map(|&u| coords_v.iter().map(move |&v| Point::from([u, v])))
+        .filter(|&point| {
+            polygon.contains_point(point)
+                && segments.iter().all(|&segment| {
+                    distance_to_segment(point, segment) > margin
+                })
+        })
+        .map(|point| {
+            ApproxPoint::new(point, surface.point_from_surface_coords(point))
+        })
+        .collect()
+}
+
+fn distance_to_segment(point: Point<2>, [a, b]: [Point<2>; 2]) -> Scalar {
+    let ab = b - a;
+    let length_squared = ab.dot(&ab);
+
+    let t = if length_squared == Scalar::ZERO {
+        Scalar::ZERO
+    } else {
+        ((point - a).dot(&ab) / length_squared).clamp(Scalar::ZERO, Scalar::ONE)
+    };
+
+    (point - (a + ab * t)).magnitude()
+}
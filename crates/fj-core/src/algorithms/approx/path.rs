@@ -30,7 +30,7 @@
 
 use std::iter;
 
-use fj_math::{Circle, Point, Scalar, Sign};
+use fj_math::{Circle, Point, Scalar, Sign, Spline};
 
 use crate::geometry::{CurveBoundary, Geometry, GlobalPath, SurfacePath};
 
@@ -53,6 +53,9 @@ impl Approx for (&SurfacePath, CurveBoundary<Point<1>>) {
                 approx_circle(circle, range, tolerance.into())
             }
             SurfacePath::Line(_) => vec![],
+            SurfacePath::Spline(spline) => {
+                approx_spline(spline, range, tolerance.into())
+            }
         }
     }
 }
@@ -74,6 +77,9 @@ impl Approx for (GlobalPath, CurveBoundary<Point<1>>) {
                 approx_circle(&circle, range, tolerance.into())
             }
             GlobalPath::Line(_) => vec![],
+            GlobalPath::Spline(spline) => {
+                approx_spline(&spline, range, tolerance.into())
+            }
         }
     }
 }
@@ -100,7 +106,127 @@ fn approx_circle<const D: usize>(
     points
 }
 
-struct PathApproxParams {
+/// Approximate a spline
+///
+/// `tolerance` specifies how much the approximation is allowed to deviate
+/// from the spline.
+///
+/// Only the domain of the spline is approximated. Parts of the range that
+/// extend beyond it are covered by a straight segment.
+fn approx_spline<const D: usize>(
+    spline: &Spline<D>,
+    boundary: impl Into<CurveBoundary<Point<1>>>,
+    tolerance: Tolerance,
+) -> Vec<(Point<1>, Point<D>)> {
+    let boundary = boundary.into();
+
+    let [a, b] = boundary.inner.map(|point| point.t);
+    let [min, max] = if a < b { [a, b] } else { [b, a] };
+
+    // Same as with circles, stay clear of the boundaries.
+    let margin = Scalar::from(1e-6);
+
+    let mut points = spline_params(spline, tolerance)
+        .into_iter()
+        .filter(|&t| t > min + margin && t < max - margin)
+        .map(|t| {
+            let point_curve = Point::from([t]);
+            (point_curve, spline.point_from_spline_coords(point_curve))
+        })
+        .collect::<Vec<_>>();
+
+    if a > b {
+        points.reverse();
+    }
+
+    points
+}
+
+/// Compute the parameters of the points that approximate a whole spline
+///
+/// The knot spans within the domain of the spline are subdivided, until each
+/// segment is within the tolerance of the spline. This only depends on the
+/// spline and the tolerance, which keeps the approximation deterministic.
+///
+/// Returns the parameters in ascending order, including the boundaries of the
+/// domain.
+fn spline_params<const D: usize>(
+    spline: &Spline<D>,
+    tolerance: Tolerance,
+) -> Vec<Scalar> {
+    // Limits the number of segments per knot span to `2^MAX_DEPTH`, in case
+    // the tolerance is unreasonably small compared to the spline.
+    const MAX_DEPTH: u32 = 16;
+
+    fn subdivide<const D: usize>(
+        spline: &Spline<D>,
+        [a, b]: [Scalar; 2],
+        tolerance: Tolerance,
+        depth: u32,
+        params: &mut Vec<Scalar>,
+    ) {
+        let is_flat = {
+            let chord = [a, b].map(|t| spline.point_from_spline_coords([t]));
+
+            // Sample the spline between the boundaries of the segment. A
+            // single sample in the middle could miss a segment that is curved
+            // like an "S".
+            [1, 2, 3].into_iter().all(|i| {
+                let t = a + (b - a) * Scalar::from_u64(i) / Scalar::from(4.);
+                let point = spline.point_from_spline_coords([t]);
+
+                distance_to_segment(point, chord) <= tolerance.inner()
+            })
+        };
+
+        if is_flat || depth >= MAX_DEPTH {
+            params.push(b);
+            return;
+        }
+
+        let middle = (a + b) / Scalar::TWO;
+        subdivide(spline, [a, middle], tolerance, depth + 1, params);
+        subdivide(spline, [middle, b], tolerance, depth + 1, params);
+    }
+
+    let [min, max] = spline.domain();
+
+    let mut knots = spline
+        .knots()
+        .iter()
+        .copied()
+        .filter(|&knot| knot > min && knot < max)
+        .collect::<Vec<_>>();
+    knots.dedup();
+
+    let mut params = vec![min];
+    let mut start = min;
+    for end in knots.into_iter().chain([max]) {
+        subdivide(spline, [start, end], tolerance, 0, &mut params);
+        start = end;
+    }
+
+    params
+}
+
+fn distance_to_segment<const D: usize>(
+    point: Point<D>,
+    [a, b]: [Point<D>; 2],
+) -> Scalar {
+    let ab = b - a;
+    let length_squared = ab.dot(&ab);
+
+    if length_squared == Scalar::ZERO {
+        return (point - a).magnitude();
+    }
+
+    let t = ((point - a).dot(&ab) / length_squared)
+        .max(Scalar::ZERO)
+        .min(Scalar::ONE);
+    (point - (a + ab * t)).magnitude()
+}
+
+pub(super) struct PathApproxParams {
     increment: Scalar,
 }
 
@@ -138,9 +264,12 @@ impl PathApproxParams {
         let [min, max] = if a < b { [a, b] } else { [b, a] };
 
         // We can't generate a point exactly at the boundaries of the range as
-        // part of the approximation. Make sure we stay inside the range.
-        let min = min.floor() + 1.;
-        let max = max.ceil() - 1.;
+        // part of the approximation. Make sure we stay inside the range, with
+        // a small margin. Otherwise numerical inaccuracy could result in a
+        // point that is nearly identical to a boundary.
+        let margin = Scalar::from(1e-6);
+        let min = (min + margin).floor() + 1.;
+        let max = (max - margin).ceil() - 1.;
 
         let [start, end] = match direction {
             Sign::Negative => [max, min],
@@ -170,11 +299,11 @@ impl PathApproxParams {
 mod tests {
     use std::f64::consts::TAU;
 
-    use fj_math::{Circle, Point, Scalar};
+    use fj_math::{Circle, Point, Scalar, Spline};
 
     use crate::algorithms::approx::{path::CurveBoundary, Tolerance};
 
-    use super::PathApproxParams;
+    use super::{approx_spline, distance_to_segment, PathApproxParams};
 
     #[test]
     fn increment_for_circle() {
@@ -243,4 +372,80 @@ mod tests {
             assert_eq!(points, expected_points);
         }
     }
+
+    #[test]
+    fn points_for_circle_near_boundary() {
+        let circle = Circle::from_center_and_radius([0., 0.], 1.);
+        let params = PathApproxParams::for_circle(&circle, 0.375);
+        let increment = params.increment();
+
+        let points = |boundary: [Scalar; 2]| {
+            params
+                .points(boundary.map(|t| Point::from([t])))
+                .map(|point| point.t / increment)
+                .collect::<Vec<_>>()
+        };
+
+        // Boundaries that are exactly on a multiple of the increment are
+        // excluded, as they always have been.
+        assert_eq!(points([increment, increment * 3.]), [Scalar::TWO]);
+
+        // Boundaries that are off by a tiny numerical error are excluded too.
+        // Without the margin, the approximation would contain points that are
+        // nearly identical to the boundaries.
+        let error = Scalar::from(1e-12);
+        assert_eq!(
+            points([increment - error, increment * 3. + error]),
+            [Scalar::TWO]
+        );
+        assert_eq!(
+            points([increment * 3. + error, increment - error]),
+            [Scalar::TWO]
+        );
+    }
+
+    #[test]
+    fn points_for_spline() {
+        let spline = Spline::clamped(
+            3,
+            [[0., 0.], [1., 2.], [2., -1.], [3., 1.], [4., 0.], [5., 1.]],
+        );
+        let tolerance = Tolerance::from_scalar(0.01).unwrap();
+
+        let boundary = CurveBoundary::from([[0.], [1.]]);
+        let points = approx_spline(&spline, boundary, tolerance);
+
+        // Every segment of the approximation, including the ones connecting to
+        // the boundary, must be within the tolerance of the spline.
+        let coords = [Scalar::ZERO]
+            .into_iter()
+            .chain(points.iter().map(|(point_curve, _)| point_curve.t))
+            .chain([Scalar::ONE])
+            .collect::<Vec<_>>();
+        for segment in coords.windows(2) {
+            let [a, b] = [segment[0], segment[1]];
+            let chord = [a, b].map(|t| spline.point_from_spline_coords([t]));
+
+            for i in 1..10 {
+                let t = a + (b - a) * Scalar::from_u64(i) / Scalar::from(10.);
+                let point = spline.point_from_spline_coords([t]);
+
+                // The approximation only samples each segment, so allow for
+                // a bit of extra deviation.
+                let distance = distance_to_segment(point, chord);
+                assert!(distance <= tolerance.inner() * Scalar::from(1.5));
+            }
+        }
+
+        // Reversing the boundary reverses the points.
+        let mut reversed =
+            approx_spline(&spline, boundary.reverse(), tolerance);
+        reversed.reverse();
+        assert_eq!(points, reversed);
+
+        // Approximating part of the spline results in a subset of the points.
+        let partial = approx_spline(&spline, [[0.25], [0.75]], tolerance);
+        assert!(!partial.is_empty());
+        assert!(partial.iter().all(|point| points.contains(point)));
+    }
 }
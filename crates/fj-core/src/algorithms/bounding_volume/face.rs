@@ -1,7 +1,10 @@
-use fj_math::Aabb;
+use fj_math::{Aabb, Vector};
 
 use crate::{
-    geometry::{Geometry, GlobalPath},
+    geometry::{
+        Geometry, GlobalPath, RevolvedCurve, SurfaceGeom, SurfacePath,
+        SweptCurve,
+    },
     topology::Face,
 };
 
@@ -10,23 +13,72 @@ impl super::BoundingVolume<3> for &Face {
         self.region().exterior().aabb(geometry).map(|aabb2| {
             let surface = geometry.of_surface(self.surface());
 
-            match surface.u {
-                GlobalPath::Circle(circle) => {
+            match surface {
+                SurfaceGeom::Swept(SweptCurve {
+                    u: GlobalPath::Circle(circle),
+                    v,
+                }) => {
                     // This is not the most precise way to calculate the AABB,
                     // doing it for the whole circle, but it should do.
 
                     let aabb_bottom = circle.aabb();
                     let aabb_top = Aabb {
-                        min: aabb_bottom.min + surface.v,
-                        max: aabb_bottom.max + surface.v,
+                        min: aabb_bottom.min + *v,
+                        max: aabb_bottom.max + *v,
                     };
 
                     aabb_bottom.merged(&aabb_top)
                 }
-                GlobalPath::Line(_) => Aabb {
+                SurfaceGeom::Swept(SweptCurve {
+                    u: GlobalPath::Spline(spline),
+                    v,
+                }) => {
+                    // Same as above, this uses the whole spline.
+
+                    let aabb_bottom = spline.aabb();
+                    let aabb_top = Aabb {
+                        min: aabb_bottom.min + *v,
+                        max: aabb_bottom.max + *v,
+                    };
+
+                    aabb_bottom.merged(&aabb_top)
+                }
+                SurfaceGeom::Swept(SweptCurve {
+                    u: GlobalPath::Line(_),
+                    ..
+                }) => Aabb {
                     min: surface.point_from_surface_coords(aabb2.min),
                     max: surface.point_from_surface_coords(aabb2.max),
                 },
+                SurfaceGeom::Revolved(RevolvedCurve {
+                    axis,
+                    radial,
+                    profile,
+                }) => {
+                    // Same as above, this uses the full revolution of the
+                    // profile. For circular profiles, it even uses the whole
+                    // circle.
+
+                    let profile = match profile {
+                        SurfacePath::Circle(circle) => circle.aabb(),
+                        SurfacePath::Spline(spline) => spline.aabb(),
+                        SurfacePath::Line(line) => Aabb::<2>::from_points(
+                            [aabb2.min.v, aabb2.max.v]
+                                .map(|v| line.point_from_line_coords([v])),
+                        ),
+                    };
+
+                    let radius = profile.min.u.abs().max(profile.max.u.abs())
+                        * radial.magnitude();
+                    let offset = Vector::from_component(radius);
+
+                    Aabb::<3>::from_points(
+                        [profile.min.v, profile.max.v]
+                            .map(|v| axis.point_from_line_coords([v]))
+                            .into_iter()
+                            .flat_map(|point| [point - offset, point + offset]),
+                    )
+                }
             }
         })
     }
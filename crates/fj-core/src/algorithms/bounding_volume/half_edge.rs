@@ -11,7 +11,7 @@ impl super::BoundingVolume<2> for &Handle<HalfEdge> {
         let half_edge = self;
 
         let half_edge_geom = geometry.of_half_edge(half_edge);
-        let path = half_edge_geom.path;
+        let path = &half_edge_geom.path;
 
         match path {
             SurfacePath::Circle(circle) => {
@@ -26,6 +26,10 @@ impl super::BoundingVolume<2> for &Handle<HalfEdge> {
                     max: circle.center() + center_to_min_max,
                 })
             }
+            SurfacePath::Spline(spline) => {
+                // Same as with the circle, use the AABB of the whole spline.
+                Some(spline.aabb())
+            }
             SurfacePath::Line(_) => {
                 let points = half_edge_geom.boundary.inner.map(|point_curve| {
                     path.point_from_path_coords(point_curve)
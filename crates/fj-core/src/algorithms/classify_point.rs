@@ -0,0 +1,185 @@
+//! Classify points as inside or outside of an object
+
+use fj_math::{Point, Triangle};
+
+use crate::geometry::Geometry;
+
+use super::{
+    approx::{face::FaceApprox, Approx, Tolerance},
+    intersect::{
+        ray_triangle::RayTriangleIntersection, HorizontalRayToTheRight,
+        Intersect,
+    },
+    triangulate::triangulate_face,
+};
+
+/// Classify a point as inside or outside of an object
+///
+/// Implemented for all objects whose approximation is a collection of faces,
+/// like [`Solid`] and [`Shell`]. The result is only meaningful, if those faces
+/// form a closed surface.
+///
+/// [`Shell`]: crate::topology::Shell
+/// [`Solid`]: crate::topology::Solid
+pub trait ClassifyPoint {
+    /// Classify the point in relation to the object
+    ///
+    /// This works by casting a ray from the point, and counting how often it
+    /// crosses the boundary of the object. To do this, the boundary is
+    /// approximated, and `tolerance` defines how far the approximation is
+    /// allowed to deviate from it.
+    ///
+    /// Since the approximation of curved faces is only accurate within the
+    /// tolerance, so is the result for points close to them. To account for
+    /// that, all points that are within `tolerance` of the approximated
+    /// boundary are classified as being on the boundary.
+    fn classify_point(
+        self,
+        point: impl Into<Point<3>>,
+        tolerance: impl Into<Tolerance>,
+        geometry: &Geometry,
+    ) -> PointClassification;
+}
+
+impl<T> ClassifyPoint for T
+where
+    T: Approx,
+    T::Approximation: IntoIterator<Item = FaceApprox>,
+{
+    fn classify_point(
+        self,
+        point: impl Into<Point<3>>,
+        tolerance: impl Into<Tolerance>,
+        geometry: &Geometry,
+    ) -> PointClassification {
+        let point = point.into();
+        let tolerance = tolerance.into();
+
+        let ray = HorizontalRayToTheRight { origin: point };
+        let mut num_hits = 0;
+
+        let triangles = self
+            .approx(tolerance, geometry)
+            .into_iter()
+            .flat_map(triangulate_face)
+            .filter_map(|triangle| {
+                Triangle::from_points(triangle.map(|point| point.point_global))
+                    .ok()
+            });
+
+        for triangle in triangles {
+            if triangle.distance_to_point(point) <= tolerance.inner() {
+                return PointClassification::OnBoundary;
+            }
+
+            match (&ray, &triangle).intersect() {
+                Some(RayTriangleIntersection::RayHitsTriangle) => {
+                    num_hits += 1;
+                }
+                Some(RayTriangleIntersection::RayStartsOnTriangle) => {
+                    return PointClassification::OnBoundary;
+                }
+                None => {}
+            }
+        }
+
+        if num_hits % 2 == 0 {
+            PointClassification::Outside
+        } else {
+            PointClassification::Inside
+        }
+    }
+}
+
+/// The position of a point, relative to an object
+///
+/// Returned by [`ClassifyPoint::classify_point`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PointClassification {
+    /// The point is inside of the object
+    Inside,
+
+    /// The point is outside of the object
+    Outside,
+
+    /// The point is on the boundary of the object
+    OnBoundary,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        operations::{
+            build::{BuildCycle, BuildRegion, BuildSketch},
+            reverse::Reverse,
+            sweep::SweepSketch,
+            update::{UpdateRegion, UpdateSketch},
+        },
+        topology::{Cycle, Region, Sketch, Solid},
+        Core,
+    };
+
+    use super::{ClassifyPoint, PointClassification};
+
+    fn sweep(region: Region, core: &mut Core) -> Solid {
+        Sketch::empty(&core.layers.topology)
+            .add_regions([region], core)
+            .sweep_sketch(
+                core.layers.topology.surfaces.xy_plane(),
+                [0., 0., 1.],
+                core,
+            )
+    }
+
+    #[test]
+    fn cuboid() {
+        let mut core = Core::new();
+
+        let region = Region::polygon(
+            [[0., 0.], [1., 0.], [1., 1.], [0., 1.]],
+            core.layers.topology.surfaces.space_2d(),
+            &mut core,
+        );
+        let cuboid = sweep(region, &mut core);
+
+        let classify = |point: [f64; 3]| {
+            cuboid.classify_point(point, 0.001, &core.layers.geometry)
+        };
+
+        // The ray from the center of the cuboid hits the middle of a face,
+        // where the face is triangulated. The rays from the other points run
+        // along the edges and faces of the cuboid.
+        assert_eq!(classify([0.5, 0.5, 0.5]), PointClassification::Inside);
+        assert_eq!(classify([0.5, 0.5, 2.]), PointClassification::Outside);
+        assert_eq!(classify([-1., 0., 0.]), PointClassification::Outside);
+        assert_eq!(classify([-1., 0.5, 1.]), PointClassification::Outside);
+        assert_eq!(classify([2., 0.5, 0.5]), PointClassification::Outside);
+
+        assert_eq!(classify([1., 0.5, 0.5]), PointClassification::OnBoundary);
+        assert_eq!(classify([0., 0., 0.]), PointClassification::OnBoundary);
+    }
+
+    #[test]
+    fn cylinder_with_hole() {
+        let mut core = Core::new();
+
+        let surface = core.layers.topology.surfaces.space_2d();
+        let region = Region::circle([0., 0.], 2., surface.clone(), &mut core)
+            .add_interiors(
+                [Cycle::circle([0., 0.], 1., surface, &mut core)
+                    .reverse(&mut core)],
+                &mut core,
+            );
+        let spacer = sweep(region, &mut core);
+
+        let classify = |point: [f64; 3]| {
+            spacer.classify_point(point, 0.01, &core.layers.geometry)
+        };
+
+        assert_eq!(classify([0., 0., 0.5]), PointClassification::Outside);
+        assert_eq!(classify([1.5, 0., 0.5]), PointClassification::Inside);
+        assert_eq!(classify([-1.5, 0., 0.5]), PointClassification::Inside);
+        assert_eq!(classify([0., 3., 0.5]), PointClassification::Outside);
+        assert_eq!(classify([2., 0., 0.5]), PointClassification::OnBoundary);
+    }
+}
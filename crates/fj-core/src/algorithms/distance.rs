@@ -0,0 +1,545 @@
+//! Compute the minimum distance between objects
+//!
+//! Objects are approximated by points, line segments, and triangles, and the
+//! minimum distance between those is computed exactly. This is exact for
+//! objects that are bounded by straight edges and planar faces. Curved edges
+//! and faces are approximated, and the result is only as accurate as the
+//! provided tolerance.
+
+use fj_math::{Aabb, Point, Scalar, Triangle, Vector};
+
+use crate::{
+    geometry::Geometry,
+    storage::Handle,
+    topology::{Face, HalfEdge, Shell, Solid, Surface, Vertex},
+};
+
+use super::{
+    approx::{face::FaceApprox, Approx, Tolerance},
+    triangulate::triangulate_face,
+};
+
+/// Compute the minimum distance between two objects
+///
+/// Implemented for the following objects:
+///
+/// - A [`Point`], in global coordinates.
+/// - A [`Vertex`], paired with a [`Face`] that contains it. Vertices don't
+///   have a position by themselves, so it is taken from the face's half-edges.
+/// - A [`HalfEdge`], paired with the [`Surface`] that it is defined on.
+/// - [`Face`], [`Shell`], and [`Solid`]. Their faces are considered as
+///   surfaces, not as enclosed volumes, meaning an object that is fully inside
+///   a solid still has a distance to it.
+pub trait ComputeDistance: Sized {
+    /// Compute the minimum distance to another object, and where it occurs
+    ///
+    /// `tolerance` defines how far the approximation of curved edges and
+    /// faces is allowed to deviate from the actual objects.
+    ///
+    /// Returns `None`, if either object is empty.
+    fn distance(
+        self,
+        other: impl ComputeDistance,
+        tolerance: impl Into<Tolerance>,
+        geometry: &Geometry,
+    ) -> Option<ClosestPoints> {
+        let tolerance = tolerance.into();
+
+        let with_aabb = |primitive: DistancePrimitive| {
+            (
+                primitive,
+                Aabb::<3>::from_points(primitive.points().iter().copied()),
+            )
+        };
+        let a = self
+            .distance_primitives(tolerance, geometry)
+            .into_iter()
+            .map(with_aabb)
+            .collect::<Vec<_>>();
+        let b = other
+            .distance_primitives(tolerance, geometry)
+            .into_iter()
+            .map(with_aabb)
+            .collect::<Vec<_>>();
+
+        let mut closest: Option<ClosestPoints> = None;
+
+        for (a, aabb_a) in &a {
+            for (b, aabb_b) in &b {
+                // The bounding boxes provide a lower bound for the distance
+                // between the primitives, and computing that is much cheaper
+                // than computing the actual distance.
+                if let Some(closest) = closest {
+                    if aabb_distance(aabb_a, aabb_b) > closest.distance {
+                        continue;
+                    }
+                }
+
+                let points = closest_points(a, b);
+                let [point_a, point_b] = points;
+                let distance = point_a.distance_to(&point_b);
+
+                if closest.map_or(true, |closest| distance < closest.distance) {
+                    closest = Some(ClosestPoints { distance, points });
+                }
+            }
+        }
+
+        closest
+    }
+
+    /// Approximate the object as primitives that distances can be computed to
+    fn distance_primitives(
+        self,
+        tolerance: Tolerance,
+        geometry: &Geometry,
+    ) -> Vec<DistancePrimitive>;
+}
+
+impl ComputeDistance for Point<3> {
+    fn distance_primitives(
+        self,
+        _: Tolerance,
+        _: &Geometry,
+    ) -> Vec<DistancePrimitive> {
+        vec![DistancePrimitive::Point(self)]
+    }
+}
+
+impl ComputeDistance for (&Handle<Vertex>, &Face) {
+    fn distance_primitives(
+        self,
+        _: Tolerance,
+        geometry: &Geometry,
+    ) -> Vec<DistancePrimitive> {
+        let (vertex, face) = self;
+
+        let half_edge = face
+            .region()
+            .all_cycles()
+            .flat_map(|cycle| cycle.half_edges())
+            .find(|half_edge| half_edge.start_vertex() == vertex);
+
+        let Some(half_edge) = half_edge else {
+            return Vec::new();
+        };
+
+        let position = geometry
+            .of_surface(face.surface())
+            .point_from_surface_coords(
+                geometry.of_half_edge(half_edge).start_position(),
+            );
+
+        vec![DistancePrimitive::Point(position)]
+    }
+}
+
+impl ComputeDistance for (&Handle<HalfEdge>, &Handle<Surface>) {
+    fn distance_primitives(
+        self,
+        tolerance: Tolerance,
+        geometry: &Geometry,
+    ) -> Vec<DistancePrimitive> {
+        let (half_edge, surface) = self;
+
+        // The approximation of a half-edge leaves out its end point, so we
+        // need to add that ourselves.
+        let half_edge_geom = geometry.of_half_edge(half_edge);
+        let [_, end] = half_edge_geom.boundary.inner;
+        let end = geometry.of_surface(surface).point_from_surface_coords(
+            half_edge_geom.path.point_from_path_coords(end),
+        );
+
+        let mut points = self
+            .approx(tolerance, geometry)
+            .points
+            .into_iter()
+            .map(|point| point.global_form)
+            .collect::<Vec<_>>();
+        points.push(end);
+
+        points
+            .windows(2)
+            .map(|segment| DistancePrimitive::Segment([segment[0], segment[1]]))
+            .collect()
+    }
+}
+
+impl ComputeDistance for &Handle<Face> {
+    fn distance_primitives(
+        self,
+        tolerance: Tolerance,
+        geometry: &Geometry,
+    ) -> Vec<DistancePrimitive> {
+        triangles([self.clone().approx(tolerance, geometry)])
+    }
+}
+
+impl ComputeDistance for &Shell {
+    fn distance_primitives(
+        self,
+        tolerance: Tolerance,
+        geometry: &Geometry,
+    ) -> Vec<DistancePrimitive> {
+        triangles(self.approx(tolerance, geometry))
+    }
+}
+
+impl ComputeDistance for &Solid {
+    fn distance_primitives(
+        self,
+        tolerance: Tolerance,
+        geometry: &Geometry,
+    ) -> Vec<DistancePrimitive> {
+        triangles(self.approx(tolerance, geometry))
+    }
+}
+
+/// The minimum distance between two objects
+///
+/// Returned by [`ComputeDistance::distance`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ClosestPoints {
+    /// The minimum distance between the objects
+    pub distance: Scalar,
+
+    /// The closest points on each object, in global coordinates
+    ///
+    /// The first point is on the object that the distance was computed from,
+    /// the second point is on the other object. If the minimum distance occurs
+    /// at multiple places, these are the points at one of them.
+    pub points: [Point<3>; 2],
+}
+
+/// A primitive that approximates (part of) an object
+///
+/// See [`ComputeDistance::distance_primitives`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DistancePrimitive {
+    /// A point
+    Point(Point<3>),
+
+    /// A line segment between two points
+    Segment([Point<3>; 2]),
+
+    /// A triangle that spans an area
+    Triangle([Point<3>; 3]),
+}
+
+impl DistancePrimitive {
+    fn points(&self) -> &[Point<3>] {
+        match self {
+            Self::Point(point) => std::slice::from_ref(point),
+            Self::Segment(points) => points,
+            Self::Triangle(points) => points,
+        }
+    }
+}
+
+fn triangles(
+    approx: impl IntoIterator<Item = FaceApprox>,
+) -> Vec<DistancePrimitive> {
+    approx
+        .into_iter()
+        .flat_map(triangulate_face)
+        .filter_map(|triangle| {
+            Triangle::from_points(triangle.map(|point| point.point_global)).ok()
+        })
+        .map(|triangle| DistancePrimitive::Triangle(triangle.points()))
+        .collect()
+}
+
+fn aabb_distance(a: &Aabb<3>, b: &Aabb<3>) -> Scalar {
+    let gap = |i: usize| {
+        let before = a.min.coords.components[i] - b.max.coords.components[i];
+        let after = b.min.coords.components[i] - a.max.coords.components[i];
+        before.max(after).max(Scalar::ZERO)
+    };
+
+    Vector::from([gap(0), gap(1), gap(2)]).magnitude()
+}
+
+/// Compute the closest points between two primitives
+fn closest_points(
+    a: &DistancePrimitive,
+    b: &DistancePrimitive,
+) -> [Point<3>; 2] {
+    use DistancePrimitive::{Point, Segment, Triangle};
+
+    match (*a, *b) {
+        (Point(a), Point(b)) => [a, b],
+        (Point(a), Segment(b)) => [a, closest_point_on_segment(a, b)],
+        (Point(a), Triangle(b)) => [a, closest_point_on_triangle(a, b)],
+        (Segment(a), Segment(b)) => closest_points_segments(a, b),
+        (Segment(a), Triangle(b)) => closest_points_segment_triangle(a, b),
+        (Triangle(a), Triangle(b)) => closest_points_triangles(a, b),
+        _ => {
+            let [b, a] = closest_points(b, a);
+            [a, b]
+        }
+    }
+}
+
+fn closest_point_on_segment(
+    point: Point<3>,
+    [a, b]: [Point<3>; 2],
+) -> Point<3> {
+    let ab = b - a;
+
+    let length_squared = ab.dot(&ab);
+    if length_squared == Scalar::ZERO {
+        return a;
+    }
+
+    let t = ((point - a).dot(&ab) / length_squared)
+        .clamp(Scalar::ZERO, Scalar::ONE);
+    a + ab * t
+}
+
+/// Compute the point on a triangle that is closest to the provided point
+///
+/// The triangle must span an area.
+fn closest_point_on_triangle(
+    point: Point<3>,
+    triangle: [Point<3>; 3],
+) -> Point<3> {
+    Triangle::from(triangle).closest_point(point)
+}
+
+fn closest_points_segments(
+    [p1, q1]: [Point<3>; 2],
+    [p2, q2]: [Point<3>; 2],
+) -> [Point<3>; 2] {
+    let d1 = q1 - p1;
+    let d2 = q2 - p2;
+    let r = p1 - p2;
+
+    let a = d1.dot(&d1);
+    let e = d2.dot(&d2);
+    let f = d2.dot(&r);
+
+    let clamp = |value: Scalar| value.clamp(Scalar::ZERO, Scalar::ONE);
+
+    let (s, t) = if a == Scalar::ZERO && e == Scalar::ZERO {
+        (Scalar::ZERO, Scalar::ZERO)
+    } else if a == Scalar::ZERO {
+        (Scalar::ZERO, clamp(f / e))
+    } else {
+        let c = d1.dot(&r);
+
+        if e == Scalar::ZERO {
+            (clamp(-c / a), Scalar::ZERO)
+        } else {
+            let b = d1.dot(&d2);
+            let denom = a * e - b * b;
+
+            // If the segments are parallel, any point on the first one will
+            // do, and the second one is adjusted below.
+            let s = if denom == Scalar::ZERO {
+                Scalar::ZERO
+            } else {
+                clamp((b * f - c * e) / denom)
+            };
+            let t = (b * s + f) / e;
+
+            if t < Scalar::ZERO {
+                (clamp(-c / a), Scalar::ZERO)
+            } else if t > Scalar::ONE {
+                (clamp((b - c) / a), Scalar::ONE)
+            } else {
+                (s, t)
+            }
+        }
+    };
+
+    [p1 + d1 * s, p2 + d2 * t]
+}
+
+fn closest_points_segment_triangle(
+    segment: [Point<3>; 2],
+    triangle: [Point<3>; 3],
+) -> [Point<3>; 2] {
+    if let Some(point) = segment_triangle_intersection(segment, triangle) {
+        return [point, point];
+    }
+
+    // The segment doesn't pierce the triangle. That means the minimum distance
+    // occurs at one of its end points, or between it and one of the edges of
+    // the triangle.
+    let [a, b, c] = triangle;
+    let candidates = segment
+        .map(|point| [point, closest_point_on_triangle(point, triangle)])
+        .into_iter()
+        .chain(
+            [[a, b], [b, c], [c, a]]
+                .map(|edge| closest_points_segments(segment, edge)),
+        );
+
+    candidates
+        .min_by_key(|[a, b]| a.distance_to(b))
+        .expect("Iterating over fixed number of candidates")
+}
+
+/// Compute where a segment crosses the plane of a triangle within the triangle
+///
+/// Segments that lie within the plane of the triangle never pierce it.
+fn segment_triangle_intersection(
+    [p, q]: [Point<3>; 2],
+    [a, b, c]: [Point<3>; 3],
+) -> Option<Point<3>> {
+    let normal = (b - a).cross(&(c - a));
+
+    let dp = normal.dot(&(p - a));
+    let dq = normal.dot(&(q - a));
+
+    let same_side = (dp > Scalar::ZERO && dq > Scalar::ZERO)
+        || (dp < Scalar::ZERO && dq < Scalar::ZERO);
+    if same_side || dp == dq {
+        return None;
+    }
+
+    let point = p + (q - p) * (dp / (dp - dq));
+
+    let is_inside = [[a, b], [b, c], [c, a]]
+        .into_iter()
+        .all(|[u, v]| normal.dot(&(v - u).cross(&(point - u))) >= Scalar::ZERO);
+
+    is_inside.then_some(point)
+}
+
+fn closest_points_triangles(
+    a: [Point<3>; 3],
+    b: [Point<3>; 3],
+) -> [Point<3>; 2] {
+    // If the triangles intersect, an edge of one pierces the other. Otherwise,
+    // the minimum distance occurs between an edge of one triangle and the other
+    // triangle. Either way, checking all edges against the other triangle
+    // covers it.
+    let edges = |[a, b, c]: [Point<3>; 3]| [[a, b], [b, c], [c, a]];
+
+    let from_a = edges(a)
+        .map(|edge| closest_points_segment_triangle(edge, b))
+        .into_iter();
+    let from_b = edges(b).map(|edge| {
+        let [b, a] = closest_points_segment_triangle(edge, a);
+        [a, b]
+    });
+
+    from_a
+        .chain(from_b)
+        .min_by_key(|[a, b]| a.distance_to(b))
+        .expect("Iterating over fixed number of candidates")
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Point, Scalar};
+
+    use crate::{
+        operations::{
+            build::{BuildCycle, BuildRegion, BuildSketch},
+            insert::Insert,
+            reverse::Reverse,
+            sweep::SweepSketch,
+            update::{UpdateRegion, UpdateSketch},
+        },
+        topology::{Cycle, Face, Region, Sketch, Solid},
+        Core,
+    };
+
+    use super::ComputeDistance;
+
+    fn sweep(region: Region, core: &mut Core) -> Solid {
+        Sketch::empty(&core.layers.topology)
+            .add_regions([region], core)
+            .sweep_sketch(
+                core.layers.topology.surfaces.xy_plane(),
+                [0., 0., 1.],
+                core,
+            )
+    }
+
+    fn is_near(a: Scalar, b: f64, tolerance: f64) -> bool {
+        (a - b).abs() <= Scalar::from(tolerance)
+    }
+
+    #[test]
+    fn cuboids() {
+        let mut core = Core::new();
+
+        let a = Region::polygon(
+            [[0., 0.], [1., 0.], [1., 1.], [0., 1.]],
+            core.layers.topology.surfaces.space_2d(),
+            &mut core,
+        );
+        let b = Region::polygon(
+            [[3., 0.], [4., 0.], [4., 1.], [3., 1.]],
+            core.layers.topology.surfaces.space_2d(),
+            &mut core,
+        );
+        let a = sweep(a, &mut core);
+        let b = sweep(b, &mut core);
+
+        let closest = a.distance(&b, 0.001, &core.layers.geometry).unwrap();
+        assert!(is_near(closest.distance, 2., 1e-12));
+        let [point_a, point_b] = closest.points;
+        assert!(is_near(point_a.x, 1., 1e-12));
+        assert!(is_near(point_b.x, 3., 1e-12));
+
+        // A point inside of the solid still has a distance to its faces.
+        let point = Point::from([3.5, 0.5, 0.25]);
+        let closest = point.distance(&b, 0.001, &core.layers.geometry);
+        assert!(is_near(closest.unwrap().distance, 0.25, 1e-12));
+    }
+
+    #[test]
+    fn bolt_in_hole() {
+        let mut core = Core::new();
+
+        let surface = core.layers.topology.surfaces.space_2d();
+        let spacer = Region::circle([0., 0.], 2., surface.clone(), &mut core)
+            .add_interiors(
+                [Cycle::circle([0., 0.], 1.2, surface.clone(), &mut core)
+                    .reverse(&mut core)],
+                &mut core,
+            );
+        let bolt = Region::circle([0., 0.], 1., surface, &mut core);
+        let spacer = sweep(spacer, &mut core);
+        let bolt = sweep(bolt, &mut core);
+
+        let tolerance = 0.01;
+        let closest = bolt
+            .distance(&spacer, tolerance, &core.layers.geometry)
+            .unwrap();
+
+        // Both circles are approximated, which can bring their approximations
+        // closer to each other, or farther apart, by up to the tolerance.
+        assert!(is_near(closest.distance, 0.2, tolerance));
+    }
+
+    #[test]
+    fn vertex_and_half_edge() {
+        let mut core = Core::new();
+
+        let surface = core.layers.topology.surfaces.xy_plane();
+        let region = Region::polygon(
+            [[0., 0.], [1., 0.], [1., 1.], [0., 1.]],
+            surface.clone(),
+            &mut core,
+        );
+        let face = Face::new(surface.clone(), region.insert(&mut core));
+
+        let half_edges = face.region().exterior().half_edges();
+        let bottom = half_edges.nth(0).unwrap();
+        let top_right = half_edges.nth(2).unwrap().start_vertex();
+
+        let closest = (top_right, &face)
+            .distance((bottom, &surface), 0.001, &core.layers.geometry)
+            .unwrap();
+        assert_eq!(closest.distance, Scalar::ONE);
+        assert_eq!(
+            closest.points,
+            [Point::from([1., 1., 0.]), Point::from([1., 0., 0.])]
+        );
+    }
+}
@@ -1,10 +1,12 @@
 //! Intersection algorithms
 
+pub mod ray_face;
 pub mod ray_segment;
+pub mod ray_triangle;
 
 mod line_segment;
 
-use fj_math::{Point, Vector};
+use fj_math::{Point, Scalar, Vector};
 
 pub use self::line_segment::LineSegmentIntersection;
 
@@ -22,6 +24,28 @@ pub trait Intersect {
     fn intersect(self) -> Option<Self::Intersection>;
 }
 
+/// A ray in 3D space
+///
+/// Unlike [`HorizontalRayToTheRight`], this ray can point in any direction.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Ray {
+    /// The point where the ray originates
+    pub origin: Point<3>,
+
+    /// The direction of the ray
+    ///
+    /// Doesn't need to be normalized. Positions along the ray are measured in
+    /// multiples of this vector.
+    pub direction: Vector<3>,
+}
+
+impl Ray {
+    /// Compute the point at the provided position along the ray
+    pub fn point_at(&self, t: impl Into<Scalar>) -> Point<3> {
+        self.origin + self.direction * t.into()
+    }
+}
+
 /// A horizontal ray that goes to the right
 ///
 /// For in-kernel use, we don't need anything more flexible, and being exactly
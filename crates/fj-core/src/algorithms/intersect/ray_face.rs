@@ -0,0 +1,351 @@
+//! Intersection between a ray and faces in 3D
+
+use fj_math::{Circle, Line, Point, Scalar, Vector};
+
+use crate::{
+    algorithms::{
+        approx::{Approx, Tolerance},
+        triangulate::polygon::Polygon,
+    },
+    geometry::{Geometry, GlobalPath, SurfaceGeom, SweptCurve},
+    operations::holes::HoleLocation,
+    storage::Handle,
+    topology::{Face, Handedness, Shell, Solid},
+};
+
+use super::Ray;
+
+/// Cast a ray against the faces of an object
+///
+/// Implemented for [`Face`], [`Shell`], and [`Solid`].
+///
+/// # Implementation Note
+///
+/// Only faces on planes and on surfaces that are swept from circles (like
+/// cylinders) are supported. Faces on any other surfaces are never hit.
+pub trait CastRay {
+    /// Find the first point where the ray hits a face of the object
+    ///
+    /// Only hits that are in the direction of the ray, or at its origin, are
+    /// considered. The boundaries of faces are approximated to decide whether
+    /// a point is on the face, and `tolerance` defines how far that
+    /// approximation is allowed to deviate from them.
+    fn cast_ray(
+        self,
+        ray: &Ray,
+        tolerance: impl Into<Tolerance>,
+        geometry: &Geometry,
+    ) -> Option<RayFaceHit>;
+}
+
+impl CastRay for &Handle<Face> {
+    fn cast_ray(
+        self,
+        ray: &Ray,
+        tolerance: impl Into<Tolerance>,
+        geometry: &Geometry,
+    ) -> Option<RayFaceHit> {
+        let surface = geometry.of_surface(self.surface());
+
+        let (mut candidates, is_periodic) = match surface {
+            SurfaceGeom::Swept(SweptCurve {
+                u: GlobalPath::Line(line),
+                v,
+            }) => (ray_plane(ray, line, v), false),
+            SurfaceGeom::Swept(SweptCurve {
+                u: GlobalPath::Circle(circle),
+                v,
+            }) => (ray_swept_circle(ray, circle, v), true),
+            _ => return None,
+        };
+        candidates.retain(|&t| t >= Scalar::ZERO);
+        candidates.sort();
+
+        if candidates.is_empty() {
+            return None;
+        }
+
+        let polygon = Polygon::from_face_approx(
+            &self.clone().approx(tolerance, geometry),
+        );
+
+        for t in candidates {
+            let point_global = ray.point_at(t);
+            let point_surface = surface.project_global_point(point_global);
+
+            // The u-coordinate of a surface swept from a circle is an angle.
+            // The face might be defined in any range of angles, so we need to
+            // check all representations that might be within it.
+            let offsets = if is_periodic {
+                vec![Scalar::ZERO, -Scalar::TAU, Scalar::TAU]
+            } else {
+                vec![Scalar::ZERO]
+            };
+            let point_surface = offsets
+                .into_iter()
+                .map(|offset| point_surface + [offset, Scalar::ZERO])
+                .find(|&point| polygon.contains_point(point));
+
+            let Some(point_surface) = point_surface else {
+                continue;
+            };
+
+            // The surface normal points towards the front side of the surface,
+            // as defined by its coordinate system. That isn't necessarily the
+            // front side of the face.
+            let normal = surface.normal_at(point_surface);
+            let normal = match self.coord_handedness(geometry) {
+                Handedness::RightHanded => normal,
+                Handedness::LeftHanded => -normal,
+            };
+
+            return Some(RayFaceHit {
+                face: self.clone(),
+                t,
+                point_surface,
+                point_global,
+                normal,
+            });
+        }
+
+        None
+    }
+}
+
+impl CastRay for &Shell {
+    fn cast_ray(
+        self,
+        ray: &Ray,
+        tolerance: impl Into<Tolerance>,
+        geometry: &Geometry,
+    ) -> Option<RayFaceHit> {
+        let tolerance = tolerance.into();
+
+        self.faces()
+            .iter()
+            .filter_map(|face| face.cast_ray(ray, tolerance, geometry))
+            .min_by_key(|hit| hit.t)
+    }
+}
+
+impl CastRay for &Solid {
+    fn cast_ray(
+        self,
+        ray: &Ray,
+        tolerance: impl Into<Tolerance>,
+        geometry: &Geometry,
+    ) -> Option<RayFaceHit> {
+        let tolerance = tolerance.into();
+
+        self.shells()
+            .iter()
+            .filter_map(|shell| shell.cast_ray(ray, tolerance, geometry))
+            .min_by_key(|hit| hit.t)
+    }
+}
+
+/// A point where a ray hits a face
+///
+/// Returned by [`CastRay::cast_ray`].
+#[derive(Clone, Debug)]
+pub struct RayFaceHit {
+    /// The face that the ray hits
+    pub face: Handle<Face>,
+
+    /// The position of the hit along the ray
+    ///
+    /// See [`Ray::point_at`].
+    pub t: Scalar,
+
+    /// The point where the ray hits the face, in surface coordinates
+    pub point_surface: Point<2>,
+
+    /// The point where the ray hits the face, in global coordinates
+    pub point_global: Point<3>,
+
+    /// The normal of the face at the point of the hit
+    ///
+    /// Points towards the front side of the face.
+    pub normal: Vector<3>,
+}
+
+impl RayFaceHit {
+    /// Use the hit as the location of a hole
+    pub fn hole_location(&self) -> HoleLocation {
+        HoleLocation {
+            face: &self.face,
+            position: self.point_surface,
+        }
+    }
+}
+
+/// Compute where a ray intersects a plane
+fn ray_plane(ray: &Ray, line: &Line<3>, v: &Vector<3>) -> Vec<Scalar> {
+    let normal = line.direction().cross(v);
+
+    let direction_along_normal = ray.direction.dot(&normal);
+    if direction_along_normal == Scalar::ZERO {
+        // ray is parallel to plane
+        return Vec::new();
+    }
+
+    vec![(line.origin() - ray.origin).dot(&normal) / direction_along_normal]
+}
+
+/// Compute where a ray intersects a surface that is swept from a circle
+///
+/// This works by projecting the ray along the direction of the sweep, into the
+/// plane of the circle. Where the projected ray intersects the circle, the
+/// original ray intersects the surface.
+fn ray_swept_circle(
+    ray: &Ray,
+    circle: &Circle<3>,
+    v: &Vector<3>,
+) -> Vec<Scalar> {
+    let normal = circle.a().cross(&circle.b());
+
+    let v_along_normal = v.dot(&normal);
+    if v_along_normal == Scalar::ZERO {
+        // surface is degenerate
+        return Vec::new();
+    }
+
+    let project =
+        |vector: Vector<3>| vector - *v * vector.dot(&normal) / v_along_normal;
+    let origin = project(ray.origin - circle.center());
+    let direction = project(ray.direction);
+
+    // Solve `|origin + direction * t| = radius` for `t`.
+    let a = direction.dot(&direction);
+    let b = origin.dot(&direction) * 2.;
+    let c = origin.dot(&origin) - circle.radius() * circle.radius();
+
+    if a == Scalar::ZERO {
+        // ray is parallel to the sweep
+        return Vec::new();
+    }
+
+    let discriminant = b * b - a * c * 4.;
+    if discriminant < Scalar::ZERO {
+        return Vec::new();
+    }
+
+    let root = Scalar::from(discriminant.into_f64().sqrt());
+    vec![(-b - root) / (a * 2.), (-b + root) / (a * 2.)]
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Point, Scalar, Vector};
+
+    use crate::{
+        algorithms::intersect::Ray,
+        operations::{
+            build::{BuildCycle, BuildRegion, BuildSketch},
+            reverse::Reverse,
+            sweep::SweepSketch,
+            update::{UpdateRegion, UpdateSketch},
+        },
+        topology::{Cycle, Region, Sketch, Solid},
+        Core,
+    };
+
+    use super::CastRay;
+
+    fn sweep(region: Region, core: &mut Core) -> Solid {
+        Sketch::empty(&core.layers.topology)
+            .add_regions([region], core)
+            .sweep_sketch(
+                core.layers.topology.surfaces.xy_plane(),
+                [0., 0., 1.],
+                core,
+            )
+    }
+
+    fn ray(origin: [f64; 3], direction: [f64; 3]) -> Ray {
+        Ray {
+            origin: origin.into(),
+            direction: direction.into(),
+        }
+    }
+
+    fn is_near(a: impl Into<Point<3>>, b: impl Into<Point<3>>) -> bool {
+        a.into().distance_to(&b.into()) < Scalar::from(1e-9)
+    }
+
+    #[test]
+    fn cuboid() {
+        let mut core = Core::new();
+
+        let region = Region::polygon(
+            [[0., 0.], [1., 0.], [1., 1.], [0., 1.]],
+            core.layers.topology.surfaces.space_2d(),
+            &mut core,
+        );
+        let cuboid = sweep(region, &mut core);
+
+        let hit = cuboid
+            .cast_ray(
+                &ray([0.25, 0.5, 5.], [0., 0., -2.]),
+                0.001,
+                &core.layers.geometry,
+            )
+            .unwrap();
+        assert_eq!(hit.t, Scalar::from(2.));
+        assert!(is_near(hit.point_global, [0.25, 0.5, 1.]));
+        assert_eq!(hit.normal, Vector::from([0., 0., 1.]));
+
+        // The hit is on the top face, which is the only face that contains it.
+        let surface = core.layers.geometry.of_surface(hit.face.surface());
+        assert!(is_near(
+            surface.point_from_surface_coords(hit.point_surface),
+            hit.point_global,
+        ));
+
+        let miss = ray([0.25, 0.5, 5.], [0., 0., 1.]);
+        assert!(cuboid
+            .cast_ray(&miss, 0.001, &core.layers.geometry)
+            .is_none());
+        let miss = ray([2., 0.5, 5.], [0., 0., -1.]);
+        assert!(cuboid
+            .cast_ray(&miss, 0.001, &core.layers.geometry)
+            .is_none());
+    }
+
+    #[test]
+    fn cylinder_with_hole() {
+        let mut core = Core::new();
+
+        let surface = core.layers.topology.surfaces.space_2d();
+        let region = Region::circle([0., 0.], 2., surface.clone(), &mut core)
+            .add_interiors(
+                [Cycle::circle([0., 0.], 1., surface, &mut core)
+                    .reverse(&mut core)],
+                &mut core,
+            );
+        let spacer = sweep(region, &mut core);
+
+        // Hit the outside of the outer cylinder.
+        let hit = spacer
+            .cast_ray(
+                &ray([-5., 1.2, 0.5], [1., 0., 0.]),
+                0.001,
+                &core.layers.geometry,
+            )
+            .unwrap();
+        assert!(is_near(hit.point_global, [-1.6, 1.2, 0.5]));
+        assert!(is_near(Point::origin() + hit.normal, [-0.8, 0.6, 0.],));
+
+        // Hit the inner cylinder from within the hole. Its normal points into
+        // the hole, away from the solid.
+        let hit = spacer
+            .cast_ray(
+                &ray([0., 0.6, 0.5], [1., 0., 0.]),
+                0.001,
+                &core.layers.geometry,
+            )
+            .unwrap();
+        assert!(is_near(hit.point_global, [0.8, 0.6, 0.5]));
+        assert!(is_near(Point::origin() + hit.normal, [-0.8, -0.6, 0.],));
+    }
+}
@@ -0,0 +1,236 @@
+//! Intersection between a ray and a triangle in 3D
+
+use fj_math::{Point, Triangle};
+
+use super::{HorizontalRayToTheRight, Intersect};
+
+impl Intersect for (&HorizontalRayToTheRight<3>, &Triangle<3>) {
+    type Intersection = RayTriangleIntersection;
+
+    /// Compute the intersection between the ray and the triangle
+    ///
+    /// This is intended for determining whether a point is inside of a closed
+    /// triangle mesh, by counting how many triangles the ray hits. For this to
+    /// work, each hit must be counted exactly once.
+    ///
+    /// Rays that hit an edge or vertex (or that are parallel to a triangle)
+    /// would require special handling. Instead, the ray is treated as if its
+    /// origin was moved by an infinitesimal amount, in positive y and (even
+    /// less) positive z direction. This moved ray never hits an edge or vertex,
+    /// which means a hit is only attributed to one of the triangles that share
+    /// an edge or vertex, and triangles parallel to the ray are never hit.
+    fn intersect(self) -> Option<Self::Intersection> {
+        let (ray, triangle) = self;
+
+        let [a, b, c] = triangle.points();
+        let origin = ray.origin;
+
+        let orient3d = robust::orient3d(
+            coord_3d(a),
+            coord_3d(b),
+            coord_3d(c),
+            coord_3d(origin),
+        );
+
+        if orient3d == 0. {
+            // The ray starts in the plane of the triangle. Either it starts on
+            // the triangle, or the triangle doesn't contain the origin, and the
+            // moved ray misses it.
+            return contains_coplanar_point(triangle, origin)
+                .then_some(RayTriangleIntersection::RayStartsOnTriangle);
+        }
+
+        // Looking along the ray, we see the triangle projected into the
+        // yz-plane. This is the x-component of the triangle's normal.
+        let orientation =
+            robust::orient2d(coord_yz(a), coord_yz(b), coord_yz(c));
+        if orientation == 0. {
+            // ray is parallel to triangle
+            return None;
+        }
+
+        for [p, q] in [[a, b], [b, c], [c, a]] {
+            let side =
+                robust::orient2d(coord_yz(p), coord_yz(q), coord_yz(origin));
+
+            // If the origin is exactly on the line through the edge, the moved
+            // origin is on the side that the perturbation pushes it to.
+            let side = if side != 0. {
+                side
+            } else if p.z != q.z {
+                (p.z - q.z).into_f64()
+            } else {
+                (q.y - p.y).into_f64()
+            };
+
+            if side.signum() != orientation.signum() {
+                // ray passes the triangle
+                return None;
+            }
+        }
+
+        // `orient3d` is positive, if the origin is behind the triangle, as seen
+        // from the side its normal points to. In that case, the triangle is in
+        // front of the origin, if its normal points along the ray. Vice versa,
+        // if the origin is in front of the triangle.
+        if orient3d.signum() != orientation.signum() {
+            // triangle is behind the origin of the ray
+            return None;
+        }
+
+        Some(RayTriangleIntersection::RayHitsTriangle)
+    }
+}
+
+/// An intersection between a ray and a triangle
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RayTriangleIntersection {
+    /// The ray hits the triangle
+    RayHitsTriangle,
+
+    /// The ray starts on the triangle
+    RayStartsOnTriangle,
+}
+
+/// Determine whether a triangle contains a point in its plane
+///
+/// Includes the boundary of the triangle.
+fn contains_coplanar_point(triangle: &Triangle<3>, point: Point<3>) -> bool {
+    // Within the plane, the triangle and the point can be projected into any
+    // coordinate plane that the triangle isn't perpendicular to.
+    let projections: [fn(Point<3>) -> robust::Coord<f64>; 3] =
+        [coord_yz, coord_zx, coord_xy];
+
+    for project in projections {
+        let [a, b, c] = triangle.points().map(project);
+        let point = project(point);
+
+        let orientation = robust::orient2d(a, b, c);
+        if orientation == 0. {
+            continue;
+        }
+
+        return [[a, b], [b, c], [c, a]].into_iter().all(|[p, q]| {
+            let side = robust::orient2d(p, q, point);
+            side == 0. || side.signum() == orientation.signum()
+        });
+    }
+
+    unreachable!("Triangle spans an area; checked on construction")
+}
+
+fn coord_3d(point: Point<3>) -> robust::Coord3D<f64> {
+    robust::Coord3D {
+        x: point.x.into_f64(),
+        y: point.y.into_f64(),
+        z: point.z.into_f64(),
+    }
+}
+
+fn coord_yz(point: Point<3>) -> robust::Coord<f64> {
+    robust::Coord {
+        x: point.y.into_f64(),
+        y: point.z.into_f64(),
+    }
+}
+
+fn coord_zx(point: Point<3>) -> robust::Coord<f64> {
+    robust::Coord {
+        x: point.z.into_f64(),
+        y: point.x.into_f64(),
+    }
+}
+
+fn coord_xy(point: Point<3>) -> robust::Coord<f64> {
+    robust::Coord {
+        x: point.x.into_f64(),
+        y: point.y.into_f64(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::Triangle;
+
+    use crate::algorithms::intersect::Intersect;
+
+    use super::{HorizontalRayToTheRight, RayTriangleIntersection};
+
+    #[test]
+    fn ray_hits_triangle() {
+        let ray = HorizontalRayToTheRight::from([0., 1., 1.]);
+
+        let front = Triangle::from([[1., 0., 0.], [1., 3., 0.], [1., 0., 3.]]);
+        let back =
+            Triangle::from([[-1., 0., 0.], [-1., 3., 0.], [-1., 0., 3.]]);
+        let beside = Triangle::from([[1., 2., 2.], [1., 5., 2.], [1., 2., 5.]]);
+
+        assert_eq!(
+            (&ray, &front).intersect(),
+            Some(RayTriangleIntersection::RayHitsTriangle)
+        );
+        assert!((&ray, &back).intersect().is_none());
+        assert!((&ray, &beside).intersect().is_none());
+    }
+
+    #[test]
+    fn ray_starts_on_triangle() {
+        let ray = HorizontalRayToTheRight::from([1., 1., 1.]);
+
+        let inside = Triangle::from([[1., 0., 0.], [1., 3., 0.], [1., 0., 3.]]);
+        let on_edge =
+            Triangle::from([[1., 1., 0.], [1., 1., 3.], [1., 3., 0.]]);
+        let on_vertex =
+            Triangle::from([[1., 1., 1.], [1., 3., 1.], [1., 1., 3.]]);
+
+        for triangle in [inside, on_edge, on_vertex] {
+            assert_eq!(
+                (&ray, &triangle).intersect(),
+                Some(RayTriangleIntersection::RayStartsOnTriangle)
+            );
+        }
+    }
+
+    #[test]
+    fn ray_is_parallel_to_triangle() {
+        let ray = HorizontalRayToTheRight::from([0., 0., 0.]);
+
+        let in_plane =
+            Triangle::from([[1., -1., 0.], [2., 1., 0.], [3., -1., 0.]]);
+        let above =
+            Triangle::from([[1., -1., 1.], [2., 1., 1.], [3., -1., 1.]]);
+
+        assert!((&ray, &in_plane).intersect().is_none());
+        assert!((&ray, &above).intersect().is_none());
+    }
+
+    #[test]
+    fn ray_hits_shared_edge_once() {
+        let ray = HorizontalRayToTheRight::from([0., 1., 1.]);
+
+        // Two triangles that form a square, with the ray hitting the diagonal.
+        let a = Triangle::from([[1., 0., 0.], [1., 2., 0.], [1., 2., 2.]]);
+        let b = Triangle::from([[1., 0., 0.], [1., 2., 2.], [1., 0., 2.]]);
+
+        let hits = [a, b]
+            .iter()
+            .filter(|triangle| (&ray, *triangle).intersect().is_some())
+            .count();
+        assert_eq!(hits, 1);
+    }
+
+    #[test]
+    fn ray_hits_shared_vertex_once() {
+        let ray = HorizontalRayToTheRight::from([0., 0., 0.]);
+
+        // A fan of triangles around the point where the ray hits.
+        let center = [1., 0., 0.];
+        let rim = [[1., 1., 0.], [1., 0., 1.], [1., -1., 0.], [1., 0., -1.]];
+
+        let hits = (0..rim.len())
+            .map(|i| Triangle::from([center, rim[i], rim[(i + 1) % rim.len()]]))
+            .filter(|triangle| (&ray, triangle).intersect().is_some())
+            .count();
+        assert_eq!(hits, 1);
+    }
+}
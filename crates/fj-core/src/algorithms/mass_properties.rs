@@ -0,0 +1,237 @@
+//! Compute mass properties of an object
+//!
+//! The computation is based on the triangulated boundary of the object, using
+//! the divergence theorem. This is exact for objects whose faces are planar
+//! and bounded by straight edges. Curved faces and edges are approximated,
+//! and the result is only as accurate as the provided tolerance.
+
+use fj_math::{Point, Scalar, Vector};
+
+use crate::geometry::Geometry;
+
+use super::{
+    approx::{face::FaceApprox, Approx, Tolerance},
+    triangulate::triangulate_face,
+};
+
+/// Compute the mass properties of an object
+///
+/// Implemented for all objects whose approximation is a collection of faces,
+/// like [`Solid`] and [`Shell`]. The result is only meaningful, if those faces
+/// form a closed surface, with their front sides facing outward.
+///
+/// [`Shell`]: crate::topology::Shell
+/// [`Solid`]: crate::topology::Solid
+pub trait ComputeMassProperties {
+    /// Compute the mass properties of the object
+    ///
+    /// `tolerance` defines how far the approximation of curved faces is allowed
+    /// to deviate from the actual object.
+    fn mass_properties(
+        self,
+        tolerance: impl Into<Tolerance>,
+        geometry: &Geometry,
+    ) -> MassProperties;
+}
+
+impl<T> ComputeMassProperties for T
+where
+    T: Approx,
+    T::Approximation: IntoIterator<Item = FaceApprox>,
+{
+    fn mass_properties(
+        self,
+        tolerance: impl Into<Tolerance>,
+        geometry: &Geometry,
+    ) -> MassProperties {
+        let triangles = self
+            .approx(tolerance, geometry)
+            .into_iter()
+            .flat_map(triangulate_face)
+            .map(|triangle| triangle.map(|point| point.point_global));
+
+        MassProperties::from_triangles(triangles)
+    }
+}
+
+/// The mass properties of an object
+///
+/// All values that depend on mass assume a uniform density of `1`. Multiply
+/// them by the density of the material to get the actual values.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MassProperties {
+    /// The volume that the object encloses
+    pub volume: Scalar,
+
+    /// The area of the object's surface
+    pub surface_area: Scalar,
+
+    /// The centroid of the enclosed volume, which is the center of mass
+    ///
+    /// This is the origin, if the object doesn't enclose any volume.
+    pub centroid: Point<3>,
+
+    /// The inertia tensor, relative to the centroid
+    ///
+    /// The rows and columns refer to the x, y and z axes, in that order.
+    pub inertia: [[Scalar; 3]; 3],
+}
+
+impl MassProperties {
+    /// Compute mass properties from the triangles that bound a volume
+    ///
+    /// The triangles need to be wound counter-clockwise, as seen from outside
+    /// of the volume.
+    pub fn from_triangles(
+        triangles: impl IntoIterator<Item = [Point<3>; 3]>,
+    ) -> Self {
+        let mut volume = Scalar::ZERO;
+        let mut surface_area = Scalar::ZERO;
+        let mut first_moment = Vector::from([0., 0., 0.]);
+        let mut second_moment = [[Scalar::ZERO; 3]; 3];
+
+        for [a, b, c] in triangles {
+            surface_area += (b - a).cross(&(c - a)).magnitude() / 2.;
+
+            // Each triangle forms a tetrahedron with the origin. The integrals
+            // over the tetrahedra add up to the integral over the volume, as
+            // those parts of the tetrahedra that lie outside of the volume
+            // cancel each other out.
+            let [a, b, c] = [a, b, c].map(|point| point.coords);
+            let det = a.dot(&b.cross(&c));
+            let sum = a + b + c;
+
+            volume += det / 6.;
+            first_moment = first_moment + sum * det / 24.;
+
+            for (i, row) in second_moment.iter_mut().enumerate() {
+                for (j, element) in row.iter_mut().enumerate() {
+                    let products = a.components[i] * a.components[j]
+                        + b.components[i] * b.components[j]
+                        + c.components[i] * c.components[j]
+                        + sum.components[i] * sum.components[j];
+                    *element += products * det / 120.;
+                }
+            }
+        }
+
+        let centroid = if volume == Scalar::ZERO {
+            Point::origin()
+        } else {
+            Point {
+                coords: first_moment / volume,
+            }
+        };
+
+        // Move the second moment from the origin to the centroid, then derive
+        // the inertia tensor from it.
+        let mut covariance = second_moment;
+        for (i, row) in covariance.iter_mut().enumerate() {
+            for (j, element) in row.iter_mut().enumerate() {
+                *element -= volume
+                    * centroid.coords.components[i]
+                    * centroid.coords.components[j];
+            }
+        }
+        let trace = covariance[0][0] + covariance[1][1] + covariance[2][2];
+
+        let mut inertia = covariance;
+        for (i, row) in inertia.iter_mut().enumerate() {
+            for (j, element) in row.iter_mut().enumerate() {
+                *element = if i == j { trace - *element } else { -*element };
+            }
+        }
+
+        Self {
+            volume,
+            surface_area,
+            centroid,
+            inertia,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Point, Scalar};
+
+    use crate::{
+        operations::{
+            build::{BuildRegion, BuildSketch},
+            sweep::SweepSketch,
+            update::UpdateSketch,
+        },
+        topology::{Region, Sketch, Solid},
+        Core,
+    };
+
+    use super::ComputeMassProperties;
+
+    fn sweep(region: Region, height: f64, core: &mut Core) -> Solid {
+        Sketch::empty(&core.layers.topology)
+            .add_regions([region], core)
+            .sweep_sketch(
+                core.layers.topology.surfaces.xy_plane(),
+                [0., 0., height],
+                core,
+            )
+    }
+
+    #[test]
+    fn cuboid() {
+        let mut core = Core::new();
+
+        let region = Region::polygon(
+            [[0., 0.], [1., 0.], [1., 2.], [0., 2.]],
+            core.layers.topology.surfaces.space_2d(),
+            &mut core,
+        );
+        let cuboid = sweep(region, 3., &mut core);
+
+        let properties = cuboid.mass_properties(0.01, &core.layers.geometry);
+
+        let eq = |a: Scalar, b: f64| (a - b).abs() < Scalar::from(1e-12);
+        assert!(eq(properties.volume, 6.));
+        assert!(eq(properties.surface_area, 22.));
+        let centroid = Point::from([0.5, 1., 1.5]);
+        assert!(
+            properties.centroid.distance_to(&centroid) < Scalar::from(1e-12)
+        );
+
+        // Inertia of a cuboid with mass `m` and side lengths `a`, `b`, and
+        // `c`, around the axis parallel to `c`: `m * (a² + b²) / 12`.
+        let expected = [[6.5, 0., 0.], [0., 5., 0.], [0., 0., 2.5]];
+        for (row, expected) in properties.inertia.iter().zip(expected) {
+            for (&element, expected) in row.iter().zip(expected) {
+                assert!(eq(element, expected), "{element} != {expected}");
+            }
+        }
+    }
+
+    #[test]
+    fn cylinder() {
+        let mut core = Core::new();
+
+        let radius = 1.;
+        let height = 2.;
+
+        let region = Region::circle(
+            [0., 0.],
+            radius,
+            core.layers.topology.surfaces.space_2d(),
+            &mut core,
+        );
+        let cylinder = sweep(region, height, &mut core);
+
+        let properties = cylinder.mass_properties(0.001, &core.layers.geometry);
+
+        let volume = Scalar::PI * radius * radius * height;
+        let relative_error = (properties.volume - volume).abs() / volume;
+        assert!(relative_error < Scalar::from(0.01));
+
+        let centroid = Point::from([0., 0., height / 2.]);
+        assert!(
+            properties.centroid.distance_to(&centroid) < Scalar::from(1e-9)
+        );
+    }
+}
@@ -1,14 +1,14 @@
 //! Shape triangulation
 
 mod delaunay;
-mod polygon;
+pub(crate) mod polygon;
 
 use fj_interop::Mesh;
 use fj_math::Point;
 
-use crate::{operations::presentation::GetColor, Core};
+use crate::{operations::presentation::GetColor, topology::Handedness, Core};
 
-use self::polygon::Polygon;
+use self::{delaunay::TriangulationPoint, polygon::Polygon};
 
 use super::approx::{face::FaceApprox, Approx, Tolerance};
 
@@ -46,48 +46,65 @@ where
 
 impl Triangulate for FaceApprox {
     fn triangulate_into_mesh(self, mesh: &mut Mesh<Point<3>>, core: &mut Core) {
-        let face_as_polygon = Polygon::new()
-            .with_exterior(
-                self.exterior
-                    .points()
-                    .into_iter()
-                    .map(|point| point.local_form),
-            )
-            .with_interiors(self.interiors.iter().map(|interior| {
-                interior.points().into_iter().map(|point| point.local_form)
-            }));
-
-        let cycles = [self.exterior].into_iter().chain(self.interiors);
-        let mut triangles =
-            delaunay::triangulate(cycles, self.coord_handedness);
-        triangles.retain(|triangle| {
-            face_as_polygon
-                .contains_triangle(triangle.map(|point| point.point_surface))
-        });
-
         let color = self.face.region().get_color(core).unwrap_or_default();
+        let surface = core.layers.geometry.of_surface(self.face.surface());
+        let coord_handedness = self.coord_handedness;
 
-        for triangle in triangles {
+        for triangle in triangulate_face(self) {
             let points = triangle.map(|point| point.point_global);
-            mesh.push_triangle(points, color);
+
+            // The surface normal points towards the front side of the surface,
+            // as defined by its coordinate system. That isn't necessarily the
+            // front side of the face.
+            let normals = triangle.map(|point| {
+                let normal = surface.normal_at(point.point_surface);
+                match coord_handedness {
+                    Handedness::RightHanded => normal,
+                    Handedness::LeftHanded => -normal,
+                }
+            });
+
+            mesh.push_triangle_with_normals(points, normals, color);
         }
     }
 }
 
+/// Triangulate the approximation of a face
+///
+/// The triangles are wound counter-clockwise, as seen from the front side of
+/// the face.
+pub(crate) fn triangulate_face(
+    approx: FaceApprox,
+) -> Vec<[TriangulationPoint; 3]> {
+    let face_as_polygon = Polygon::from_face_approx(&approx);
+
+    let cycles = [approx.exterior].into_iter().chain(approx.interiors);
+    let mut triangles =
+        delaunay::triangulate(cycles, approx.inner, approx.coord_handedness);
+    triangles.retain(|triangle| {
+        face_as_polygon
+            .contains_triangle(triangle.map(|point| point.point_surface))
+    });
+
+    triangles
+}
+
 #[cfg(test)]
 mod tests {
+    use std::f64::consts::{FRAC_PI_4, PI};
+
     use fj_interop::Mesh;
     use fj_math::{Point, Scalar};
 
     use crate::{
         algorithms::approx::{Approx, Tolerance},
         operations::{
-            build::{BuildCycle, BuildFace},
+            build::{BuildCycle, BuildFace, BuildSurface},
             insert::Insert,
             update::{UpdateFace, UpdateRegion},
         },
         storage::Handle,
-        topology::{Cycle, Face},
+        topology::{Cycle, Face, Surface},
         Core,
     };
 
@@ -304,6 +321,55 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn spherical_face() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let surface = Surface::sphere(Point::origin(), 10., &mut core);
+        let face = Face::polygon(
+            surface,
+            [
+                [0., FRAC_PI_4],
+                [PI, FRAC_PI_4],
+                [PI, FRAC_PI_4 * 3.],
+                [0., FRAC_PI_4 * 3.],
+            ],
+            &mut core,
+        )
+        .insert(&mut core);
+
+        let triangles = triangulate(face, &mut core)?;
+
+        // The edges of the face don't define the curvature of the sphere, so
+        // the triangulation must include points within them.
+        let within = Point::from([0., 10., 0.]);
+        assert!(triangles
+            .vertices()
+            .any(|vertex| (vertex - within).magnitude() < Scalar::from(1e-9)));
+
+        for vertex in triangles.vertices() {
+            let distance = (vertex - Point::origin()).magnitude();
+            assert!((distance - Scalar::from(10.)).abs() < Scalar::from(1e-9));
+        }
+
+        // The normals are those of the sphere, not of the triangles. They
+        // still point to the same side as the triangles, though.
+        for triangle in triangles.triangles() {
+            for (point, normal) in
+                triangle.inner.points().into_iter().zip(triangle.normals)
+            {
+                let radial = (point - Point::origin()).normalize();
+                assert!(
+                    (normal.dot(&radial).abs() - Scalar::ONE).abs()
+                        < Scalar::from(1e-9)
+                );
+                assert!(normal.dot(&triangle.inner.normal()) > Scalar::ZERO);
+            }
+        }
+
+        Ok(())
+    }
+
     fn triangulate(
         face: Handle<Face>,
         core: &mut Core,
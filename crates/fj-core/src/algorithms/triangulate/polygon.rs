@@ -1,8 +1,11 @@
 use fj_interop::ext::SliceExt;
 use fj_math::{Point, PolyChain, Segment, Triangle};
 
-use crate::algorithms::intersect::{
-    ray_segment::RaySegmentIntersection, HorizontalRayToTheRight, Intersect,
+use crate::algorithms::{
+    approx::face::FaceApprox,
+    intersect::{
+        ray_segment::RaySegmentIntersection, HorizontalRayToTheRight, Intersect,
+    },
 };
 
 #[derive(Default)]
@@ -17,6 +20,23 @@ impl Polygon {
         Self::default()
     }
 
+    /// Construct a polygon from the boundary of an approximated face
+    ///
+    /// The polygon is defined in the surface coordinates of the face.
+    pub fn from_face_approx(approx: &FaceApprox) -> Self {
+        Self::new()
+            .with_exterior(
+                approx
+                    .exterior
+                    .points()
+                    .into_iter()
+                    .map(|point| point.local_form),
+            )
+            .with_interiors(approx.interiors.iter().map(|interior| {
+                interior.points().into_iter().map(|point| point.local_form)
+            }))
+    }
+
     pub fn with_exterior(mut self, exterior: impl Into<PolyChain<2>>) -> Self {
         self.exterior = exterior.into();
         self
@@ -113,7 +133,7 @@ impl Polygon {
     /// This code is being duplicated by the `Contains<Point<2>>` implementation
     /// for `Face`. It would be nice to be able to consolidate the duplication,
     /// but this has turned out to be difficult.
-    fn contains_point(&self, point: impl Into<Point<2>>) -> bool {
+    pub fn contains_point(&self, point: impl Into<Point<2>>) -> bool {
         let ray = HorizontalRayToTheRight {
             origin: point.into(),
         };
@@ -10,6 +10,7 @@ use crate::{
 use super::{CurveGeom, GlobalPath, HalfEdgeGeom, LocalCurveGeom, SurfaceGeom};
 
 /// Geometric data that is associated with topological objects
+#[derive(Clone)]
 pub struct Geometry {
     curve: BTreeMap<Handle<Curve>, CurveGeom>,
     half_edge: BTreeMap<Handle<HalfEdge>, HalfEdgeGeom>,
@@ -39,24 +40,15 @@ impl Geometry {
 
         self_.define_surface_inner(
             self_.xy_plane.clone(),
-            SurfaceGeom {
-                u: GlobalPath::x_axis(),
-                v: Vector::unit_y(),
-            },
+            SurfaceGeom::swept(GlobalPath::x_axis(), Vector::unit_y()),
         );
         self_.define_surface_inner(
             self_.xz_plane.clone(),
-            SurfaceGeom {
-                u: GlobalPath::x_axis(),
-                v: Vector::unit_z(),
-            },
+            SurfaceGeom::swept(GlobalPath::x_axis(), Vector::unit_z()),
         );
         self_.define_surface_inner(
             self_.yz_plane.clone(),
-            SurfaceGeom {
-                u: GlobalPath::y_axis(),
-                v: Vector::unit_z(),
-            },
+            SurfaceGeom::swept(GlobalPath::y_axis(), Vector::unit_z()),
         );
 
         self_
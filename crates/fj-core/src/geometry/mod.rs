@@ -13,5 +13,5 @@ pub use self::{
     geometry::Geometry,
     half_edge::HalfEdgeGeom,
     path::{GlobalPath, SurfacePath},
-    surface::SurfaceGeom,
+    surface::{RevolvedCurve, SurfaceGeom, SweptCurve},
 };
@@ -2,16 +2,19 @@
 //!
 //! See [`SurfacePath`] and [`GlobalPath`].
 
-use fj_math::{Circle, Line, Point, Scalar, Transform, Vector};
+use fj_math::{Circle, Line, Point, Scalar, Spline, Transform, Vector};
 
 /// A path through surface (2D) space
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
 pub enum SurfacePath {
     /// A circle
     Circle(Circle<2>),
 
     /// A line
     Line(Line<2>),
+
+    /// A spline
+    Spline(Spline<2>),
 }
 
 impl SurfacePath {
@@ -66,6 +69,19 @@ impl SurfacePath {
         match self {
             Self::Circle(circle) => circle.point_from_circle_coords(point),
             Self::Line(line) => line.point_from_line_coords(point),
+            Self::Spline(spline) => spline.point_from_spline_coords(point),
+        }
+    }
+
+    /// Compute the tangent of the path at the provided path coordinates
+    ///
+    /// The tangent is the derivative of the path with respect to its
+    /// coordinate, which means its magnitude is not normalized.
+    pub fn tangent_at(&self, point: impl Into<Point<1>>) -> Vector<2> {
+        match self {
+            Self::Circle(circle) => circle_tangent_at(circle, point),
+            Self::Line(line) => line.direction(),
+            Self::Spline(spline) => spline.tangent_at(point),
         }
     }
 
@@ -75,18 +91,22 @@ impl SurfacePath {
         match self {
             Self::Circle(circle) => Self::Circle(circle.reverse()),
             Self::Line(line) => Self::Line(line.reverse()),
+            Self::Spline(spline) => Self::Spline(spline.reverse()),
         }
     }
 }
 
 /// A path through global (3D) space
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
 pub enum GlobalPath {
     /// A circle
     Circle(Circle<3>),
 
     /// A line
     Line(Line<3>),
+
+    /// A spline
+    Spline(Spline<3>),
 }
 
 impl GlobalPath {
@@ -136,6 +156,7 @@ impl GlobalPath {
         match self {
             Self::Circle(circle) => circle.center() + circle.a(),
             Self::Line(line) => line.origin(),
+            Self::Spline(spline) => spline.point_from_spline_coords([0.]),
         }
     }
 
@@ -147,6 +168,7 @@ impl GlobalPath {
         match self {
             Self::Circle(circle) => circle.point_from_circle_coords(point),
             Self::Line(line) => line.point_from_line_coords(point),
+            Self::Spline(spline) => spline.point_from_spline_coords(point),
         }
     }
 
@@ -158,6 +180,22 @@ impl GlobalPath {
         match self {
             Self::Circle(circle) => circle.vector_from_circle_coords(vector),
             Self::Line(line) => line.vector_from_line_coords(vector),
+            Self::Spline(spline) => {
+                spline.point_from_spline_coords([vector.into().t])
+                    - self.origin()
+            }
+        }
+    }
+
+    /// Compute the tangent of the path at the provided path coordinates
+    ///
+    /// The tangent is the derivative of the path with respect to its
+    /// coordinate, which means its magnitude is not normalized.
+    pub fn tangent_at(&self, point: impl Into<Point<1>>) -> Vector<3> {
+        match self {
+            Self::Circle(circle) => circle_tangent_at(circle, point),
+            Self::Line(line) => line.direction(),
+            Self::Spline(spline) => spline.tangent_at(point),
         }
     }
 
@@ -169,6 +207,18 @@ impl GlobalPath {
                 Self::Circle(transform.transform_circle(&curve))
             }
             Self::Line(curve) => Self::Line(transform.transform_line(&curve)),
+            Self::Spline(curve) => {
+                Self::Spline(transform.transform_spline(&curve))
+            }
         }
     }
 }
+
+fn circle_tangent_at<const D: usize>(
+    circle: &Circle<D>,
+    point: impl Into<Point<1>>,
+) -> Vector<D> {
+    // The derivative of a circle is the circle itself, a quarter turn ahead.
+    let t = point.into().t + Scalar::PI / Scalar::TWO;
+    circle.vector_from_circle_coords([t])
+}
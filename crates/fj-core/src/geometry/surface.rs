@@ -1,12 +1,150 @@
 //! The geometry that defines a surface
 
-use fj_math::{Line, Plane, Point, Transform, Vector};
+use fj_math::{Circle, Line, Plane, Point, Scalar, Transform, Vector};
 
-use super::GlobalPath;
+use super::{GlobalPath, SurfacePath};
 
 /// The geometry that defines a surface
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
-pub struct SurfaceGeom {
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub enum SurfaceGeom {
+    /// A surface that is created by sweeping a curve along a straight path
+    ///
+    /// This covers planes and extruded surfaces, like cylinders.
+    Swept(SweptCurve),
+
+    /// A surface that is created by revolving a profile around an axis
+    ///
+    /// This covers spheres, tori, and general surfaces of revolution.
+    Revolved(RevolvedCurve),
+}
+
+impl SurfaceGeom {
+    /// Construct a surface by sweeping `u` along `v`
+    pub fn swept(u: impl Into<GlobalPath>, v: impl Into<Vector<3>>) -> Self {
+        Self::Swept(SweptCurve {
+            u: u.into(),
+            v: v.into(),
+        })
+    }
+
+    /// Construct a sphere
+    ///
+    /// See [`RevolvedCurve::sphere`].
+    pub fn sphere(
+        center: impl Into<Point<3>>,
+        radius: impl Into<Scalar>,
+    ) -> Self {
+        Self::Revolved(RevolvedCurve::sphere(center, radius))
+    }
+
+    /// Construct a torus
+    ///
+    /// See [`RevolvedCurve::torus`].
+    pub fn torus(
+        center: impl Into<Point<3>>,
+        axis: impl Into<Vector<3>>,
+        major_radius: impl Into<Scalar>,
+        minor_radius: impl Into<Scalar>,
+    ) -> Self {
+        Self::Revolved(RevolvedCurve::torus(
+            center,
+            axis,
+            major_radius,
+            minor_radius,
+        ))
+    }
+
+    /// Convert a point in surface coordinates to model coordinates
+    pub fn point_from_surface_coords(
+        &self,
+        point: impl Into<Point<2>>,
+    ) -> Point<3> {
+        match self {
+            Self::Swept(surface) => surface.point_from_surface_coords(point),
+            Self::Revolved(surface) => surface.point_from_surface_coords(point),
+        }
+    }
+
+    /// Convert a vector in surface coordinates to model coordinates
+    ///
+    /// The conversion of a vector depends on where on the surface it is
+    /// located, unless the surface is a plane. That location is provided as
+    /// `point`, in surface coordinates.
+    pub fn vector_from_surface_coords(
+        &self,
+        point: impl Into<Point<2>>,
+        vector: impl Into<Vector<2>>,
+    ) -> Vector<3> {
+        let vector = vector.into();
+        let [du, dv] = self.derivatives_at(point);
+
+        du * vector.u + dv * vector.v
+    }
+
+    /// Compute the normal of the surface at the provided surface coordinates
+    ///
+    /// The normal points towards the side of the surface that the u- and
+    /// v-axis define as the front, according to the right-hand rule.
+    pub fn normal_at(&self, point: impl Into<Point<2>>) -> Vector<3> {
+        match self {
+            Self::Swept(_) => {
+                let [du, dv] = self.derivatives_at(point);
+                du.cross(&dv).normalize()
+            }
+            Self::Revolved(surface) => surface.normal_at(point),
+        }
+    }
+
+    /// Project the global point into the surface
+    ///
+    /// See [`SweptCurve::project_global_point`] and
+    /// [`RevolvedCurve::project_global_point`].
+    pub fn project_global_point(&self, point: impl Into<Point<3>>) -> Point<2> {
+        match self {
+            Self::Swept(surface) => surface.project_global_point(point),
+            Self::Revolved(surface) => surface.project_global_point(point),
+        }
+    }
+
+    /// Transform the surface geometry
+    #[must_use]
+    pub fn transform(self, transform: &Transform) -> Self {
+        match self {
+            Self::Swept(surface) => Self::Swept(surface.transform(transform)),
+            Self::Revolved(surface) => {
+                Self::Revolved(surface.transform(transform))
+            }
+        }
+    }
+
+    /// Compute the partial derivatives of the surface, along u and v
+    fn derivatives_at(&self, point: impl Into<Point<2>>) -> [Vector<3>; 2] {
+        let point = point.into();
+
+        match self {
+            Self::Swept(surface) => {
+                [surface.u.tangent_at([point.u]), surface.v]
+            }
+            Self::Revolved(surface) => surface.derivatives_at(point),
+        }
+    }
+}
+
+impl From<SweptCurve> for SurfaceGeom {
+    fn from(surface: SweptCurve) -> Self {
+        Self::Swept(surface)
+    }
+}
+
+impl From<RevolvedCurve> for SurfaceGeom {
+    fn from(surface: RevolvedCurve) -> Self {
+        Self::Revolved(surface)
+    }
+}
+
+/// A surface that is created by sweeping a curve along a straight path
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct SweptCurve {
     /// The u-axis of the surface
     pub u: GlobalPath,
 
@@ -14,7 +152,7 @@ pub struct SurfaceGeom {
     pub v: Vector<3>,
 }
 
-impl SurfaceGeom {
+impl SweptCurve {
     /// Convert a point in surface coordinates to model coordinates
     pub fn point_from_surface_coords(
         &self,
@@ -40,14 +178,77 @@ impl SurfaceGeom {
     }
 
     /// Project the global point into the surface
+    ///
+    /// If the u-axis of the surface is a line, the surface is a plane, and the
+    /// point is projected orthogonally.
+    ///
+    /// If the u-axis is a circle, the surface is the result of sweeping that
+    /// circle along the v-axis. The point is moved along the v-axis into the
+    /// plane of the circle, then projected onto the circle. For a cylinder,
+    /// where the v-axis is perpendicular to the circle, this results in the
+    /// closest point on the surface. For an oblique cylinder, it is exact only
+    /// for points that are on the surface.
+    ///
+    /// If the u-axis is a spline, the point is moved along the v-axis in the
+    /// same way, and the closest point on the spline is found numerically.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the u-axis is a circle and the v-axis lies within the plane
+    /// of that circle, as the surface is degenerate in that case.
     pub fn project_global_point(&self, point: impl Into<Point<3>>) -> Point<2> {
-        let GlobalPath::Line(line) = self.u else {
-            todo!("Projecting point into non-plane surface is not supported")
-        };
+        let point = point.into();
+
+        match &self.u {
+            GlobalPath::Line(line) => {
+                let plane = Plane::from_parametric(
+                    line.origin(),
+                    line.direction(),
+                    self.v,
+                );
+                plane.project_point(point)
+            }
+            GlobalPath::Circle(circle) => {
+                let normal = circle.a().cross(&circle.b()).normalize();
+
+                let v_along_normal = self.v.dot(&normal);
+                assert_ne!(
+                    v_along_normal,
+                    Scalar::ZERO,
+                    "Can't project point into degenerate surface"
+                );
+
+                let v = (point - circle.center()).dot(&normal) / v_along_normal;
 
-        let plane =
-            Plane::from_parametric(line.origin(), line.direction(), self.v);
-        plane.project_point(point)
+                let in_circle_plane = point - self.v * v;
+                let offset = in_circle_plane - circle.center();
+
+                let u = offset.dot(&circle.b()).atan2(offset.dot(&circle.a()));
+                let u = if u < Scalar::ZERO { u + Scalar::TAU } else { u };
+
+                Point::from([u, v])
+            }
+            GlobalPath::Spline(spline) => {
+                // Same as with the circle, the point is moved along the v-axis
+                // to find its u-coordinate. Here, that is done by projecting
+                // both the point and the spline into the plane perpendicular
+                // to the v-axis.
+                let v_squared = self.v.dot(&self.v);
+                let along_v = |point: Point<3>| point.coords.dot(&self.v);
+                let project = |point: Point<3>| {
+                    point - self.v * along_v(point) / v_squared
+                };
+
+                let u = spline
+                    .map_control_points(project)
+                    .point_to_spline_coords(project(point));
+                let v = (point - spline.point_from_spline_coords(u))
+                    .dot(&self.v)
+                    / v_squared;
+
+                Point::from([u.t, v])
+            }
+        }
     }
 
     /// Transform the surface geometry
@@ -59,16 +260,221 @@ impl SurfaceGeom {
     }
 }
 
+/// A surface that is created by revolving a profile around an axis
+///
+/// The profile is a path within the half-plane that is bounded by the axis and
+/// extends into the direction of `radial`. Its first coordinate is the
+/// distance from the axis, in units of `radial`; its second coordinate is the
+/// position along the axis, in units of the axis direction. The distance from
+/// the axis must not be negative.
+///
+/// The u-coordinate of the surface is the angle of rotation around the axis,
+/// according to the right-hand rule, starting at `radial`. The v-coordinate is
+/// the coordinate on the profile.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct RevolvedCurve {
+    /// The axis that the profile is revolved around
+    pub axis: Line<3>,
+
+    /// The direction from the axis to the profile, where the u-coordinate is 0
+    ///
+    /// Must be perpendicular to the axis.
+    pub radial: Vector<3>,
+
+    /// The profile that is revolved around the axis
+    pub profile: SurfacePath,
+}
+
+impl RevolvedCurve {
+    /// Construct a sphere
+    ///
+    /// The axis of the sphere points along the z-axis. The v-coordinate ranges
+    /// from `0` at the bottom pole to `PI` at the top pole, and the normal of
+    /// the sphere points outwards.
+    pub fn sphere(
+        center: impl Into<Point<3>>,
+        radius: impl Into<Scalar>,
+    ) -> Self {
+        let radius = radius.into();
+
+        Self {
+            axis: Line::from_origin_and_direction(
+                center.into(),
+                Vector::unit_z(),
+            ),
+            radial: Vector::unit_x(),
+            profile: SurfacePath::Circle(Circle::new(
+                [0., 0.],
+                [Scalar::ZERO, -radius],
+                [radius, Scalar::ZERO],
+            )),
+        }
+    }
+
+    /// Construct a torus
+    ///
+    /// The v-coordinate starts at the outer equator of the torus, and the
+    /// normal of the torus points outwards.
+    ///
+    /// The u-coordinate starts in the direction of the x-, y-, or z-axis that
+    /// is least parallel to the axis of the torus (preferring the x-axis),
+    /// projected into the equatorial plane.
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the axis is zero.
+    pub fn torus(
+        center: impl Into<Point<3>>,
+        axis: impl Into<Vector<3>>,
+        major_radius: impl Into<Scalar>,
+        minor_radius: impl Into<Scalar>,
+    ) -> Self {
+        let axis = axis.into();
+        let minor_radius = minor_radius.into();
+
+        assert_ne!(
+            axis.magnitude(),
+            Scalar::ZERO,
+            "Can't construct torus with a zero axis"
+        );
+        let axis = axis.normalize();
+
+        // Pick the unit vector that is least parallel to the axis as the
+        // reference for the radial direction. Projecting it into the
+        // equatorial plane can then never result in a zero vector.
+        let reference = [Vector::unit_x(), Vector::unit_y(), Vector::unit_z()]
+            .into_iter()
+            .min_by_key(|unit| axis.dot(unit).abs())
+            .expect("Array is not empty");
+        let radial = reference - axis * axis.dot(&reference);
+
+        Self {
+            axis: Line::from_origin_and_direction(center.into(), axis),
+            radial: radial.normalize(),
+            profile: SurfacePath::Circle(Circle::new(
+                [major_radius.into(), Scalar::ZERO],
+                [minor_radius, Scalar::ZERO],
+                [Scalar::ZERO, minor_radius],
+            )),
+        }
+    }
+
+    /// Convert a point in surface coordinates to model coordinates
+    pub fn point_from_surface_coords(
+        &self,
+        point: impl Into<Point<2>>,
+    ) -> Point<3> {
+        let point = point.into();
+        let profile = self.profile.point_from_path_coords([point.v]);
+
+        self.axis.origin()
+            + self.radial_at(point.u) * profile.u
+            + self.axis.direction() * profile.v
+    }
+
+    /// Compute the normal of the surface at the provided surface coordinates
+    ///
+    /// The normal is well-defined on the axis too, where the surface is
+    /// singular, as long as the profile doesn't cross the axis there.
+    pub fn normal_at(&self, point: impl Into<Point<2>>) -> Vector<3> {
+        let point = point.into();
+
+        // The derivative along u is proportional to the distance from the
+        // axis, so it vanishes on the axis. Using a vector that is parallel to
+        // it, but not scaled by that distance, avoids that singularity.
+        let tangential = self.axis_unit().cross(&self.radial_at(point.u));
+        let [_, dv] = self.derivatives_at(point);
+
+        tangential.cross(&dv).normalize()
+    }
+
+    /// Project the global point into the surface
+    ///
+    /// The point is rotated around the axis into the half-plane of the
+    /// profile, then projected onto the profile. This results in the closest
+    /// point on the surface, if the profile is a line or a circle.
+    pub fn project_global_point(&self, point: impl Into<Point<3>>) -> Point<2> {
+        let axis = self.axis_unit();
+        let offset = point.into() - self.axis.origin();
+
+        let along_axis = offset.dot(&axis);
+        let from_axis = offset - axis * along_axis;
+
+        let u = from_axis
+            .dot(&axis.cross(&self.radial))
+            .atan2(from_axis.dot(&self.radial));
+        let u = if u < Scalar::ZERO { u + Scalar::TAU } else { u };
+
+        let in_profile = Point::from([
+            from_axis.magnitude() / self.radial.magnitude(),
+            along_axis / self.axis.direction().magnitude(),
+        ]);
+
+        let v = match &self.profile {
+            SurfacePath::Circle(circle) => {
+                let offset = in_profile - circle.center();
+
+                let v = offset.dot(&circle.b()).atan2(offset.dot(&circle.a()));
+                if v < Scalar::ZERO {
+                    v + Scalar::TAU
+                } else {
+                    v
+                }
+            }
+            SurfacePath::Line(line) => line.point_to_line_coords(in_profile).t,
+            SurfacePath::Spline(spline) => {
+                spline.point_to_spline_coords(in_profile).t
+            }
+        };
+
+        Point::from([u, v])
+    }
+
+    /// Transform the surface geometry
+    #[must_use]
+    pub fn transform(self, transform: &Transform) -> Self {
+        Self {
+            axis: transform.transform_line(&self.axis),
+            radial: transform.transform_vector(&self.radial),
+            profile: self.profile,
+        }
+    }
+
+    fn derivatives_at(&self, point: Point<2>) -> [Vector<3>; 2] {
+        let profile = self.profile.point_from_path_coords([point.v]);
+        let tangent = self.profile.tangent_at([point.v]);
+
+        let radial = self.radial_at(point.u);
+
+        let du = self.axis_unit().cross(&radial) * profile.u;
+        let dv = radial * tangent.u + self.axis.direction() * tangent.v;
+
+        [du, dv]
+    }
+
+    /// The direction from the axis to the profile, rotated by `angle`
+    fn radial_at(&self, angle: Scalar) -> Vector<3> {
+        let (sin, cos) = angle.sin_cos();
+        self.radial * cos + self.axis_unit().cross(&self.radial) * sin
+    }
+
+    fn axis_unit(&self) -> Vector<3> {
+        self.axis.direction().normalize()
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use fj_math::{Line, Point, Vector};
+    use std::f64::consts::{FRAC_PI_2, PI};
+
+    use fj_math::{Circle, Line, Point, Scalar, Transform, Vector};
     use pretty_assertions::assert_eq;
 
-    use crate::geometry::{GlobalPath, SurfaceGeom};
+    use crate::geometry::{GlobalPath, SurfaceGeom, SweptCurve};
 
     #[test]
     fn point_from_surface_coords() {
-        let surface = SurfaceGeom {
+        let surface = SweptCurve {
             u: GlobalPath::Line(Line::from_origin_and_direction(
                 Point::from([1., 1., 1.]),
                 Vector::from([0., 2., 0.]),
@@ -82,9 +488,29 @@ mod tests {
         );
     }
 
+    #[test]
+    fn torus_with_any_axis() {
+        let center = Point::from([1., 2., 3.]);
+
+        for axis in [Vector::unit_x(), Vector::unit_y(), Vector::unit_z()]
+            .into_iter()
+            .chain([Vector::from([1., 1., 1.])])
+        {
+            let torus = SurfaceGeom::torus(center, axis, 2., 1.);
+
+            let point = torus.point_from_surface_coords([0., 0.]);
+            let epsilon = Scalar::from(1e-12);
+            assert!(
+                ((point - center).magnitude() - Scalar::from(3.)).abs()
+                    < epsilon
+            );
+            assert!((point - center).dot(&axis).abs() < epsilon);
+        }
+    }
+
     #[test]
     fn vector_from_surface_coords() {
-        let surface = SurfaceGeom {
+        let surface = SweptCurve {
             u: GlobalPath::Line(Line::from_origin_and_direction(
                 Point::from([1., 0., 0.]),
                 Vector::from([0., 2., 0.]),
@@ -97,4 +523,163 @@ mod tests {
             Vector::from([0., 4., 8.]),
         );
     }
+
+    #[test]
+    fn project_global_point_into_plane() {
+        let surface = SweptCurve {
+            u: GlobalPath::Line(Line::from_origin_and_direction(
+                Point::from([1., 0., 0.]),
+                Vector::from([0., 2., 0.]),
+            )),
+            v: Vector::from([0., 0., 2.]),
+        };
+
+        assert_eq!(
+            surface.project_global_point([3., 4., 8.]),
+            Point::from([2., 4.]),
+        );
+    }
+
+    #[test]
+    fn project_global_point_into_cylinder() {
+        let surface = SweptCurve {
+            u: GlobalPath::Circle(Circle::new(
+                [0., 0., 1.],
+                [2., 0., 0.],
+                [0., 2., 0.],
+            )),
+            v: Vector::from([0., 0., 2.]),
+        };
+
+        // A point on the surface is projected to its own surface coordinates.
+        let coords = Point::from([FRAC_PI_2 * 3., 1.5]);
+        let point = surface.point_from_surface_coords(coords);
+        let projected = surface.project_global_point(point);
+        assert!((projected - coords).magnitude() < Scalar::from(1e-12));
+
+        // A point outside of the surface is projected onto the closest point.
+        let projected = surface.project_global_point([0., 5., 0.]);
+        assert!(
+            (projected - Point::from([FRAC_PI_2, -0.5])).magnitude()
+                < Scalar::from(1e-12)
+        );
+    }
+
+    #[test]
+    fn project_global_point_into_oblique_cylinder() {
+        let surface = SweptCurve {
+            u: GlobalPath::Circle(Circle::new(
+                [0., 0., 0.],
+                [1., 0., 0.],
+                [0., 1., 0.],
+            )),
+            v: Vector::from([1., 1., 1.]),
+        };
+
+        let coords = Point::from([1., 2.]);
+        let point = surface.point_from_surface_coords(coords);
+
+        let projected = surface.project_global_point(point);
+        assert!((projected - coords).magnitude() < Scalar::from(1e-12));
+    }
+
+    #[test]
+    fn sphere_point_and_normal() {
+        let surface = SurfaceGeom::sphere([1., 2., 3.], 2.);
+
+        let assert_point_and_normal =
+            |coords: [f64; 2], point: [f64; 3], normal: [f64; 3]| {
+                assert!(
+                    (surface.point_from_surface_coords(coords)
+                        - Point::from(point))
+                    .magnitude()
+                        < Scalar::from(1e-12)
+                );
+                assert!(
+                    (surface.normal_at(coords) - Vector::from(normal))
+                        .magnitude()
+                        < Scalar::from(1e-12)
+                );
+            };
+
+        assert_point_and_normal([0., 0.], [1., 2., 1.], [0., 0., -1.]);
+        assert_point_and_normal(
+            [FRAC_PI_2, FRAC_PI_2],
+            [1., 4., 3.],
+            [0., 1., 0.],
+        );
+        assert_point_and_normal([0., PI], [1., 2., 5.], [0., 0., 1.]);
+    }
+
+    #[test]
+    fn torus_point_and_normal() {
+        let surface = SurfaceGeom::torus([0., 0., 0.], [0., 0., 1.], 2., 0.5);
+
+        let assert_point_and_normal =
+            |coords: [f64; 2], point: [f64; 3], normal: [f64; 3]| {
+                assert!(
+                    (surface.point_from_surface_coords(coords)
+                        - Point::from(point))
+                    .magnitude()
+                        < Scalar::from(1e-12)
+                );
+                assert!(
+                    (surface.normal_at(coords) - Vector::from(normal))
+                        .magnitude()
+                        < Scalar::from(1e-12)
+                );
+            };
+
+        assert_point_and_normal([0., 0.], [2.5, 0., 0.], [1., 0., 0.]);
+        assert_point_and_normal([0., FRAC_PI_2], [2., 0., 0.5], [0., 0., 1.]);
+        assert_point_and_normal([PI, PI], [-1.5, 0., 0.], [1., 0., 0.]);
+    }
+
+    #[test]
+    fn vector_from_sphere_coords() {
+        let surface = SurfaceGeom::sphere([0., 0., 0.], 2.);
+
+        let vector =
+            surface.vector_from_surface_coords([0., FRAC_PI_2], [1., 1.]);
+        assert!(
+            (vector - Vector::from([0., 2., 2.])).magnitude()
+                < Scalar::from(1e-12)
+        );
+    }
+
+    #[test]
+    fn project_global_point_into_torus() {
+        let surface = SurfaceGeom::torus([1., 1., 1.], [1., 1., 0.], 2., 0.5);
+
+        // A point on the surface is projected to its own surface coordinates.
+        let coords = Point::from([FRAC_PI_2 * 3., 1.5]);
+        let point = surface.point_from_surface_coords(coords);
+        let projected = surface.project_global_point(point);
+        assert!((projected - coords).magnitude() < Scalar::from(1e-12));
+
+        // A point outside of the surface is projected onto the closest point.
+        let coords = Point::from([FRAC_PI_2, 0.5]);
+        let outside = surface.point_from_surface_coords(coords)
+            + surface.normal_at(coords);
+        let projected = surface.project_global_point(outside);
+        assert!((projected - coords).magnitude() < Scalar::from(1e-12));
+    }
+
+    #[test]
+    fn transform_revolved_surface() {
+        let surface = SurfaceGeom::sphere([0., 0., 0.], 1.);
+        let transform = Transform::translation([1., 2., 3.])
+            * Transform::rotation(Vector::from([0., 0., FRAC_PI_2]));
+
+        let transformed = surface.clone().transform(&transform);
+
+        let coords = [FRAC_PI_2, 1.];
+        let expected = transform
+            .transform_point(&surface.point_from_surface_coords(coords));
+        assert!(
+            (transformed.point_from_surface_coords(coords) - expected)
+                .magnitude()
+                < Scalar::from(1e-12)
+        );
+    }
 }
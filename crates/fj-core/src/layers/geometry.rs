@@ -17,7 +17,7 @@ impl Layer<Geometry> {
         geometry: LocalCurveGeom,
     ) {
         let mut events = Vec::new();
-        self.process(
+        self.process_and_record(
             DefineCurve {
                 curve,
                 surface,
@@ -34,7 +34,7 @@ impl Layer<Geometry> {
         geometry: HalfEdgeGeom,
     ) {
         let mut events = Vec::new();
-        self.process(
+        self.process_and_record(
             DefineHalfEdge {
                 half_edge,
                 geometry,
@@ -55,15 +55,24 @@ impl Layer<Geometry> {
         geometry: SurfaceGeom,
     ) {
         let mut events = Vec::new();
-        self.process(DefineSurface { surface, geometry }, &mut events);
+        self.process_and_record(
+            DefineSurface { surface, geometry },
+            &mut events,
+        );
     }
 }
 
 /// Define the geometry of a curve
+#[derive(Clone)]
 pub struct DefineCurve {
-    curve: Handle<Curve>,
-    surface: Handle<Surface>,
-    geometry: LocalCurveGeom,
+    /// The curve to define the geometry for
+    pub curve: Handle<Curve>,
+
+    /// The surface that the geometry is defined in
+    pub surface: Handle<Surface>,
+
+    /// The geometry of the curve, local to the surface
+    pub geometry: LocalCurveGeom,
 }
 
 impl Command<Geometry> for DefineCurve {
@@ -90,9 +99,13 @@ impl Event<Geometry> for DefineCurve {
 }
 
 /// Define the geometry of a half-edge
+#[derive(Clone)]
 pub struct DefineHalfEdge {
-    half_edge: Handle<HalfEdge>,
-    geometry: HalfEdgeGeom,
+    /// The half-edge to define the geometry for
+    pub half_edge: Handle<HalfEdge>,
+
+    /// The geometry of the half-edge
+    pub geometry: HalfEdgeGeom,
 }
 
 impl Command<Geometry> for DefineHalfEdge {
@@ -110,14 +123,21 @@ impl Command<Geometry> for DefineHalfEdge {
 
 impl Event<Geometry> for DefineHalfEdge {
     fn evolve(&self, state: &mut Geometry) {
-        state.define_half_edge_inner(self.half_edge.clone(), self.geometry);
+        state.define_half_edge_inner(
+            self.half_edge.clone(),
+            self.geometry.clone(),
+        );
     }
 }
 
 /// Define the geometry of a surface
+#[derive(Clone)]
 pub struct DefineSurface {
-    surface: Handle<Surface>,
-    geometry: SurfaceGeom,
+    /// The surface to define the geometry for
+    pub surface: Handle<Surface>,
+
+    /// The geometry of the surface
+    pub geometry: SurfaceGeom,
 }
 
 impl Command<Geometry> for DefineSurface {
@@ -135,6 +155,6 @@ impl Command<Geometry> for DefineSurface {
 
 impl Event<Geometry> for DefineSurface {
     fn evolve(&self, state: &mut Geometry) {
-        state.define_surface_inner(self.surface.clone(), self.geometry);
+        state.define_surface_inner(self.surface.clone(), self.geometry.clone());
     }
 }
@@ -0,0 +1,253 @@
+use std::any::{self, Any};
+
+use super::Event;
+
+/// The recorded events of a layer
+///
+/// Recording is enabled per layer, using [`Layer::record_history`]. From then
+/// on, every event that is processed using [`Layer::process_and_record`] is
+/// recorded. This serves as
+/// an audit trail of all changes, and makes it possible to restore the state
+/// at an earlier [`Checkpoint`] (undo), or to replay the events after it
+/// again (redo).
+///
+/// [`Layer::record_history`]: super::Layer::record_history
+/// [`Layer::process_and_record`]: super::Layer::process_and_record
+pub struct History<S> {
+    base: S,
+    clone_state: fn(&S) -> S,
+
+    events: Vec<RecordedEvent<S>>,
+    position: usize,
+    next_serial: u64,
+}
+
+impl<S> History<S> {
+    pub(super) fn new(state: &S, clone_state: fn(&S) -> S) -> Self {
+        Self {
+            base: clone_state(state),
+            clone_state,
+            events: Vec::new(),
+            position: 0,
+            next_serial: 0,
+        }
+    }
+
+    /// Access all recorded events, in the order they were recorded
+    ///
+    /// This includes events that have been undone by restoring an earlier
+    /// checkpoint. Only the events before [`History::position`] are currently
+    /// applied to the layer's state.
+    pub fn events(&self) -> &[RecordedEvent<S>] {
+        &self.events
+    }
+
+    /// The number of recorded events that are currently applied
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Create a checkpoint that represents the current state
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint {
+            position: self.position,
+            last_serial: self.last_serial(self.position),
+        }
+    }
+
+    /// Indicate whether the state that the checkpoint represents can be restored
+    ///
+    /// This is not the case, if the checkpoint refers to events that have been
+    /// discarded.
+    pub fn can_restore(&self, checkpoint: Checkpoint) -> bool {
+        checkpoint.position <= self.events.len()
+            && checkpoint.last_serial == self.last_serial(checkpoint.position)
+    }
+
+    pub(super) fn record<E>(&mut self, events: impl IntoIterator<Item = E>)
+    where
+        E: Event<S> + Send + 'static,
+    {
+        // Recording new events after restoring an earlier checkpoint starts a
+        // new branch of history. The undone events can no longer be replayed.
+        self.events.truncate(self.position);
+
+        for event in events {
+            self.events.push(RecordedEvent {
+                event: Box::new(event),
+                type_name: any::type_name::<E>(),
+                serial: self.next_serial,
+            });
+            self.next_serial += 1;
+        }
+
+        self.position = self.events.len();
+    }
+
+    pub(super) fn restore(
+        &mut self,
+        checkpoint: Checkpoint,
+        state: &mut S,
+    ) -> Result<(), RestoreError> {
+        if !self.can_restore(checkpoint) {
+            return Err(RestoreError::EventsDiscarded);
+        }
+
+        // Events can only be applied, not reverted. To go back, we need to
+        // start over from the state before the first event.
+        let start = if checkpoint.position < self.position {
+            *state = (self.clone_state)(&self.base);
+            0
+        } else {
+            self.position
+        };
+
+        for recorded in &self.events[start..checkpoint.position] {
+            recorded.event.evolve(state);
+        }
+
+        self.position = checkpoint.position;
+
+        Ok(())
+    }
+
+    fn last_serial(&self, position: usize) -> Option<u64> {
+        position
+            .checked_sub(1)
+            .map(|index| self.events[index].serial)
+    }
+}
+
+/// An event that was recorded by [`History`]
+pub struct RecordedEvent<S> {
+    event: Box<dyn AnyEvent<S> + Send>,
+    type_name: &'static str,
+    serial: u64,
+}
+
+impl<S> RecordedEvent<S> {
+    /// The name of the event's type
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// Access the event, if it is of the provided type
+    pub fn downcast_ref<E: 'static>(&self) -> Option<&E> {
+        self.event.as_any().downcast_ref()
+    }
+}
+
+/// A point in the history of a layer, that its state can be restored to
+///
+/// Created by [`History::checkpoint`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Checkpoint {
+    position: usize,
+    last_serial: Option<u64>,
+}
+
+/// Error restoring a [`Checkpoint`]
+#[derive(Debug, thiserror::Error)]
+pub enum RestoreError {
+    /// Checkpoint refers to events that are no longer recorded
+    ///
+    /// This happens, if the checkpoint was created after an earlier checkpoint
+    /// that was restored, and new events have been recorded since.
+    #[error("Checkpoint refers to events that are no longer recorded")]
+    EventsDiscarded,
+
+    /// Layer doesn't record its history
+    #[error("Layer doesn't record its history")]
+    NotRecording,
+}
+
+/// An event whose concrete type is erased
+///
+/// Allows for recording events of different types, while still providing
+/// access to the concrete type.
+trait AnyEvent<S>: Event<S> {
+    fn as_any(&self) -> &dyn Any;
+}
+
+impl<S, E> AnyEvent<S> for E
+where
+    E: Event<S> + 'static,
+{
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::layers::{Command, Event, Layer};
+
+    use super::RestoreError;
+
+    #[derive(Clone)]
+    struct Push(u32);
+
+    impl Command<Vec<u32>> for Push {
+        type Result = ();
+        type Event = Self;
+
+        fn decide(self, _: &Vec<u32>, events: &mut Vec<Self::Event>) {
+            events.push(self);
+        }
+    }
+
+    impl Event<Vec<u32>> for Push {
+        fn evolve(&self, state: &mut Vec<u32>) {
+            state.push(self.0);
+        }
+    }
+
+    #[test]
+    fn undo_and_redo() {
+        let mut layer = Layer::new(vec![0]);
+        layer.record_history();
+
+        layer.process_and_record(Push(1), &mut Vec::new());
+        let a = layer.checkpoint().unwrap();
+        layer.process_and_record(Push(2), &mut Vec::new());
+        let b = layer.checkpoint().unwrap();
+
+        layer.restore(a).unwrap();
+        assert_eq!(*layer, [0, 1]);
+        layer.restore(b).unwrap();
+        assert_eq!(*layer, [0, 1, 2]);
+
+        // Recording new events after an undo discards the undone ones.
+        layer.restore(a).unwrap();
+        layer.process_and_record(Push(3), &mut Vec::new());
+        assert_eq!(*layer, [0, 1, 3]);
+        assert!(matches!(
+            layer.restore(b),
+            Err(RestoreError::EventsDiscarded)
+        ));
+
+        let events = layer
+            .history()
+            .unwrap()
+            .events()
+            .iter()
+            .map(|event| event.downcast_ref::<Push>().unwrap().0)
+            .collect::<Vec<_>>();
+        assert_eq!(events, [1, 3]);
+    }
+
+    #[test]
+    fn not_recording() {
+        let mut layer = Layer::new(vec![0]);
+        assert!(layer.checkpoint().is_none());
+
+        layer.record_history();
+        let checkpoint = layer.checkpoint().unwrap();
+
+        let mut other = Layer::new(vec![0]);
+        assert!(matches!(
+            other.restore(checkpoint),
+            Err(RestoreError::NotRecording)
+        ));
+    }
+}
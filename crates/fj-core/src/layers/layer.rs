@@ -1,4 +1,9 @@
-use std::ops::Deref;
+use std::{any::Any, ops::Deref};
+
+use super::{
+    history::{Checkpoint, History, RestoreError},
+    subscriptions::{Subscribers, Subscription},
+};
 
 /// A generic layer, which controls access to layer state
 ///
@@ -10,23 +15,38 @@ use std::ops::Deref;
 /// processed by [`Layer::process`]. Processing a command can result in any
 /// number of events, which can then be used as commands for other layers.
 ///
+/// Optionally, a layer can record the events that it processes using
+/// [`Layer::process_and_record`]. See [`Layer::record_history`].
+///
+/// External code can react to changes as they happen, by subscribing to the
+/// events that a layer processes. See [`Layer::subscribe`].
+///
 /// This design takes inspiration from, and uses the nomenclature of, this
 /// article:
 /// <https://thinkbeforecoding.com/post/2021/12/17/functional-event-sourcing-decider>
 pub struct Layer<S> {
     state: S,
+    history: Option<History<S>>,
+    subscribers: Subscribers,
 }
 
 impl<S> Layer<S> {
     /// Create an instance of `Layer`
     pub fn new(state: S) -> Self {
-        Self { state }
+        Self {
+            state,
+            history: None,
+            subscribers: Subscribers::default(),
+        }
     }
 
     /// Process a command
     ///
     /// The command is processed synchronously. When this method returns, the
     /// state has been updated.
+    ///
+    /// The resulting events are neither recorded, nor passed to subscribers.
+    /// Use [`Layer::process_and_record`] for that.
     pub fn process<C>(
         &mut self,
         command: C,
@@ -37,13 +57,106 @@ impl<S> Layer<S> {
     {
         let result = command.decide(&self.state, events);
 
-        for event in events {
+        for event in events.iter() {
             event.evolve(&mut self.state);
         }
 
         result
     }
 
+    /// Process a command, then record the resulting events and publish them
+    ///
+    /// Works like [`Layer::process`]. In addition, the events are recorded, if
+    /// this layer records its history, and passed to all subscribers.
+    ///
+    /// Events that are processed using [`Layer::process`] instead, can't be
+    /// replayed when restoring a checkpoint.
+    pub fn process_and_record<C>(
+        &mut self,
+        command: C,
+        events: &mut Vec<C::Event>,
+    ) -> C::Result
+    where
+        C: Command<S>,
+        C::Event: Clone + Send + 'static,
+    {
+        let result = self.process(command, events);
+
+        if let Some(history) = &mut self.history {
+            history.record(events.iter().cloned());
+        }
+
+        for event in events.iter() {
+            self.subscribers.notify(event);
+        }
+
+        result
+    }
+
+    /// Access the recorded history, if this layer records it
+    pub fn history(&self) -> Option<&History<S>> {
+        self.history.as_ref()
+    }
+
+    /// Create a checkpoint that represents the current state
+    ///
+    /// Returns `None`, if this layer doesn't record its history.
+    pub fn checkpoint(&self) -> Option<Checkpoint> {
+        self.history.as_ref().map(History::checkpoint)
+    }
+
+    /// Restore the state that the provided checkpoint represents
+    ///
+    /// This works for checkpoints that were created before the current state
+    /// (undo), as well as those that were created after it and have since been
+    /// restored away from (redo).
+    pub fn restore(
+        &mut self,
+        checkpoint: Checkpoint,
+    ) -> Result<(), RestoreError> {
+        let history =
+            self.history.as_mut().ok_or(RestoreError::NotRecording)?;
+        history.restore(checkpoint, &mut self.state)
+    }
+
+    /// Start recording the events that this layer processes
+    ///
+    /// The current state becomes the earliest state that can be restored. Does
+    /// nothing, if this layer already records its history.
+    pub fn record_history(&mut self)
+    where
+        S: Clone,
+    {
+        if self.history.is_none() {
+            self.history = Some(History::new(&self.state, S::clone));
+        }
+    }
+
+    /// Register a subscriber that is called with the events this layer processes
+    ///
+    /// Subscribers are called with every event that is processed using
+    /// [`Layer::process_and_record`], after the state has been updated, in the
+    /// order they were registered. They receive the event as [`Any`], and can
+    /// use [`downcast_ref`] to access the events they are interested in.
+    ///
+    /// Events that are replayed by [`Layer::restore`] are not passed to
+    /// subscribers again.
+    ///
+    /// [`downcast_ref`]: https://doc.rust-lang.org/std/any/trait.Any.html#method.downcast_ref
+    pub fn subscribe(
+        &mut self,
+        subscriber: impl FnMut(&dyn Any) + Send + 'static,
+    ) -> Subscription {
+        self.subscribers.add(subscriber)
+    }
+
+    /// Remove a subscriber that was registered using [`Layer::subscribe`]
+    ///
+    /// Returns `false`, if the subscriber had already been removed.
+    pub fn unsubscribe(&mut self, subscription: Subscription) -> bool {
+        self.subscribers.remove(subscription)
+    }
+
     /// Drop this instance, returning the wrapped state
     pub fn into_state(self) -> S {
         self.state
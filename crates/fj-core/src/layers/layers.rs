@@ -5,7 +5,7 @@ use crate::{
     validation::{Validation, ValidationConfig},
 };
 
-use super::Layer;
+use super::{Checkpoint, Layer, RestoreError};
 
 /// # Loosely coupled layers, that together define shapes
 ///
@@ -64,6 +64,62 @@ impl Layers {
             ..Self::new()
         }
     }
+
+    /// Start recording the history of the layers that support it
+    ///
+    /// Recording the history makes it possible to create checkpoints, and to
+    /// later restore the state that they represent. See [`Layers::checkpoint`]
+    /// and [`Layers::restore`].
+    ///
+    /// This affects the geometry and presentation layers. The topology and
+    /// validation layers are left alone, as the topology layer only ever adds
+    /// new objects, and the validation layer tracks errors of those.
+    ///
+    /// This means that handles to topological objects that were created after
+    /// a checkpoint still exist after restoring it, but their geometry is gone.
+    /// Such handles are invalid after the restore, and looking up their
+    /// geometry (for example using [`Geometry::of_half_edge`]) panics.
+    ///
+    /// [`Geometry::of_half_edge`]: crate::geometry::Geometry::of_half_edge
+    pub fn record_history(&mut self) {
+        self.geometry.record_history();
+        self.presentation.record_history();
+    }
+
+    /// Create a checkpoint that represents the current state of the layers
+    ///
+    /// Returns `None`, if the layers don't record their history.
+    pub fn checkpoint(&self) -> Option<LayersCheckpoint> {
+        Some(LayersCheckpoint {
+            geometry: self.geometry.checkpoint()?,
+            presentation: self.presentation.checkpoint()?,
+        })
+    }
+
+    /// Restore the state that the provided checkpoint represents
+    ///
+    /// See [`Layer::restore`]. If the checkpoint can't be restored, none of the
+    /// layers are changed.
+    pub fn restore(
+        &mut self,
+        checkpoint: LayersCheckpoint,
+    ) -> Result<(), RestoreError> {
+        let (Some(geometry), Some(presentation)) =
+            (self.geometry.history(), self.presentation.history())
+        else {
+            return Err(RestoreError::NotRecording);
+        };
+        if !geometry.can_restore(checkpoint.geometry)
+            || !presentation.can_restore(checkpoint.presentation)
+        {
+            return Err(RestoreError::EventsDiscarded);
+        }
+
+        self.geometry.restore(checkpoint.geometry)?;
+        self.presentation.restore(checkpoint.presentation)?;
+
+        Ok(())
+    }
 }
 
 impl Default for Layers {
@@ -71,3 +127,46 @@ impl Default for Layers {
         Self::new()
     }
 }
+
+/// A checkpoint that represents the state of all layers that record history
+///
+/// Created by [`Layers::checkpoint`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct LayersCheckpoint {
+    geometry: Checkpoint,
+    presentation: Checkpoint,
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_interop::Color;
+
+    use crate::{
+        operations::{build::BuildRegion, insert::Insert},
+        topology::Region,
+        Core,
+    };
+
+    #[test]
+    fn restore_checkpoint() {
+        let mut core = Core::new();
+        core.layers.record_history();
+
+        let region = Region::polygon(
+            [[0., 0.], [1., 0.], [0., 1.]],
+            core.layers.topology.surfaces.space_2d(),
+            &mut core,
+        )
+        .insert(&mut core);
+
+        let red = Color([255, 0, 0, 255]);
+        let blue = Color([0, 0, 255, 255]);
+
+        core.layers.presentation.set_color(region.clone(), red);
+        let checkpoint = core.layers.checkpoint().unwrap();
+        core.layers.presentation.set_color(region.clone(), blue);
+
+        core.layers.restore(checkpoint).unwrap();
+        assert_eq!(core.layers.presentation.color.get(&region), Some(&red));
+    }
+}
@@ -7,10 +7,14 @@ pub mod presentation;
 pub mod topology;
 pub mod validation;
 
+mod history;
 mod layer;
 mod layers;
+mod subscriptions;
 
 pub use self::{
+    history::{Checkpoint, History, RecordedEvent, RestoreError},
     layer::{Command, Event, Layer},
-    layers::Layers,
+    layers::{Layers, LayersCheckpoint},
+    subscriptions::Subscription,
 };
@@ -14,7 +14,7 @@ impl Layer<Presentation> {
     /// Set the color of a region
     pub fn set_color(&mut self, region: Handle<Region>, color: Color) {
         let mut events = Vec::new();
-        self.process(SetColor { region, color }, &mut events);
+        self.process_and_record(SetColor { region, color }, &mut events);
     }
 
     /// Mark an object as being derived from another
@@ -24,17 +24,21 @@ impl Layer<Presentation> {
         derived: AnyObject<Stored>,
     ) {
         let mut events = Vec::new();
-        self.process(DeriveObject { original, derived }, &mut events);
+        self.process_and_record(
+            DeriveObject { original, derived },
+            &mut events,
+        );
     }
 }
 
 /// Set the color of a region
+#[derive(Clone)]
 pub struct SetColor {
     /// The region to set the color for
-    region: Handle<Region>,
+    pub region: Handle<Region>,
 
     /// The color to set
-    color: Color,
+    pub color: Color,
 }
 
 impl Command<Presentation> for SetColor {
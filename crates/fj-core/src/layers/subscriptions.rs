@@ -0,0 +1,88 @@
+use std::any::Any;
+
+/// The subscribers of a layer, which are notified of every event it processes
+///
+/// Subscribers are registered using [`Layer::subscribe`].
+///
+/// [`Layer::subscribe`]: super::Layer::subscribe
+#[derive(Default)]
+pub(super) struct Subscribers {
+    subscribers: Vec<(Subscription, Box<Subscriber>)>,
+    next_id: u64,
+}
+
+impl Subscribers {
+    pub(super) fn add(
+        &mut self,
+        subscriber: impl FnMut(&dyn Any) + Send + 'static,
+    ) -> Subscription {
+        let subscription = Subscription(self.next_id);
+        self.next_id += 1;
+
+        self.subscribers.push((subscription, Box::new(subscriber)));
+
+        subscription
+    }
+
+    pub(super) fn remove(&mut self, subscription: Subscription) -> bool {
+        let num_subscribers = self.subscribers.len();
+        self.subscribers.retain(|(s, _)| *s != subscription);
+        self.subscribers.len() < num_subscribers
+    }
+
+    pub(super) fn notify(&mut self, event: &dyn Any) {
+        for (_, subscriber) in &mut self.subscribers {
+            subscriber(event);
+        }
+    }
+}
+
+type Subscriber = dyn FnMut(&dyn Any) + Send;
+
+/// Identifies a subscriber that has been registered with a layer
+///
+/// Returned by [`Layer::subscribe`], and can be passed to
+/// [`Layer::unsubscribe`].
+///
+/// [`Layer::subscribe`]: super::Layer::subscribe
+/// [`Layer::unsubscribe`]: super::Layer::unsubscribe
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct Subscription(u64);
+
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc;
+
+    use fj_interop::Color;
+
+    use crate::{
+        layers::presentation::SetColor,
+        operations::{build::BuildRegion, insert::Insert},
+        topology::Region,
+        Core,
+    };
+
+    #[test]
+    fn notify_subscribers() {
+        let mut core = Core::new();
+
+        let surface = core.layers.topology.surfaces.space_2d();
+        let region =
+            Region::circle([0., 0.], 1., surface, &mut core).insert(&mut core);
+
+        let (sender, receiver) = mpsc::channel();
+        let subscription = core.layers.presentation.subscribe(move |event| {
+            if let Some(SetColor { region, color }) = event.downcast_ref() {
+                sender.send((region.clone(), *color)).unwrap();
+            }
+        });
+
+        let red = Color([255, 0, 0, 255]);
+        core.layers.presentation.set_color(region.clone(), red);
+        assert_eq!(receiver.try_recv(), Ok((region.clone(), red)));
+
+        assert!(core.layers.presentation.unsubscribe(subscription));
+        core.layers.presentation.set_color(region, Color::default());
+        assert!(receiver.try_recv().is_err());
+    }
+}
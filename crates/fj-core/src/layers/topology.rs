@@ -19,14 +19,14 @@ impl Layer<Topology> {
         validation: &mut Layer<Validation>,
     ) {
         let mut events = Vec::new();
-        self.process(InsertObject { object }, &mut events);
+        self.process_and_record(InsertObject { object }, &mut events);
 
         for event in events {
             let event = ValidateObject {
                 object: event.object.into(),
                 geometry,
             };
-            validation.process(event, &mut Vec::new());
+            validation.process_and_record(event, &mut Vec::new());
         }
     }
 }
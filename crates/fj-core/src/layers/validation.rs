@@ -3,7 +3,10 @@
 use crate::{
     geometry::Geometry,
     topology::{AnyObject, Stored},
-    validation::{Validation, ValidationError, ValidationErrors},
+    validation::{
+        ObjectValidationError, Severity, Validation, ValidationError,
+        ValidationErrors,
+    },
 };
 
 use super::{Command, Event, Layer};
@@ -11,7 +14,15 @@ use super::{Command, Event, Layer};
 impl Layer<Validation> {
     /// Take all errors stored in the validation layer
     pub fn take_errors(&mut self) -> Result<(), ValidationErrors> {
-        self.process(TakeErrors, &mut Vec::new())
+        self.process_and_record(TakeErrors, &mut Vec::new())
+    }
+
+    /// Take all warnings stored in the validation layer
+    ///
+    /// Warnings are validation errors, whose kind has been configured to have
+    /// [`Severity::Warning`].
+    pub fn take_warnings(&mut self) -> Vec<ObjectValidationError> {
+        self.process_and_record(TakeWarnings, &mut Vec::new())
     }
 }
 
@@ -34,9 +45,15 @@ impl Command<Validation> for ValidateObject<'_> {
             .validate(&state.config, &mut errors, self.geometry);
 
         for err in errors {
+            let severity = state.config.severity(err.kind());
+            if severity == Severity::Off {
+                continue;
+            }
+
             events.push(ValidationFailed {
                 object: self.object.clone(),
                 err,
+                severity,
             });
         }
     }
@@ -45,6 +62,7 @@ impl Command<Validation> for ValidateObject<'_> {
 /// Take all errors stored in the validation layer
 ///
 /// Serves both as a command for and event produced by `Layer<Validation>`.
+#[derive(Clone)]
 pub struct TakeErrors;
 
 impl Command<Validation> for TakeErrors {
@@ -56,7 +74,9 @@ impl Command<Validation> for TakeErrors {
         state: &Validation,
         events: &mut Vec<Self::Event>,
     ) -> Self::Result {
-        let errors = ValidationErrors(state.errors.values().cloned().collect());
+        let errors = ValidationErrors(
+            state.errors.values().flatten().cloned().collect(),
+        );
 
         events.push(self);
 
@@ -74,6 +94,32 @@ impl Event<Validation> for TakeErrors {
     }
 }
 
+/// Take all warnings stored in the validation layer
+///
+/// Serves both as a command for and event produced by `Layer<Validation>`.
+#[derive(Clone)]
+pub struct TakeWarnings;
+
+impl Command<Validation> for TakeWarnings {
+    type Result = Vec<ObjectValidationError>;
+    type Event = Self;
+
+    fn decide(
+        self,
+        state: &Validation,
+        events: &mut Vec<Self::Event>,
+    ) -> Self::Result {
+        events.push(self);
+        state.warnings.values().flatten().cloned().collect()
+    }
+}
+
+impl Event<Validation> for TakeWarnings {
+    fn evolve(&self, state: &mut Validation) {
+        state.warnings.clear();
+    }
+}
+
 /// Validation of an object failed
 ///
 /// Event produced by `Layer<Validation>`.
@@ -84,10 +130,34 @@ pub struct ValidationFailed {
 
     /// The validation error
     pub err: ValidationError,
+
+    /// The severity of the validation error
+    ///
+    /// This is never [`Severity::Off`], as those errors are discarded.
+    pub severity: Severity,
 }
 
 impl Event<Validation> for ValidationFailed {
     fn evolve(&self, state: &mut Validation) {
-        state.errors.insert(self.object.id(), self.err.clone());
+        let err = ObjectValidationError {
+            object: self.object.clone(),
+            err: self.err.clone(),
+        };
+
+        match self.severity {
+            Severity::Error => {
+                state.errors.entry(self.object.id()).or_default().push(err);
+            }
+            Severity::Warning => {
+                state
+                    .warnings
+                    .entry(self.object.id())
+                    .or_default()
+                    .push(err);
+            }
+            Severity::Off => {
+                unreachable!("Validation errors that are off are discarded");
+            }
+        }
     }
 }
@@ -85,6 +85,7 @@ pub mod algorithms;
 pub mod geometry;
 pub mod layers;
 pub mod operations;
+pub mod persist;
 pub mod presentation;
 pub mod queries;
 pub mod storage;
@@ -0,0 +1,218 @@
+use fj_math::Scalar;
+
+use crate::{
+    operations::insert::Insert,
+    storage::Handle,
+    topology::{HalfEdge, Shell, Solid},
+    Core,
+};
+
+use super::{cut_wedges, BlendError, BlendedEdge};
+
+/// # Chamfer edges
+///
+/// See [module documentation] for more information.
+///
+/// [module documentation]: super
+pub trait Chamfer: Sized {
+    /// # Chamfer the provided edges
+    ///
+    /// Each edge is identified by one of its half-edges. The chamfer meets
+    /// both faces adjacent to an edge at the provided distance from it.
+    ///
+    /// Returns an error, if any of the edges is not supported, as described in
+    /// the module documentation, or if the distance is too large for the faces
+    /// adjacent to an edge. See [`BlendError`].
+    fn chamfer(
+        &self,
+        edges: impl IntoIterator<Item = Handle<HalfEdge>>,
+        distance: impl Into<Scalar>,
+        core: &mut Core,
+    ) -> Result<Self, BlendError>;
+}
+
+impl Chamfer for Solid {
+    fn chamfer(
+        &self,
+        edges: impl IntoIterator<Item = Handle<HalfEdge>>,
+        distance: impl Into<Scalar>,
+        core: &mut Core,
+    ) -> Result<Self, BlendError> {
+        let distance = distance.into();
+
+        let edges = BlendedEdge::collect(self, edges, |_| distance, core)?;
+        cut_wedges(self, &edges, core)
+    }
+}
+
+impl Chamfer for Shell {
+    fn chamfer(
+        &self,
+        edges: impl IntoIterator<Item = Handle<HalfEdge>>,
+        distance: impl Into<Scalar>,
+        core: &mut Core,
+    ) -> Result<Self, BlendError> {
+        let shell = self.clone().insert(core);
+        Ok(Solid::new([shell])
+            .chamfer(edges, distance, core)?
+            .shells()
+            .only()
+            .clone_object())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Point, Scalar, Vector};
+
+    use crate::{
+        operations::{
+            build::{BuildRegion, BuildSketch},
+            insert::Insert,
+            sweep::SweepSketch,
+            update::UpdateSketch,
+        },
+        storage::Handle,
+        topology::{HalfEdge, Region, Sketch, Solid},
+        Core,
+    };
+
+    use super::{BlendError, Chamfer};
+
+    fn cube(core: &mut Core) -> Solid {
+        let surface = core.layers.topology.surfaces.xy_plane();
+
+        Sketch::empty(&core.layers.topology)
+            .add_regions(
+                [Region::polygon(
+                    [[0., 0.], [1., 0.], [1., 1.], [0., 1.]],
+                    core.layers.topology.surfaces.space_2d(),
+                    core,
+                )],
+                core,
+            )
+            .sweep_sketch(surface, Vector::unit_z(), core)
+    }
+
+    /// Find the half-edge that starts and ends at the provided points
+    fn half_edge(
+        solid: &Solid,
+        [start, end]: [[f64; 3]; 2],
+        core: &Core,
+    ) -> Handle<HalfEdge> {
+        let geometry = &core.layers.geometry;
+        let [start, end] = [start, end].map(Point::from);
+
+        solid
+            .shells()
+            .iter()
+            .flat_map(|shell| shell.faces())
+            .find_map(|face| {
+                let surface = geometry.of_surface(face.surface());
+                let position = |half_edge: &Handle<HalfEdge>| {
+                    surface.point_from_surface_coords(
+                        geometry.of_half_edge(half_edge).start_position(),
+                    )
+                };
+
+                face.region().all_cycles().find_map(|cycle| {
+                    cycle.half_edges().pairs().find_map(|(half_edge, next)| {
+                        let is_match = (position(half_edge) - start)
+                            .magnitude()
+                            < Scalar::from(1e-9)
+                            && (position(next) - end).magnitude()
+                                < Scalar::from(1e-9);
+
+                        is_match.then(|| half_edge.clone())
+                    })
+                })
+            })
+            .expect("Expected half-edge between points")
+    }
+
+    fn num_faces(solid: &Solid) -> usize {
+        solid.shells().iter().map(|shell| shell.faces().len()).sum()
+    }
+
+    #[test]
+    fn chamfer_single_edge() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let cube = cube(&mut core);
+        let edge = half_edge(&cube, [[0., 0., 1.], [1., 0., 1.]], &core);
+
+        let solid = cube.chamfer([edge], 0.25, &mut core)?.insert(&mut core);
+        core.layers.validation.take_errors()?;
+
+        assert_eq!(num_faces(&solid), 7);
+
+        Ok(())
+    }
+
+    #[test]
+    fn chamfer_adjacent_edges() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let cube = cube(&mut core);
+        let edges =
+            [[[0., 0., 1.], [1., 0., 1.]], [[1., 0., 1.], [1., 1., 1.]]]
+                .map(|points| half_edge(&cube, points, &core));
+
+        let solid = cube.chamfer(edges, 0.25, &mut core)?.insert(&mut core);
+        core.layers.validation.take_errors()?;
+
+        assert_eq!(num_faces(&solid), 8);
+
+        Ok(())
+    }
+
+    #[test]
+    fn chamfer_too_large() {
+        let mut core = Core::new();
+
+        let cube = cube(&mut core);
+        let edge = half_edge(&cube, [[0., 0., 1.], [1., 0., 1.]], &core);
+
+        let result = cube.chamfer([edge.clone()], 2., &mut core);
+        assert_eq!(
+            result.err(),
+            Some(BlendError::TooLarge { half_edge: edge })
+        );
+    }
+
+    #[test]
+    fn chamfer_curved_edges() {
+        let mut core = Core::new();
+
+        let surface = core.layers.topology.surfaces.xy_plane();
+        let cylinder = Sketch::empty(&core.layers.topology)
+            .add_regions(
+                [Region::circle(
+                    [0., 0.],
+                    1.,
+                    core.layers.topology.surfaces.space_2d(),
+                    &mut core,
+                )],
+                &mut core,
+            )
+            .sweep_sketch(surface, Vector::unit_z(), &mut core);
+
+        let half_edges = cylinder
+            .shells()
+            .iter()
+            .flat_map(|shell| shell.faces())
+            .flat_map(|face| face.region().exterior().half_edges())
+            .cloned()
+            .collect::<Vec<_>>();
+        assert!(!half_edges.is_empty());
+
+        for half_edge in half_edges {
+            let result = cylinder.chamfer([half_edge], 0.25, &mut core);
+            assert!(matches!(
+                result,
+                Err(BlendError::UnsupportedSurface { .. }
+                    | BlendError::UnsupportedHalfEdge { .. })
+            ));
+        }
+    }
+}
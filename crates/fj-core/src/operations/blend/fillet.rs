@@ -0,0 +1,509 @@
+use std::collections::BTreeSet;
+
+use fj_interop::ext::ArrayExt;
+use fj_math::{Circle, Point, Scalar, Vector};
+
+use crate::{
+    geometry::{CurveBoundary, GlobalPath, HalfEdgeGeom, SurfacePath},
+    operations::{
+        build::BuildSurface,
+        derive::DeriveFrom,
+        geometry::{UpdateCurveGeometry, UpdateHalfEdgeGeometry},
+        insert::Insert,
+        update::{
+            UpdateCycle, UpdateFace, UpdateRegion, UpdateShell, UpdateSolid,
+        },
+    },
+    storage::Handle,
+    topology::{
+        Curve, Cycle, Face, HalfEdge, Region, Shell, Solid, Surface, Vertex,
+    },
+    Core,
+};
+
+use super::{cut_wedges, start_position, BlendError, BlendedEdge, EdgeInFace};
+
+/// # Fillet edges
+///
+/// See [module documentation] for more information.
+///
+/// [module documentation]: super
+pub trait Fillet: Sized {
+    /// # Fillet the provided edges
+    ///
+    /// Each edge is identified by one of its half-edges. The edge is replaced
+    /// by a cylindrical face with the provided radius, which is tangent to both
+    /// faces adjacent to the edge.
+    ///
+    /// Returns an error, if any of the edges is not supported, as described in
+    /// the module documentation, if the radius is too large for the faces
+    /// adjacent to an edge, or if any of the edges share a vertex. See
+    /// [`BlendError`].
+    fn fillet(
+        &self,
+        edges: impl IntoIterator<Item = Handle<HalfEdge>>,
+        radius: impl Into<Scalar>,
+        core: &mut Core,
+    ) -> Result<Self, BlendError>;
+}
+
+impl Fillet for Solid {
+    fn fillet(
+        &self,
+        edges: impl IntoIterator<Item = Handle<HalfEdge>>,
+        radius: impl Into<Scalar>,
+        core: &mut Core,
+    ) -> Result<Self, BlendError> {
+        let radius = radius.into();
+        let tolerance = core.layers.validation.config.identical_max_distance;
+
+        // The fillet is tangent to both faces, which determines how far from
+        // the edge it meets them.
+        let edges = BlendedEdge::collect(
+            self,
+            edges,
+            |angle| {
+                let (sin, cos) = (angle / 2.).sin_cos();
+                radius * cos / sin
+            },
+            core,
+        )?;
+
+        let mut vertices = BTreeSet::new();
+        for edge in &edges {
+            let [[start, ..], [end, ..]] = edge.ends;
+            let direction = (end - start).normalize();
+
+            for normal in edge.end_normals {
+                if normal.cross(&direction).magnitude() >= tolerance {
+                    return Err(BlendError::NonPerpendicularEnd {
+                        half_edge: edge.half_edge.clone(),
+                    });
+                }
+            }
+            for vertex in &edge.vertices {
+                if !vertices.insert(vertex.id()) {
+                    return Err(BlendError::SharedVertex {
+                        vertex: vertex.clone(),
+                    });
+                }
+            }
+        }
+
+        // Cutting the wedges leaves a chamfer in place of each edge, which we
+        // then replace with the fillet.
+        let mut solid = cut_wedges(self, &edges, core)?;
+        for edge in &edges {
+            solid = round_chamfer(&solid, edge, radius, core);
+        }
+
+        Ok(solid)
+    }
+}
+
+impl Fillet for Shell {
+    fn fillet(
+        &self,
+        edges: impl IntoIterator<Item = Handle<HalfEdge>>,
+        radius: impl Into<Scalar>,
+        core: &mut Core,
+    ) -> Result<Self, BlendError> {
+        let shell = self.clone().insert(core);
+        Ok(Solid::new([shell])
+            .fillet(edges, radius, core)?
+            .shells()
+            .only()
+            .clone_object())
+    }
+}
+
+/// Replace the chamfer of a blended edge with a fillet
+///
+/// The half-edges of the chamfer that are parallel to the edge are shared with
+/// the adjacent faces, which stay as they are. The other half-edges are shared
+/// with the faces where the blend ends, and are replaced with arcs there.
+fn round_chamfer(
+    solid: &Solid,
+    edge: &BlendedEdge,
+    radius: Scalar,
+    core: &mut Core,
+) -> Solid {
+    let tolerance = core.layers.validation.config.distinct_min_distance;
+
+    let (shell, chamfer) = solid
+        .shells()
+        .iter()
+        .find_map(|shell| {
+            let chamfer = shell
+                .faces()
+                .iter()
+                .find(|face| is_chamfer(face, edge, tolerance, core))?;
+            Some((shell.clone(), chamfer.clone()))
+        })
+        .expect("Chamfered solid must contain chamfer of blended edge");
+
+    // The axis of the cylinder is at the radius from both adjacent faces. We
+    // define the cylinder's surface such that the start of its u-axis is where
+    // the fillet meets the first face.
+    let [normal_a, _] = edge.normals;
+    let [origin, end] = edge.ends.map(|[_, on_a, _]| on_a - normal_a * radius);
+    let axis = end - origin;
+    let radial = edge.ends[0][1] - origin;
+    let tangential = axis.normalize().cross(&radial);
+
+    let surface = Surface::from_uv(
+        GlobalPath::Circle(Circle::new(origin, radial, tangential)),
+        axis,
+        core,
+    );
+
+    // The vertices of the chamfer are at the corners of the cylinder's
+    // boundary. Assigning their surface coordinates from those, rather than
+    // projecting them, keeps the boundary exactly aligned with the u- and
+    // v-axes.
+    let angle = {
+        let offset = edge.ends[0][2] - origin;
+        offset.dot(&tangential).atan2(offset.dot(&radial))
+    };
+    let corners = [0., 1.]
+        .into_iter()
+        .zip(edge.ends)
+        .flat_map(|(v, [_, on_a, on_b])| {
+            [
+                (on_a, Point::from([Scalar::ZERO, v.into()])),
+                (on_b, Point::from([angle, v.into()])),
+            ]
+        })
+        .collect::<Vec<_>>();
+    let surface_coords = |point: Point<3>| {
+        corners
+            .iter()
+            .min_by_key(|(corner, _)| (*corner - point).magnitude())
+            .map(|(_, coords)| *coords)
+            .expect("Chamfer has corners")
+    };
+
+    let mut updated_shell = shell.clone_object();
+    let mut half_edges = Vec::new();
+
+    for (half_edge, next) in chamfer.region().exterior().half_edges().pairs() {
+        let coords = [half_edge, next].map(|half_edge| {
+            surface_coords(start_position(
+                half_edge,
+                &chamfer,
+                &core.layers.geometry,
+            ))
+        });
+
+        let is_along_edge =
+            (coords[0].v - coords[1].v).abs() > Scalar::from(0.5);
+        if is_along_edge {
+            // This half-edge is shared with one of the adjacent faces. We can
+            // keep its curve, and just need to define it on the new surface.
+            let boundary =
+                core.layers.geometry.of_half_edge(half_edge).boundary;
+
+            half_edges.push(cylinder_half_edge(
+                half_edge.curve().clone(),
+                half_edge.start_vertex().clone(),
+                boundary,
+                coords,
+                &surface,
+                core,
+            ));
+
+            continue;
+        }
+
+        // This half-edge is shared with a face where the blend ends. Its
+        // sibling there needs to be replaced with an arc.
+        let boundary = CurveBoundary {
+            inner: coords.map(|point| Point::from([point.u])),
+        };
+        let curve = Curve::new().insert(core);
+
+        half_edges.push(cylinder_half_edge(
+            curve.clone(),
+            half_edge.start_vertex().clone(),
+            boundary,
+            coords,
+            &surface,
+            core,
+        ));
+
+        let sibling = EdgeInFace::find(
+            &updated_shell.faces().iter().cloned().collect::<Vec<_>>(),
+            |other| {
+                other.curve().id() == half_edge.curve().id()
+                    && other.id() != half_edge.id()
+            },
+        )
+        .expect("Half-edge of chamfer must have a sibling");
+
+        let arc = {
+            let surface_geom = core
+                .layers
+                .geometry
+                .of_surface(sibling.face.surface())
+                .clone();
+
+            let center = origin + axis * coords[0].v;
+            let [center, a, b] = [center, center + radial, center + tangential]
+                .map(|point| surface_geom.project_global_point(point));
+            let [a, b] = [a - center, b - center];
+
+            // Constructing the perpendicular vector like this, makes sure it
+            // has the same length.
+            let perpendicular = Vector::from([-a.v, a.u]);
+            let b = if perpendicular.dot(&b) < Scalar::ZERO {
+                -perpendicular
+            } else {
+                perpendicular
+            };
+
+            SurfacePath::Circle(Circle::new(center, a, b))
+        };
+
+        let replacement = half_edge_on_surface(
+            curve,
+            sibling.half_edge(0).start_vertex().clone(),
+            HalfEdgeGeom {
+                path: arc,
+                boundary: boundary.reverse(),
+            },
+            sibling.face.surface(),
+            core,
+        );
+
+        updated_shell = updated_shell.update_face(
+            &sibling.face,
+            |face, core| {
+                [face.update_region(
+                    |region, core| {
+                        replace_half_edge(
+                            region,
+                            sibling.half_edge(0),
+                            replacement,
+                            core,
+                        )
+                    },
+                    core,
+                )]
+            },
+            core,
+        );
+    }
+
+    let region = Region::new(Cycle::new(half_edges).insert(core), [])
+        .insert(core)
+        .derive_from(chamfer.region(), core);
+    let updated_shell = updated_shell.update_face(
+        &chamfer,
+        |_, _| [Face::new(surface, region)],
+        core,
+    );
+
+    solid.update_shell(&shell, |_, _| [updated_shell], core)
+}
+
+/// Determine whether the face is the chamfer left behind by the blended edge
+fn is_chamfer(
+    face: &Handle<Face>,
+    edge: &BlendedEdge,
+    tolerance: Scalar,
+    core: &Core,
+) -> bool {
+    let corners = edge.ends.iter().flat_map(|[_, a, b]| [*a, *b]);
+    let half_edges = face.region().exterior().half_edges();
+
+    face.region().interiors().is_empty()
+        && half_edges.len() == 4
+        && half_edges.iter().all(|half_edge| {
+            let position =
+                start_position(half_edge, face, &core.layers.geometry);
+            corners
+                .clone()
+                .any(|corner| (corner - position).magnitude() < tolerance)
+        })
+}
+
+fn cylinder_half_edge(
+    curve: Handle<Curve>,
+    start_vertex: Handle<Vertex>,
+    boundary: CurveBoundary<Point<1>>,
+    coords: [Point<2>; 2],
+    surface: &Handle<Surface>,
+    core: &mut Core,
+) -> Handle<HalfEdge> {
+    let path = SurfacePath::line_from_points_with_coords(
+        boundary.inner.zip_ext(coords),
+    );
+
+    half_edge_on_surface(
+        curve,
+        start_vertex,
+        HalfEdgeGeom { path, boundary },
+        surface,
+        core,
+    )
+}
+
+fn half_edge_on_surface(
+    curve: Handle<Curve>,
+    start_vertex: Handle<Vertex>,
+    geometry: HalfEdgeGeom,
+    surface: &Handle<Surface>,
+    core: &mut Core,
+) -> Handle<HalfEdge> {
+    let curve = curve.make_path_on_surface(
+        geometry.path.clone(),
+        surface.clone(),
+        &mut core.layers.geometry,
+    );
+
+    HalfEdge::new(curve, start_vertex)
+        .insert(core)
+        .set_geometry(geometry, &mut core.layers.geometry)
+}
+
+fn replace_half_edge(
+    region: &Handle<Region>,
+    original: &Handle<HalfEdge>,
+    replacement: Handle<HalfEdge>,
+    core: &mut Core,
+) -> Region {
+    let update = |cycle: &Handle<Cycle>, core: &mut Core| {
+        cycle.update_half_edge(original, |_, _| [replacement], core)
+    };
+
+    if region.exterior().half_edges().contains(original) {
+        return region.update_exterior(update, core);
+    }
+
+    let interior = region
+        .interiors()
+        .iter()
+        .find(|cycle| cycle.half_edges().contains(original))
+        .expect("Half-edge must be part of region")
+        .clone();
+    region.update_interior(&interior, |cycle, core| [update(cycle, core)], core)
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Point, Scalar, Vector};
+
+    use crate::{
+        operations::{
+            build::{BuildRegion, BuildSketch},
+            insert::Insert,
+            sweep::SweepSketch,
+            update::UpdateSketch,
+        },
+        storage::Handle,
+        topology::{HalfEdge, Region, Sketch, Solid},
+        Core,
+    };
+
+    use super::{BlendError, Fillet};
+
+    fn cube(core: &mut Core) -> Solid {
+        let surface = core.layers.topology.surfaces.xy_plane();
+
+        Sketch::empty(&core.layers.topology)
+            .add_regions(
+                [Region::polygon(
+                    [[0., 0.], [1., 0.], [1., 1.], [0., 1.]],
+                    core.layers.topology.surfaces.space_2d(),
+                    core,
+                )],
+                core,
+            )
+            .sweep_sketch(surface, Vector::unit_z(), core)
+    }
+
+    /// Find the half-edge that starts and ends at the provided points
+    fn half_edge(
+        solid: &Solid,
+        [start, end]: [[f64; 3]; 2],
+        core: &Core,
+    ) -> Handle<HalfEdge> {
+        let geometry = &core.layers.geometry;
+        let [start, end] = [start, end].map(Point::from);
+
+        solid
+            .shells()
+            .iter()
+            .flat_map(|shell| shell.faces())
+            .find_map(|face| {
+                let surface = geometry.of_surface(face.surface());
+                let position = |half_edge: &Handle<HalfEdge>| {
+                    surface.point_from_surface_coords(
+                        geometry.of_half_edge(half_edge).start_position(),
+                    )
+                };
+
+                face.region().all_cycles().find_map(|cycle| {
+                    cycle.half_edges().pairs().find_map(|(half_edge, next)| {
+                        let is_match = (position(half_edge) - start)
+                            .magnitude()
+                            < Scalar::from(1e-9)
+                            && (position(next) - end).magnitude()
+                                < Scalar::from(1e-9);
+
+                        is_match.then(|| half_edge.clone())
+                    })
+                })
+            })
+            .expect("Expected half-edge between points")
+    }
+
+    fn num_faces(solid: &Solid) -> usize {
+        solid.shells().iter().map(|shell| shell.faces().len()).sum()
+    }
+
+    #[test]
+    fn fillet_single_edge() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let cube = cube(&mut core);
+        let edge = half_edge(&cube, [[0., 0., 1.], [1., 0., 1.]], &core);
+
+        let solid = cube.fillet([edge], 0.25, &mut core)?.insert(&mut core);
+        core.layers.validation.take_errors()?;
+
+        assert_eq!(num_faces(&solid), 7);
+
+        Ok(())
+    }
+
+    #[test]
+    fn fillet_opposite_edges() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let cube = cube(&mut core);
+        let edges =
+            [[[0., 0., 1.], [1., 0., 1.]], [[1., 1., 1.], [0., 1., 1.]]]
+                .map(|points| half_edge(&cube, points, &core));
+
+        let solid = cube.fillet(edges, 0.25, &mut core)?.insert(&mut core);
+        core.layers.validation.take_errors()?;
+
+        assert_eq!(num_faces(&solid), 8);
+
+        Ok(())
+    }
+
+    #[test]
+    fn fillet_edges_sharing_vertex() {
+        let mut core = Core::new();
+
+        let cube = cube(&mut core);
+        let edges =
+            [[[0., 0., 1.], [1., 0., 1.]], [[1., 0., 1.], [1., 1., 1.]]]
+                .map(|points| half_edge(&cube, points, &core));
+
+        let result = cube.fillet(edges, 0.25, &mut core);
+        assert!(matches!(result, Err(BlendError::SharedVertex { .. })));
+    }
+}
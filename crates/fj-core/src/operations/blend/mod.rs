@@ -0,0 +1,419 @@
+//! # Chamfer and fillet edges of solids
+//!
+//! See [`Chamfer`] and [`Fillet`].
+//!
+//! A blend replaces an edge between two faces with a new face, which is planar
+//! for a chamfer and cylindrical for a fillet. The two faces that meet at the
+//! edge are cut back accordingly, as are the faces where the blend ends.
+//!
+//! ## Implementation Note
+//!
+//! Only straight edges between two planar faces that meet at a convex angle
+//! are supported. Each vertex of such an edge must be shared by exactly one
+//! other face, which is where the blend ends. Trying to blend any other edge
+//! results in a [`BlendError`].
+//!
+//! Blending works by subtracting a wedge from the solid, using the [boolean
+//! operations]. This means that only solids that are bounded by planar faces
+//! with straight edges can be blended. Fillets then replace the planar face
+//! that this leaves, which further requires the faces where they end to be
+//! perpendicular to the edge.
+//!
+//! [boolean operations]: super::boolean
+
+mod chamfer;
+mod fillet;
+
+pub use self::{chamfer::Chamfer, fillet::Fillet};
+
+use fj_interop::{ext::ArrayExt, Color};
+use fj_math::{Point, Scalar, Vector};
+
+use crate::{
+    geometry::{Geometry, GlobalPath, SurfaceGeom, SurfacePath, SweptCurve},
+    operations::{
+        boolean::{BooleanError, Difference},
+        build::BuildFace,
+        build::BuildSurface,
+        insert::Insert,
+        loft::{Loft, LoftError},
+        presentation::GetColor,
+        presentation::SetColor,
+    },
+    storage::Handle,
+    topology::{Face, HalfEdge, Solid, Surface, Vertex},
+    Core,
+};
+
+/// Error blending edges of a solid
+#[derive(Debug, Eq, PartialEq, thiserror::Error)]
+pub enum BlendError {
+    /// The half-edge is not part of the solid
+    #[error("Blended half-edge is not part of the solid")]
+    HalfEdgeNotInSolid {
+        /// The half-edge that identifies the edge
+        half_edge: Handle<HalfEdge>,
+    },
+
+    /// A face adjacent to the edge is not planar
+    #[error("Blending edges of faces that are not planar is not supported")]
+    UnsupportedSurface {
+        /// The half-edge that identifies the edge
+        half_edge: Handle<HalfEdge>,
+    },
+
+    /// The edge, or an edge adjacent to it, is not straight
+    #[error("Blending edges next to curved edges is not supported")]
+    UnsupportedHalfEdge {
+        /// The half-edge that identifies the edge
+        half_edge: Handle<HalfEdge>,
+    },
+
+    /// The faces adjacent to the edge meet at a concave angle
+    #[error("Only edges where faces meet at a convex angle can be blended")]
+    ConcaveEdge {
+        /// The half-edge that identifies the edge
+        half_edge: Handle<HalfEdge>,
+    },
+
+    /// A vertex of the edge is not shared by exactly three faces
+    #[error(
+        "Blended edges must end in vertices that are shared by exactly three \
+        faces"
+    )]
+    UnsupportedVertex {
+        /// The half-edge that identifies the edge
+        half_edge: Handle<HalfEdge>,
+    },
+
+    /// The blend is too large for the faces adjacent to the edge
+    #[error("Blend is too large for the faces adjacent to the edge")]
+    TooLarge {
+        /// The half-edge that identifies the edge
+        half_edge: Handle<HalfEdge>,
+    },
+
+    /// A fillet ends in a face that is not perpendicular to the edge
+    #[error("Fillets must end in faces that are perpendicular to the edge")]
+    NonPerpendicularEnd {
+        /// The half-edge that identifies the edge
+        half_edge: Handle<HalfEdge>,
+    },
+
+    /// Filleted edges share a vertex
+    #[error("Filleted edges must not share vertices")]
+    SharedVertex {
+        /// The vertex that is shared
+        vertex: Handle<Vertex>,
+    },
+
+    /// Error creating the wedge that is removed from the solid
+    #[error("Error creating the wedge that is removed from the solid")]
+    Loft(#[from] LoftError),
+
+    /// Error removing the wedge from the solid
+    #[error("Error removing the wedge from the solid")]
+    Boolean(#[from] BooleanError),
+}
+
+/// An edge that is going to be blended
+struct BlendedEdge {
+    /// The half-edge that identifies the edge
+    half_edge: Handle<HalfEdge>,
+
+    /// The vertices of the edge
+    vertices: [Handle<Vertex>; 2],
+
+    /// The normals of the two faces that meet at the edge
+    normals: [Vector<3>; 2],
+
+    /// The normals of the faces where the blend ends, one for each vertex
+    end_normals: [Vector<3>; 2],
+
+    /// The cross-section of the blend at each vertex
+    ///
+    /// The first point is the position of the vertex, the second and third
+    /// are the points where the blend meets the first and second face.
+    ends: [[Point<3>; 3]; 2],
+
+    /// The color of the first face, if it has one
+    color: Option<Color>,
+}
+
+impl BlendedEdge {
+    /// Collect the edges of a solid that are going to be blended
+    ///
+    /// `setback` computes the distance from the edge, at which the blend meets
+    /// the faces, from the angle between those faces.
+    fn collect(
+        solid: &Solid,
+        half_edges: impl IntoIterator<Item = Handle<HalfEdge>>,
+        setback: impl Fn(Scalar) -> Scalar,
+        core: &mut Core,
+    ) -> Result<Vec<Self>, BlendError> {
+        let faces = solid
+            .shells()
+            .iter()
+            .flat_map(|shell| shell.faces().iter().cloned())
+            .collect::<Vec<_>>();
+
+        half_edges
+            .into_iter()
+            .map(|half_edge| Self::new(&half_edge, &faces, &setback, core))
+            .collect()
+    }
+
+    fn new(
+        half_edge: &Handle<HalfEdge>,
+        faces: &[Handle<Face>],
+        setback: impl Fn(Scalar) -> Scalar,
+        core: &mut Core,
+    ) -> Result<Self, BlendError> {
+        let geometry = &core.layers.geometry;
+
+        let a = EdgeInFace::find(faces, |other| other.id() == half_edge.id())
+            .ok_or_else(|| BlendError::HalfEdgeNotInSolid {
+            half_edge: half_edge.clone(),
+        })?;
+        let b = a.sibling(faces);
+
+        for edge in [&a, &b] {
+            edge.check_supported(half_edge, geometry)?;
+        }
+
+        let [normal_a, normal_b] =
+            [&a, &b].map(|edge| edge.face_normal(geometry));
+
+        let start = a.position(0, geometry);
+        let end = a.position(1, geometry);
+        let direction = (end - start).normalize();
+
+        // Directions within each face, perpendicular to the edge and pointing
+        // into the face.
+        let inwards_a = normal_a.cross(&direction).normalize();
+        let inwards_b = normal_b.cross(&-direction).normalize();
+
+        if inwards_a.dot(&normal_b) >= Scalar::ZERO {
+            return Err(BlendError::ConcaveEdge {
+                half_edge: half_edge.clone(),
+            });
+        }
+
+        let setback = setback(inwards_a.dot(&inwards_b).acos());
+
+        // The blend ends where the lines that are parallel to the edge, at the
+        // setback distance, meet the adjacent edges of the two faces.
+        let along = |vertex: Point<3>, neighbor: Point<3>, inwards| {
+            let offset = neighbor - vertex;
+            let fraction = setback / offset.dot(&inwards);
+
+            if fraction <= Scalar::ZERO || fraction >= Scalar::ONE {
+                return Err(BlendError::TooLarge {
+                    half_edge: half_edge.clone(),
+                });
+            }
+
+            Ok(vertex + offset * fraction)
+        };
+
+        let ends = [
+            (start, a.position(-1, geometry), b.position(2, geometry)),
+            (end, a.position(2, geometry), b.position(-1, geometry)),
+        ]
+        .try_map_ext(|(vertex, neighbor_a, neighbor_b)| {
+            Ok::<_, BlendError>([
+                vertex,
+                along(vertex, neighbor_a, inwards_a)?,
+                along(vertex, neighbor_b, inwards_b)?,
+            ])
+        })?;
+
+        // At each vertex, the half-edges before and after the blended edge
+        // must border the same face, or the blend wouldn't end in a single
+        // face.
+        let end_normals =
+            [(-1, 1), (1, -1)].try_map_ext(|(offset_a, offset_b)| {
+                let face = a.sibling_of(offset_a, faces).face;
+
+                if face.id() != b.sibling_of(offset_b, faces).face.id() {
+                    return Err(BlendError::UnsupportedVertex {
+                        half_edge: half_edge.clone(),
+                    });
+                }
+
+                Ok(face_normal(&face, geometry))
+            })?;
+
+        Ok(Self {
+            half_edge: half_edge.clone(),
+            vertices: [0, 1]
+                .map(|offset| a.half_edge(offset).start_vertex().clone()),
+            normals: [normal_a, normal_b],
+            end_normals,
+            ends,
+            color: a.face.region().get_color(core),
+        })
+    }
+
+    /// Create the wedge that needs to be removed from the solid
+    ///
+    /// The wedge has the color of the first face, so the resulting blend
+    /// matches that.
+    fn wedge(&self, core: &mut Core) -> Result<Solid, LoftError> {
+        let ends = self.ends.map(|points| {
+            let (surface, points) = Surface::plane_from_points(points, core);
+            let face = Face::polygon(surface, points, core);
+
+            if let Some(color) = self.color {
+                face.region().set_color(color, core);
+            }
+
+            face.insert(core)
+        });
+
+        ends.loft(core)
+    }
+}
+
+/// Remove the wedges of all blended edges from the solid
+///
+/// This leaves a planar face in place of each edge, which is the final result
+/// for chamfers.
+fn cut_wedges(
+    solid: &Solid,
+    edges: &[BlendedEdge],
+    core: &mut Core,
+) -> Result<Solid, BlendError> {
+    edges.iter().try_fold(solid.clone(), |solid, edge| {
+        let wedge = edge.wedge(core)?;
+        Ok(solid.difference(&wedge, core)?)
+    })
+}
+
+/// A half-edge, within the cycle of the face it belongs to
+struct EdgeInFace {
+    face: Handle<Face>,
+    half_edges: Vec<Handle<HalfEdge>>,
+    index: usize,
+}
+
+impl EdgeInFace {
+    /// Find the first half-edge among the faces that matches the predicate
+    fn find(
+        faces: &[Handle<Face>],
+        predicate: impl Fn(&Handle<HalfEdge>) -> bool,
+    ) -> Option<Self> {
+        faces.iter().find_map(|face| {
+            face.region().all_cycles().find_map(|cycle| {
+                let index = cycle.half_edges().iter().position(&predicate)?;
+
+                Some(Self {
+                    face: face.clone(),
+                    half_edges: cycle.half_edges().iter().cloned().collect(),
+                    index,
+                })
+            })
+        })
+    }
+
+    /// Access the half-edge at the given offset from this one
+    fn half_edge(&self, offset: isize) -> &Handle<HalfEdge> {
+        let len = self.half_edges.len() as isize;
+        let index = (self.index as isize + offset).rem_euclid(len);
+        &self.half_edges[index as usize]
+    }
+
+    /// Compute the position of the half-edge's start vertex at the given offset
+    fn position(&self, offset: isize, geometry: &Geometry) -> Point<3> {
+        start_position(self.half_edge(offset), &self.face, geometry)
+    }
+
+    /// Find the sibling of this half-edge
+    fn sibling(&self, faces: &[Handle<Face>]) -> Self {
+        self.sibling_of(0, faces)
+    }
+
+    /// Find the sibling of the half-edge at the given offset from this one
+    fn sibling_of(&self, offset: isize, faces: &[Handle<Face>]) -> Self {
+        let half_edge = self.half_edge(offset);
+
+        Self::find(faces, |other| {
+            other.curve().id() == half_edge.curve().id()
+                && other.id() != half_edge.id()
+        })
+        .expect("Half-edge of a solid must have a sibling")
+    }
+
+    fn face_normal(&self, geometry: &Geometry) -> Vector<3> {
+        face_normal(&self.face, geometry)
+    }
+
+    /// Check that the half-edge and its neighbors can be blended
+    ///
+    /// `blended` is the half-edge that identifies the blended edge, which is
+    /// returned as part of any error.
+    fn check_supported(
+        &self,
+        blended: &Handle<HalfEdge>,
+        geometry: &Geometry,
+    ) -> Result<(), BlendError> {
+        if !matches!(
+            geometry.of_surface(self.face.surface()),
+            SurfaceGeom::Swept(SweptCurve {
+                u: GlobalPath::Line(_),
+                ..
+            })
+        ) {
+            return Err(BlendError::UnsupportedSurface {
+                half_edge: blended.clone(),
+            });
+        }
+
+        for offset in -1..=1 {
+            if let SurfacePath::Circle(_) | SurfacePath::Spline(_) =
+                geometry.of_half_edge(self.half_edge(offset)).path
+            {
+                return Err(BlendError::UnsupportedHalfEdge {
+                    half_edge: blended.clone(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn start_position(
+    half_edge: &Handle<HalfEdge>,
+    face: &Handle<Face>,
+    geometry: &Geometry,
+) -> Point<3> {
+    geometry
+        .of_surface(face.surface())
+        .point_from_surface_coords(
+            geometry.of_half_edge(half_edge).start_position(),
+        )
+}
+
+/// Compute the normal of the face, i.e. the direction its front points to
+fn face_normal(face: &Handle<Face>, geometry: &Geometry) -> Vector<3> {
+    let points = face
+        .region()
+        .exterior()
+        .half_edges()
+        .iter()
+        .map(|half_edge| start_position(half_edge, face, geometry))
+        .collect::<Vec<_>>();
+
+    // Newell's method is robust against collinear and concave vertices.
+    let mut normal = Vector::from([0., 0., 0.]);
+    for (i, a) in points.iter().enumerate() {
+        let b = points[(i + 1) % points.len()];
+
+        normal.x += (a.y - b.y) * (a.z + b.z);
+        normal.y += (a.z - b.z) * (a.x + b.x);
+        normal.z += (a.x - b.x) * (a.y + b.y);
+    }
+
+    normal.normalize()
+}
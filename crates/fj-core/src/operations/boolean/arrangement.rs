@@ -0,0 +1,269 @@
+//! Split a polygon along a number of line segments
+//!
+//! See [`split_polygon`].
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use fj_math::{Point, Scalar};
+
+use super::polygon::{
+    containment, distance_to_segment, edges, interior_point, signed_area,
+    Containment,
+};
+
+/// Split a polygon along the provided line segments
+///
+/// Computes the planar arrangement formed by the edges of the polygon and the
+/// provided segments, and returns all pieces of that arrangement that are
+/// inside of the polygon. Segments (or parts of segments) that are outside of
+/// the polygon, or that don't fully divide it, have no effect.
+///
+/// Each returned piece is itself a polygon, with its exterior cycle being
+/// counter-clockwise, and any interior cycles being clockwise.
+pub fn split_polygon(
+    cycles: &[Vec<Point<2>>],
+    cuts: &[[Point<2>; 2]],
+    tolerance: Scalar,
+) -> Vec<Vec<Vec<Point<2>>>> {
+    if cuts.is_empty() {
+        return vec![cycles.to_vec()];
+    }
+
+    let segments = cycles
+        .iter()
+        .flat_map(|cycle| edges(cycle))
+        .chain(cuts.iter().copied())
+        .filter(|&[a, b]| (b - a).magnitude() > tolerance)
+        .collect::<Vec<_>>();
+
+    let mut graph = Graph::from_segments(&segments, tolerance);
+    graph.remove_dangling_edges();
+
+    let mut exteriors = Vec::new();
+    let mut interiors = Vec::new();
+
+    for cycle in graph.cycles() {
+        let points = cycle
+            .into_iter()
+            .map(|index| graph.vertices[index])
+            .collect::<Vec<_>>();
+
+        let area = signed_area(&points);
+        if area > Scalar::ZERO {
+            exteriors.push((area, vec![points]));
+        } else {
+            interiors.push(points);
+        }
+    }
+
+    // Each interior cycle needs to be assigned to the smallest exterior cycle
+    // that contains it. Interior cycles that aren't contained in any exterior
+    // cycle bound the unbounded outside of the arrangement, and can be ignored.
+    for interior in interiors {
+        let point = interior[0];
+
+        let exterior = exteriors
+            .iter_mut()
+            .filter(|(_, polygon)| {
+                containment(&polygon[..1], point, tolerance)
+                    == Containment::Inside
+            })
+            .min_by_key(|(area, _)| *area);
+
+        if let Some((_, polygon)) = exterior {
+            polygon.push(interior);
+        }
+    }
+
+    exteriors
+        .into_iter()
+        .map(|(_, polygon)| polygon)
+        .filter(|polygon| {
+            containment(cycles, interior_point(polygon), tolerance)
+                == Containment::Inside
+        })
+        .collect()
+}
+
+struct Graph {
+    vertices: Vec<Point<2>>,
+    edges: BTreeSet<[usize; 2]>,
+}
+
+impl Graph {
+    fn from_segments(segments: &[[Point<2>; 2]], tolerance: Scalar) -> Self {
+        // Find all points at which each segment needs to be split, as
+        // coordinates along the segment.
+        let mut split_points =
+            vec![vec![Scalar::ZERO, Scalar::ONE]; segments.len()];
+
+        for (i, &[a, b]) in segments.iter().enumerate() {
+            for (j, &[c, d]) in segments.iter().enumerate().skip(i + 1) {
+                for point in [c, d] {
+                    if distance_to_segment(point, [a, b]) < tolerance {
+                        split_points[i].push(segment_coords([a, b], point));
+                    }
+                }
+                for point in [a, b] {
+                    if distance_to_segment(point, [c, d]) < tolerance {
+                        split_points[j].push(segment_coords([c, d], point));
+                    }
+                }
+
+                if let Some(point) = crossing([a, b], [c, d], tolerance) {
+                    split_points[i].push(segment_coords([a, b], point));
+                    split_points[j].push(segment_coords([c, d], point));
+                }
+            }
+        }
+
+        let mut self_ = Self {
+            vertices: Vec::new(),
+            edges: BTreeSet::new(),
+        };
+
+        for (&[a, b], mut split_points) in segments.iter().zip(split_points) {
+            split_points.sort();
+
+            let mut previous = None;
+            for t in split_points {
+                let t = t.max(Scalar::ZERO).min(Scalar::ONE);
+                let vertex = self_.vertex(a + (b - a) * t, tolerance);
+
+                if let Some(previous) = previous {
+                    if previous != vertex {
+                        let mut edge = [previous, vertex];
+                        edge.sort();
+                        self_.edges.insert(edge);
+                    }
+                }
+
+                previous = Some(vertex);
+            }
+        }
+
+        self_
+    }
+
+    fn vertex(&mut self, point: Point<2>, tolerance: Scalar) -> usize {
+        if let Some(index) = self
+            .vertices
+            .iter()
+            .position(|vertex| (vertex - point).magnitude() < tolerance)
+        {
+            return index;
+        }
+
+        self.vertices.push(point);
+        self.vertices.len() - 1
+    }
+
+    fn remove_dangling_edges(&mut self) {
+        loop {
+            let mut degrees = BTreeMap::new();
+            for edge in &self.edges {
+                for vertex in edge {
+                    *degrees.entry(*vertex).or_insert(0) += 1;
+                }
+            }
+
+            let num_edges = self.edges.len();
+            self.edges
+                .retain(|edge| edge.iter().all(|vertex| degrees[vertex] > 1));
+
+            if self.edges.len() == num_edges {
+                break;
+            }
+        }
+    }
+
+    /// Find all cycles that bound the faces of the graph
+    ///
+    /// Each cycle is traced such that the face it bounds is to its left. This
+    /// means that cycles that bound a finite face are counter-clockwise, while
+    /// cycles that bound a hole in a face are clockwise.
+    fn cycles(&self) -> Vec<Vec<usize>> {
+        // For each vertex, the neighboring vertices, sorted by angle.
+        let mut neighbors: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+        for &[a, b] in &self.edges {
+            neighbors.entry(a).or_default().push(b);
+            neighbors.entry(b).or_default().push(a);
+        }
+        for (vertex, neighbors) in &mut neighbors {
+            let origin = self.vertices[*vertex];
+            neighbors.sort_by_key(|neighbor| {
+                let direction = self.vertices[*neighbor] - origin;
+                direction.v.atan2(direction.u)
+            });
+        }
+
+        let mut visited = BTreeSet::new();
+        let mut cycles = Vec::new();
+
+        for &[a, b] in &self.edges {
+            for start in [[a, b], [b, a]] {
+                if visited.contains(&start) {
+                    continue;
+                }
+
+                let mut cycle = Vec::new();
+                let mut current = start;
+
+                while visited.insert(current) {
+                    let [from, to] = current;
+                    cycle.push(from);
+
+                    // The next edge is the one that comes right before the
+                    // current one in counter-clockwise order around the vertex
+                    // we arrive at. That makes it the sharpest left turn.
+                    let around = &neighbors[&to];
+                    let index = around
+                        .iter()
+                        .position(|vertex| *vertex == from)
+                        .expect("Edge must be in neighbor list");
+                    let next =
+                        around[(index + around.len() - 1) % around.len()];
+
+                    current = [to, next];
+                }
+
+                cycles.push(cycle);
+            }
+        }
+
+        cycles
+    }
+}
+
+fn segment_coords([a, b]: [Point<2>; 2], point: Point<2>) -> Scalar {
+    let ab = b - a;
+    (point - a).dot(&ab) / ab.dot(&ab)
+}
+
+/// Compute the point where two line segments cross each other
+///
+/// Only considers proper crossings, where each segment has its end points on
+/// different sides of the other.
+fn crossing(
+    [a, b]: [Point<2>; 2],
+    [c, d]: [Point<2>; 2],
+    tolerance: Scalar,
+) -> Option<Point<2>> {
+    let side = |[a, b]: [Point<2>; 2], point: Point<2>| {
+        let direction = (b - a).normalize();
+        direction.cross2d(&(point - a))
+    };
+    let crosses = |a: Scalar, b: Scalar| {
+        (a > tolerance && b < -tolerance) || (a < -tolerance && b > tolerance)
+    };
+
+    let [side_c, side_d] = [c, d].map(|point| side([a, b], point));
+    let [side_a, side_b] = [a, b].map(|point| side([c, d], point));
+
+    if crosses(side_c, side_d) && crosses(side_a, side_b) {
+        let t = side_c / (side_c - side_d);
+        return Some(c + (d - c) * t);
+    }
+
+    None
+}
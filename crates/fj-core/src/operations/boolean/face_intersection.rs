@@ -0,0 +1,96 @@
+//! Intersection between two planar faces
+//!
+//! See [`FaceIntersection`].
+
+use fj_math::{Point, Scalar};
+
+use super::{polygon::line_intervals, polyhedron::PolyFace};
+
+/// The intersection between two planar faces
+pub enum FaceIntersection {
+    /// The faces are in the same plane
+    Coplanar,
+
+    /// The faces intersect along the provided line segments
+    Segments(Vec<[Point<3>; 2]>),
+}
+
+impl FaceIntersection {
+    /// Compute the intersection between two planar faces
+    ///
+    /// Returns `None`, if the faces don't intersect.
+    pub fn compute(
+        a: &PolyFace,
+        b: &PolyFace,
+        tolerance: Scalar,
+    ) -> Option<Self> {
+        let aabbs_overlap = (0..3).all(|i| {
+            a.aabb.min.coords.components[i]
+                <= b.aabb.max.coords.components[i] + tolerance
+                && b.aabb.min.coords.components[i]
+                    <= a.aabb.max.coords.components[i] + tolerance
+        });
+        if !aabbs_overlap {
+            return None;
+        }
+
+        let is_in_plane_of = |face: &PolyFace, other: &PolyFace| {
+            face.edges().all(|[point, _]| {
+                (point - other.frame.origin).dot(&other.frame.normal).abs()
+                    < tolerance
+            })
+        };
+        if is_in_plane_of(a, b) && is_in_plane_of(b, a) {
+            return Some(Self::Coplanar);
+        }
+
+        let direction = a.frame.normal.cross(&b.frame.normal);
+        if direction.magnitude() < Scalar::from(1e-12) {
+            // The faces are parallel, but not coplanar.
+            return None;
+        }
+        let direction = direction.normalize();
+
+        // Compute a point on the line where the planes of both faces meet. To
+        // keep this numerically well-behaved, we compute it relative to the
+        // origin of `a`.
+        let origin = {
+            let cos = a.frame.normal.dot(&b.frame.normal);
+            let distance =
+                (b.frame.origin - a.frame.origin).dot(&b.frame.normal);
+            let denominator = Scalar::ONE - cos * cos;
+
+            a.frame.origin
+                + a.frame.normal * (-distance * cos / denominator)
+                + b.frame.normal * (distance / denominator)
+        };
+
+        let [intervals_a, intervals_b] = [a, b].map(|face| {
+            line_intervals(
+                &face.cycles,
+                face.frame.project(origin),
+                face.frame.project_vector(direction).normalize(),
+                tolerance,
+            )
+        });
+
+        let mut segments = Vec::new();
+
+        for &[start_a, end_a] in &intervals_a {
+            for &[start_b, end_b] in &intervals_b {
+                let start = start_a.max(start_b);
+                let end = end_a.min(end_b);
+
+                if end - start > tolerance {
+                    segments.push([start, end].map(|t| origin + direction * t));
+                }
+            }
+        }
+
+        if segments.is_empty() {
+            return None;
+        }
+
+        Some(Self::Segments(segments))
+    }
+}
@@ -0,0 +1,538 @@
+//! # Boolean operations on solids
+//!
+//! See [`Union`], [`Difference`], and [`Intersection`].
+//!
+//! ## Implementation Note
+//!
+//! Boolean operations currently only support solids that are bounded by planar
+//! faces with straight edges. Solids whose bounding boxes don't overlap are
+//! handled regardless of their geometry, but for all other cases, curved faces
+//! or edges result in a [`BooleanError`].
+//!
+//! The operations work by computing where the faces of both solids intersect,
+//! splitting them along those intersections, and then deciding for each piece,
+//! whether it is part of the result. This is not robust against all degenerate
+//! cases. Specifically, solids that touch only along an edge or at a vertex can
+//! lead to an invalid result.
+
+mod arrangement;
+mod face_intersection;
+mod polygon;
+mod polyhedron;
+mod reassemble;
+
+use fj_math::Point;
+
+use crate::{
+    algorithms::{
+        bounding_volume::BoundingVolume,
+        classify_point::{ClassifyPoint, PointClassification},
+    },
+    storage::Handle,
+    topology::{Face, Solid},
+    Core,
+};
+
+use self::{
+    arrangement::split_polygon,
+    face_intersection::FaceIntersection,
+    polygon::interior_point,
+    polyhedron::{Classification, Polyhedron},
+    reassemble::{reassemble, OutputFace},
+};
+
+use super::{build::BuildSolid, update::UpdateSolid};
+
+/// Compute the union of two [`Solid`]s
+pub trait Union: Sized {
+    /// Compute the union of this solid and another
+    ///
+    /// The result contains all points that are in either solid.
+    fn union(
+        &self,
+        other: &Self,
+        core: &mut Core,
+    ) -> Result<Self, BooleanError>;
+}
+
+impl Union for Solid {
+    fn union(
+        &self,
+        other: &Self,
+        core: &mut Core,
+    ) -> Result<Self, BooleanError> {
+        boolean(self, other, BooleanOp::Union, core)
+    }
+}
+
+/// Compute the difference of two [`Solid`]s
+pub trait Difference: Sized {
+    /// Subtract another solid from this one
+    ///
+    /// The result contains all points that are in this solid, but not in the
+    /// other.
+    fn difference(
+        &self,
+        other: &Self,
+        core: &mut Core,
+    ) -> Result<Self, BooleanError>;
+}
+
+impl Difference for Solid {
+    fn difference(
+        &self,
+        other: &Self,
+        core: &mut Core,
+    ) -> Result<Self, BooleanError> {
+        boolean(self, other, BooleanOp::Difference, core)
+    }
+}
+
+/// Compute the intersection of two [`Solid`]s
+pub trait Intersection: Sized {
+    /// Compute the intersection of this solid and another
+    ///
+    /// The result contains all points that are in both solids.
+    fn intersection(
+        &self,
+        other: &Self,
+        core: &mut Core,
+    ) -> Result<Self, BooleanError>;
+}
+
+impl Intersection for Solid {
+    fn intersection(
+        &self,
+        other: &Self,
+        core: &mut Core,
+    ) -> Result<Self, BooleanError> {
+        boolean(self, other, BooleanOp::Intersection, core)
+    }
+}
+
+/// Error computing a boolean operation
+#[derive(Debug, Eq, PartialEq, thiserror::Error)]
+pub enum BooleanError {
+    /// One of the solids has a face that is not planar
+    #[error("Boolean operations only support planar faces")]
+    UnsupportedSurface {
+        /// The face that is not planar
+        face: Handle<Face>,
+    },
+
+    /// One of the solids has a face with an edge that is not straight
+    #[error("Boolean operations only support straight edges")]
+    UnsupportedHalfEdge {
+        /// The face that the edge is part of
+        face: Handle<Face>,
+    },
+
+    /// A piece of a face could not be classified relative to the other solid
+    ///
+    /// This happens, if the point that is used to represent the piece is close
+    /// enough to the other solid to be considered on its boundary, but none of
+    /// its faces contain it.
+    #[error("Failed to classify point {point:?} relative to solid")]
+    AmbiguousClassification {
+        /// The point that could not be classified
+        point: Point<3>,
+    },
+}
+
+#[derive(Clone, Copy)]
+enum BooleanOp {
+    Union,
+    Difference,
+    Intersection,
+}
+
+impl BooleanOp {
+    /// Decide what to do with a piece of a face of one of the operands
+    fn select(
+        &self,
+        is_first_operand: bool,
+        classification: Classification,
+    ) -> Selection {
+        use Classification::*;
+
+        match (self, is_first_operand, classification) {
+            (Self::Union | Self::Difference, true, Outside) => Selection::Keep,
+            (Self::Intersection, true, Inside) => Selection::Keep,
+            (
+                Self::Union | Self::Intersection,
+                true,
+                Boundary {
+                    same_orientation: true,
+                },
+            ) => Selection::Keep,
+            (
+                Self::Difference,
+                true,
+                Boundary {
+                    same_orientation: false,
+                },
+            ) => Selection::Keep,
+
+            (Self::Union, false, Outside) => Selection::Keep,
+            (Self::Intersection, false, Inside) => Selection::Keep,
+            (Self::Difference, false, Inside) => Selection::KeepReversed,
+
+            _ => Selection::Discard,
+        }
+    }
+}
+
+enum Selection {
+    Keep,
+    KeepReversed,
+    Discard,
+}
+
+fn boolean(
+    a: &Solid,
+    b: &Solid,
+    op: BooleanOp,
+    core: &mut Core,
+) -> Result<Solid, BooleanError> {
+    let tolerance = core.layers.validation.config.distinct_min_distance;
+
+    let aabbs_overlap =
+        match (a.aabb(&core.layers.geometry), b.aabb(&core.layers.geometry)) {
+            (Some(a), Some(b)) => (0..3).all(|i| {
+                a.min.coords.components[i] <= b.max.coords.components[i]
+                    && b.min.coords.components[i] <= a.max.coords.components[i]
+            }),
+            _ => false,
+        };
+    if !aabbs_overlap {
+        return Ok(match op {
+            BooleanOp::Union => a.add_shells(b.shells().iter().cloned(), core),
+            BooleanOp::Difference => a.clone(),
+            BooleanOp::Intersection => Solid::empty(),
+        });
+    }
+
+    let solids = [a, b];
+    let a = Polyhedron::from_solid(a, &core.layers.geometry)?;
+    let b = Polyhedron::from_solid(b, &core.layers.geometry)?;
+
+    let mut cuts_a = vec![Vec::new(); a.faces.len()];
+    let mut cuts_b = vec![Vec::new(); b.faces.len()];
+
+    for (face_a, cuts_a) in a.faces.iter().zip(&mut cuts_a) {
+        for (face_b, cuts_b) in b.faces.iter().zip(&mut cuts_b) {
+            match FaceIntersection::compute(face_a, face_b, tolerance) {
+                Some(FaceIntersection::Segments(segments)) => {
+                    cuts_a.extend(segments.iter().copied());
+                    cuts_b.extend(segments);
+                }
+                Some(FaceIntersection::Coplanar) => {
+                    // Both faces need to be split along the boundary of the
+                    // other, so every resulting piece is either fully inside or
+                    // fully outside of the other face.
+                    cuts_a.extend(face_b.edges());
+                    cuts_b.extend(face_a.edges());
+                }
+                None => {}
+            }
+        }
+    }
+
+    let mut output = Vec::new();
+
+    for (is_first_operand, polyhedron, cuts, other, other_solid) in [
+        (true, &a, cuts_a, &b, solids[1]),
+        (false, &b, cuts_b, &a, solids[0]),
+    ] {
+        for (face, cuts) in polyhedron.faces.iter().zip(cuts) {
+            let cuts = cuts
+                .into_iter()
+                .map(|segment| segment.map(|point| face.frame.project(point)))
+                .collect::<Vec<_>>();
+
+            for piece in split_polygon(&face.cycles, &cuts, tolerance) {
+                let point = face.frame.lift(interior_point(&piece));
+                let classification = match other_solid.classify_point(
+                    point,
+                    tolerance,
+                    &core.layers.geometry,
+                ) {
+                    PointClassification::Inside => Classification::Inside,
+                    PointClassification::Outside => Classification::Outside,
+                    PointClassification::OnBoundary => {
+                        let same_orientation = other
+                            .boundary_orientation(
+                                point,
+                                face.frame.normal,
+                                tolerance,
+                            )
+                            .ok_or(BooleanError::AmbiguousClassification {
+                                point,
+                            })?;
+
+                        Classification::Boundary { same_orientation }
+                    }
+                };
+
+                let (frame, reverse) =
+                    match op.select(is_first_operand, classification) {
+                        Selection::Keep => (face.frame, false),
+                        Selection::KeepReversed => (face.frame.reverse(), true),
+                        Selection::Discard => continue,
+                    };
+
+                let cycles = piece
+                    .into_iter()
+                    .map(|cycle| {
+                        let mut cycle = cycle
+                            .into_iter()
+                            .map(|point| face.frame.lift(point))
+                            .collect::<Vec<_>>();
+                        if reverse {
+                            cycle.reverse();
+                        }
+                        cycle
+                    })
+                    .collect();
+
+                output.push(OutputFace {
+                    original: face.face.clone(),
+                    frame,
+                    cycles,
+                });
+            }
+        }
+    }
+
+    Ok(reassemble(output, tolerance, core))
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Aabb, Point, Scalar, Vector};
+
+    use crate::{
+        operations::{
+            build::{BuildRegion, BuildSketch},
+            insert::Insert,
+            sweep::SweepSketch,
+            transform::TransformObject,
+            update::UpdateSketch,
+        },
+        topology::{Region, Sketch, Solid},
+        Core,
+    };
+
+    use super::{BooleanError, Difference, Intersection, Union};
+
+    fn cuboid(
+        min: impl Into<Point<3>>,
+        max: impl Into<Point<3>>,
+        core: &mut Core,
+    ) -> Solid {
+        let [min, max] = [min.into(), max.into()];
+
+        let surface = core.layers.topology.surfaces.xy_plane();
+        let sweep_path =
+            Vector::from([Scalar::ZERO, Scalar::ZERO, max.z - min.z]);
+
+        let solid = Sketch::empty(&core.layers.topology)
+            .add_regions(
+                [Region::polygon(
+                    [
+                        [min.x, min.y],
+                        [max.x, min.y],
+                        [max.x, max.y],
+                        [min.x, max.y],
+                    ],
+                    core.layers.topology.surfaces.space_2d(),
+                    core,
+                )],
+                core,
+            )
+            .sweep_sketch(surface, sweep_path, core);
+
+        solid.translate([Scalar::ZERO, Scalar::ZERO, min.z], core)
+    }
+
+    fn aabb(solid: &Solid, core: &Core) -> Aabb<3> {
+        let geometry = &core.layers.geometry;
+
+        Aabb::<3>::from_points(
+            solid
+                .shells()
+                .iter()
+                .flat_map(|shell| shell.faces())
+                .flat_map(|face| {
+                    let surface = geometry.of_surface(face.surface());
+
+                    face.region().exterior().half_edges().iter().map(
+                        move |half_edge| {
+                            surface.point_from_surface_coords(
+                                geometry
+                                    .of_half_edge(half_edge)
+                                    .start_position(),
+                            )
+                        },
+                    )
+                }),
+        )
+    }
+
+    fn num_faces(solid: &Solid) -> usize {
+        solid.shells().iter().map(|shell| shell.faces().len()).sum()
+    }
+
+    #[test]
+    fn union_of_overlapping_cuboids() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let a = cuboid([0., 0., 0.], [2., 2., 2.], &mut core);
+        let b = cuboid([1., 1., 1.], [3., 3., 3.], &mut core);
+
+        let union = a.union(&b, &mut core)?.insert(&mut core);
+        core.layers.validation.take_errors()?;
+
+        assert_eq!(union.shells().len(), 1);
+        assert_eq!(num_faces(&union), 12);
+
+        let aabb = aabb(&union, &core);
+        assert!(
+            aabb.min.distance_to(&Point::from([0., 0., 0.]))
+                < Scalar::from(1e-12)
+        );
+        assert!(
+            aabb.max.distance_to(&Point::from([3., 3., 3.]))
+                < Scalar::from(1e-12)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn difference_of_overlapping_cuboids() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let a = cuboid([0., 0., 0.], [2., 2., 2.], &mut core);
+        let b = cuboid([1., 1., 1.], [3., 3., 3.], &mut core);
+
+        let difference = a.difference(&b, &mut core)?.insert(&mut core);
+        core.layers.validation.take_errors()?;
+
+        assert_eq!(difference.shells().len(), 1);
+        assert_eq!(num_faces(&difference), 9);
+
+        Ok(())
+    }
+
+    #[test]
+    fn intersection_of_overlapping_cuboids() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let a = cuboid([0., 0., 0.], [2., 2., 2.], &mut core);
+        let b = cuboid([1., 1., 1.], [3., 3., 3.], &mut core);
+
+        let intersection = a.intersection(&b, &mut core)?.insert(&mut core);
+        core.layers.validation.take_errors()?;
+
+        assert_eq!(intersection.shells().len(), 1);
+        assert_eq!(num_faces(&intersection), 6);
+
+        let aabb = aabb(&intersection, &core);
+        assert!(
+            aabb.min.distance_to(&Point::from([1., 1., 1.]))
+                < Scalar::from(1e-12)
+        );
+        assert!(
+            aabb.max.distance_to(&Point::from([2., 2., 2.]))
+                < Scalar::from(1e-12)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn difference_with_through_hole() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let a = cuboid([0., 0., 0.], [3., 3., 1.], &mut core);
+        let b = cuboid([1., 1., -1.], [2., 2., 2.], &mut core);
+
+        let difference = a.difference(&b, &mut core)?.insert(&mut core);
+        core.layers.validation.take_errors()?;
+
+        assert_eq!(difference.shells().len(), 1);
+        assert_eq!(num_faces(&difference), 10);
+
+        let faces_with_holes = difference
+            .shells()
+            .iter()
+            .flat_map(|shell| shell.faces())
+            .filter(|face| face.region().interiors().len() == 1)
+            .count();
+        assert_eq!(faces_with_holes, 2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn union_with_coplanar_faces() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let a = cuboid([0., 0., 0.], [2., 2., 1.], &mut core);
+        let b = cuboid([1., 1., 0.], [3., 3., 1.], &mut core);
+
+        let union = a.union(&b, &mut core)?.insert(&mut core);
+        core.layers.validation.take_errors()?;
+
+        assert_eq!(union.shells().len(), 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn disjoint_solids() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let a = cuboid([0., 0., 0.], [1., 1., 1.], &mut core);
+        let b = cuboid([2., 2., 2.], [3., 3., 3.], &mut core);
+
+        let union = a.union(&b, &mut core)?;
+        let difference = a.difference(&b, &mut core)?;
+        let intersection = a.intersection(&b, &mut core)?;
+
+        assert_eq!(union.shells().len(), 2);
+        assert_eq!(difference.shells().len(), 1);
+        assert_eq!(intersection.shells().len(), 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn overlapping_curved_solid() {
+        let mut core = Core::new();
+
+        let a = cuboid([0., 0., 0.], [2., 2., 2.], &mut core);
+        let b = Sketch::empty(&core.layers.topology)
+            .add_regions(
+                [Region::circle(
+                    [2., 2.],
+                    1.,
+                    core.layers.topology.surfaces.space_2d(),
+                    &mut core,
+                )],
+                &mut core,
+            )
+            .sweep_sketch(
+                core.layers.topology.surfaces.xy_plane(),
+                [0., 0., 1.],
+                &mut core,
+            );
+
+        assert!(matches!(
+            a.union(&b, &mut core),
+            Err(BooleanError::UnsupportedSurface { .. }
+                | BooleanError::UnsupportedHalfEdge { .. })
+        ));
+    }
+}
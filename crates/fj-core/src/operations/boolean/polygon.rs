@@ -0,0 +1,203 @@
+//! Helpers for working with planar polygons in 2D
+//!
+//! Polygons are represented as a list of cycles. The first cycle is the
+//! exterior, any further cycles are interiors (holes).
+
+use fj_math::{Point, Scalar, Vector};
+use itertools::Itertools;
+
+/// The position of a point relative to a polygon
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Containment {
+    /// The point is inside of the polygon
+    Inside,
+
+    /// The point is on the boundary of the polygon
+    Boundary,
+
+    /// The point is outside of the polygon
+    Outside,
+}
+
+/// Iterate over the edges of a cycle
+pub fn edges(cycle: &[Point<2>]) -> impl Iterator<Item = [Point<2>; 2]> + '_ {
+    cycle
+        .iter()
+        .copied()
+        .circular_tuple_windows()
+        .map(|(a, b)| [a, b])
+}
+
+/// Compute the signed area of a cycle
+///
+/// The area is positive, if the cycle is counter-clockwise.
+pub fn signed_area(cycle: &[Point<2>]) -> Scalar {
+    let mut sum = Scalar::ZERO;
+
+    for [a, b] in edges(cycle) {
+        sum += a.u * b.v - b.u * a.v;
+    }
+
+    sum / 2.
+}
+
+/// Compute the distance between a point and a line segment
+pub fn distance_to_segment(point: Point<2>, [a, b]: [Point<2>; 2]) -> Scalar {
+    (point - closest_point_on_segment(point, [a, b])).magnitude()
+}
+
+/// Compute the point on a line segment that is closest to the provided point
+pub fn closest_point_on_segment(
+    point: Point<2>,
+    [a, b]: [Point<2>; 2],
+) -> Point<2> {
+    let ab = b - a;
+    let length_squared = ab.dot(&ab);
+
+    if length_squared == Scalar::ZERO {
+        return a;
+    }
+
+    let t = (point - a).dot(&ab) / length_squared;
+    let t = t.max(Scalar::ZERO).min(Scalar::ONE);
+
+    a + ab * t
+}
+
+/// Determine the position of a point relative to a polygon
+///
+/// Points that are closer than `tolerance` to any edge of the polygon are
+/// considered to be on its boundary.
+pub fn containment(
+    cycles: &[Vec<Point<2>>],
+    point: Point<2>,
+    tolerance: Scalar,
+) -> Containment {
+    let mut inside = false;
+
+    for cycle in cycles {
+        for [a, b] in edges(cycle) {
+            if distance_to_segment(point, [a, b]) < tolerance {
+                return Containment::Boundary;
+            }
+
+            if (a.v > point.v) != (b.v > point.v) {
+                let u = a.u + (point.v - a.v) / (b.v - a.v) * (b.u - a.u);
+                if point.u < u {
+                    inside = !inside;
+                }
+            }
+        }
+    }
+
+    if inside {
+        Containment::Inside
+    } else {
+        Containment::Outside
+    }
+}
+
+/// Find a point that is well inside of the polygon
+///
+/// Casts horizontal scan lines between the vertices of the polygon, and picks
+/// the center of the widest span that is inside of the polygon.
+///
+/// # Panics
+///
+/// Panics, if the polygon has no area.
+pub fn interior_point(cycles: &[Vec<Point<2>>]) -> Point<2> {
+    let mut vs = cycles
+        .iter()
+        .flatten()
+        .map(|point| point.v)
+        .collect::<Vec<_>>();
+    vs.sort();
+    vs.dedup();
+
+    let mut best: Option<(Scalar, Point<2>)> = None;
+
+    for (v_a, v_b) in vs.into_iter().tuple_windows() {
+        let v = (v_a + v_b) / 2.;
+        let height = v_b - v_a;
+
+        let mut crossings = cycles
+            .iter()
+            .flat_map(|cycle| edges(cycle))
+            .filter(|[a, b]| (a.v > v) != (b.v > v))
+            .map(|[a, b]| a.u + (v - a.v) / (b.v - a.v) * (b.u - a.u))
+            .collect::<Vec<_>>();
+        crossings.sort();
+
+        for (u_a, u_b) in crossings.into_iter().tuples() {
+            let quality = (u_b - u_a).min(height);
+            let point = Point::from([(u_a + u_b) / 2., v]);
+
+            if best.map_or(true, |(best, _)| quality > best) {
+                best = Some((quality, point));
+            }
+        }
+    }
+
+    let (_, point) =
+        best.expect("Can't find interior point of polygon with no area");
+    point
+}
+
+/// Compute the intervals on a line, in which it overlaps with a polygon
+///
+/// The line is defined by an origin and a direction, which must be normalized.
+/// The returned intervals are given in line coordinates, and include the
+/// boundary of the polygon. Intervals shorter than `tolerance` are ignored.
+pub fn line_intervals(
+    cycles: &[Vec<Point<2>>],
+    origin: Point<2>,
+    direction: Vector<2>,
+    tolerance: Scalar,
+) -> Vec<[Scalar; 2]> {
+    let normal = Vector::from([-direction.v, direction.u]);
+    let to_line_coords = |point: Point<2>| (point - origin).dot(&direction);
+
+    let mut params = Vec::new();
+
+    for cycle in cycles {
+        for [a, b] in edges(cycle) {
+            let distance_a = (a - origin).dot(&normal);
+            let distance_b = (b - origin).dot(&normal);
+
+            if distance_a.abs() <= tolerance {
+                params.push(to_line_coords(a));
+            }
+
+            let crosses_line = (distance_a > tolerance
+                && distance_b < -tolerance)
+                || (distance_a < -tolerance && distance_b > tolerance);
+            if crosses_line {
+                let t = distance_a / (distance_a - distance_b);
+                params.push(to_line_coords(a + (b - a) * t));
+            }
+        }
+    }
+
+    params.sort();
+    params.dedup_by(|b, a| *b - *a <= tolerance);
+
+    let mut intervals: Vec<[Scalar; 2]> = Vec::new();
+
+    for (t_a, t_b) in params.into_iter().tuple_windows() {
+        let center = origin + direction * ((t_a + t_b) / 2.);
+        if containment(cycles, center, tolerance) == Containment::Outside {
+            continue;
+        }
+
+        if let Some([_, end]) = intervals.last_mut() {
+            if *end == t_a {
+                *end = t_b;
+                continue;
+            }
+        }
+
+        intervals.push([t_a, t_b]);
+    }
+
+    intervals
+}
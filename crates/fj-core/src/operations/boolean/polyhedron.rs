@@ -0,0 +1,251 @@
+//! A polygonal representation of solids
+//!
+//! See [`Polyhedron`].
+
+use fj_math::{Aabb, Point, Scalar, Vector};
+
+use crate::{
+    geometry::{Geometry, GlobalPath, SurfaceGeom, SurfacePath, SweptCurve},
+    storage::Handle,
+    topology::{Face, Solid},
+};
+
+use super::{
+    polygon::{containment, edges, Containment},
+    BooleanError,
+};
+
+/// A solid that is bounded by planar faces with straight edges
+pub struct Polyhedron {
+    /// The faces of the polyhedron
+    pub faces: Vec<PolyFace>,
+}
+
+impl Polyhedron {
+    /// Convert a solid into a polyhedron
+    ///
+    /// Returns an error, if the solid has faces that are not planar, or edges
+    /// that are not straight.
+    pub fn from_solid(
+        solid: &Solid,
+        geometry: &Geometry,
+    ) -> Result<Self, BooleanError> {
+        let faces = solid
+            .shells()
+            .iter()
+            .flat_map(|shell| shell.faces())
+            .map(|face| PolyFace::from_face(face, geometry))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self { faces })
+    }
+
+    /// Find the face of the polyhedron that the point is on
+    ///
+    /// Returns whether that face has the same orientation as the provided
+    /// normal, or `None`, if the point is not on the boundary of the
+    /// polyhedron.
+    pub fn boundary_orientation(
+        &self,
+        point: Point<3>,
+        normal: Vector<3>,
+        tolerance: Scalar,
+    ) -> Option<bool> {
+        self.faces.iter().find_map(|face| {
+            let distance = (point - face.frame.origin).dot(&face.frame.normal);
+            if distance.abs() >= tolerance {
+                return None;
+            }
+
+            let point = face.frame.project(point);
+            if containment(&face.cycles, point, tolerance)
+                == Containment::Outside
+            {
+                return None;
+            }
+
+            Some(normal.dot(&face.frame.normal) > Scalar::ZERO)
+        })
+    }
+}
+
+/// The position of a point relative to a polyhedron
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Classification {
+    /// The point is inside of the polyhedron
+    Inside,
+
+    /// The point is outside of the polyhedron
+    Outside,
+
+    /// The point is on the boundary of the polyhedron
+    Boundary {
+        /// Whether the face the point is on has the same orientation
+        same_orientation: bool,
+    },
+}
+
+/// A planar face of a [`Polyhedron`]
+pub struct PolyFace {
+    /// The face that this polygon was created from
+    pub face: Handle<Face>,
+
+    /// The coordinate system of the plane that the face is in
+    pub frame: Frame,
+
+    /// The cycles that bound the face, in the coordinates of `frame`
+    ///
+    /// The first cycle is the exterior, which is always counter-clockwise. All
+    /// other cycles are interiors.
+    pub cycles: Vec<Vec<Point<2>>>,
+
+    /// The bounding box of the face
+    pub aabb: Aabb<3>,
+}
+
+impl PolyFace {
+    fn from_face(
+        face: &Handle<Face>,
+        geometry: &Geometry,
+    ) -> Result<Self, BooleanError> {
+        let surface = geometry.of_surface(face.surface());
+        let SurfaceGeom::Swept(SweptCurve {
+            u: GlobalPath::Line(_),
+            ..
+        }) = surface
+        else {
+            return Err(BooleanError::UnsupportedSurface {
+                face: face.clone(),
+            });
+        };
+
+        let cycles = face
+            .region()
+            .all_cycles()
+            .map(|cycle| {
+                cycle
+                    .half_edges()
+                    .iter()
+                    .map(|half_edge| {
+                        let half_edge = geometry.of_half_edge(half_edge);
+                        let SurfacePath::Line(_) = half_edge.path else {
+                            return Err(BooleanError::UnsupportedHalfEdge {
+                                face: face.clone(),
+                            });
+                        };
+
+                        Ok(surface.point_from_surface_coords(
+                            half_edge.start_position(),
+                        ))
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let frame = Frame::from_exterior(&cycles[0]);
+        let aabb = Aabb::<3>::from_points(cycles.iter().flatten().copied());
+        let cycles = cycles
+            .into_iter()
+            .map(|cycle| {
+                cycle
+                    .into_iter()
+                    .map(|point| frame.project(point))
+                    .collect()
+            })
+            .collect();
+
+        Ok(Self {
+            face: face.clone(),
+            frame,
+            cycles,
+            aabb,
+        })
+    }
+
+    /// Iterate over all edges of the face, in 3D
+    pub fn edges(&self) -> impl Iterator<Item = [Point<3>; 2]> + '_ {
+        self.cycles.iter().flat_map(|cycle| {
+            edges(cycle)
+                .map(|points| points.map(|point| self.frame.lift(point)))
+        })
+    }
+}
+
+/// An orthonormal coordinate system of a plane
+#[derive(Clone, Copy, Debug)]
+pub struct Frame {
+    /// The origin of the coordinate system
+    pub origin: Point<3>,
+
+    /// The u-axis of the coordinate system
+    pub u: Vector<3>,
+
+    /// The v-axis of the coordinate system
+    pub v: Vector<3>,
+
+    /// The normal of the plane
+    ///
+    /// This is the cross product of `u` and `v`.
+    pub normal: Vector<3>,
+}
+
+impl Frame {
+    /// Create a frame from the exterior cycle of a face
+    ///
+    /// The normal of the frame points to the side, from which the exterior
+    /// appears counter-clockwise.
+    pub fn from_exterior(exterior: &[Point<3>]) -> Self {
+        // Newell's method is robust against collinear and concave vertices.
+        let mut normal = Vector::from([0., 0., 0.]);
+        for (i, a) in exterior.iter().enumerate() {
+            let b = exterior[(i + 1) % exterior.len()];
+
+            normal.x += (a.y - b.y) * (a.z + b.z);
+            normal.y += (a.z - b.z) * (a.x + b.x);
+            normal.z += (a.x - b.x) * (a.y + b.y);
+        }
+        let normal = normal.normalize();
+
+        let origin = exterior[0];
+        let u = exterior
+            .iter()
+            .map(|point| *point - origin)
+            .max_by_key(|vector| vector.magnitude())
+            .expect("Exterior cycle must not be empty");
+        let u = (u - normal * u.dot(&normal)).normalize();
+        let v = normal.cross(&u);
+
+        Self {
+            origin,
+            u,
+            v,
+            normal,
+        }
+    }
+
+    /// Return the frame of the same plane, with the opposite orientation
+    pub fn reverse(self) -> Self {
+        Self {
+            origin: self.origin,
+            u: self.v,
+            v: self.u,
+            normal: -self.normal,
+        }
+    }
+
+    /// Convert a point in 3D into the coordinates of the frame
+    pub fn project(&self, point: Point<3>) -> Point<2> {
+        let vector = point - self.origin;
+        Point::from([vector.dot(&self.u), vector.dot(&self.v)])
+    }
+
+    /// Convert a vector in 3D into the coordinates of the frame
+    pub fn project_vector(&self, vector: Vector<3>) -> Vector<2> {
+        Vector::from([vector.dot(&self.u), vector.dot(&self.v)])
+    }
+
+    /// Convert a point in the coordinates of the frame into 3D
+    pub fn lift(&self, point: Point<2>) -> Point<3> {
+        self.origin + self.u * point.u + self.v * point.v
+    }
+}
@@ -0,0 +1,325 @@
+//! Build a solid from the polygons that result from a boolean operation
+//!
+//! See [`reassemble`].
+
+use std::collections::BTreeMap;
+
+use fj_math::{Line, Point, Scalar};
+
+use crate::{
+    geometry::{CurveBoundary, GlobalPath, HalfEdgeGeom, SurfacePath},
+    operations::{
+        build::BuildSurface, derive::DeriveFrom, geometry::UpdateCurveGeometry,
+        geometry::UpdateHalfEdgeGeometry, insert::Insert,
+    },
+    storage::Handle,
+    topology::{
+        Curve, Cycle, Face, HalfEdge, Region, Shell, Solid, Surface, Vertex,
+    },
+    Core,
+};
+
+use super::{polygon::signed_area, polyhedron::Frame};
+
+/// A face that is part of the result of a boolean operation
+pub struct OutputFace {
+    /// The face that this face was created from
+    pub original: Handle<Face>,
+
+    /// The coordinate system of the plane that the face is in
+    ///
+    /// The normal of the frame points outside of the result.
+    pub frame: Frame,
+
+    /// The cycles that bound the face
+    ///
+    /// The first cycle is the exterior, which must be counter-clockwise, when
+    /// viewed from the direction of the frame's normal. All other cycles are
+    /// interiors.
+    pub cycles: Vec<Vec<Point<3>>>,
+}
+
+/// Build a solid from the provided faces
+///
+/// Vertices that are closer than `tolerance` are merged, and edges are split
+/// where a vertex lies on them. Faces that are connected via their edges end up
+/// in the same shell.
+pub fn reassemble(
+    faces: Vec<OutputFace>,
+    tolerance: Scalar,
+    core: &mut Core,
+) -> Solid {
+    let mut positions: Vec<Point<3>> = Vec::new();
+
+    let faces = faces
+        .into_iter()
+        .map(|face| {
+            let cycles = face
+                .cycles
+                .iter()
+                .map(|cycle| {
+                    cycle
+                        .iter()
+                        .map(|point| weld(&mut positions, *point, tolerance))
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>();
+
+            (face, cycles)
+        })
+        .collect::<Vec<_>>();
+
+    // Neighboring faces might have been split at different points along their
+    // shared edges. Make sure both have all vertices on those edges, so every
+    // edge can have a sibling.
+    let faces = faces
+        .into_iter()
+        .filter_map(|(face, cycles)| {
+            let mut cycles = cycles
+                .into_iter()
+                .map(|cycle| {
+                    let cycle = split_edges(&cycle, &positions, tolerance);
+                    remove_degenerate_vertices(cycle)
+                })
+                .collect::<Vec<_>>();
+
+            let exterior_area = {
+                let exterior = cycles[0]
+                    .iter()
+                    .map(|index| face.frame.project(positions[*index]))
+                    .collect::<Vec<_>>();
+                signed_area(&exterior)
+            };
+            if cycles[0].len() < 3 || exterior_area <= tolerance * tolerance {
+                return None;
+            }
+
+            cycles.retain(|cycle| cycle.len() >= 3);
+
+            Some((face, cycles))
+        })
+        .collect::<Vec<_>>();
+
+    let mut vertices: BTreeMap<usize, Handle<Vertex>> = BTreeMap::new();
+
+    // Each pair of half-edges that share the same vertices share a curve. The
+    // curve coordinate system is defined such that the vertex with the lower
+    // index is at `0`, the other one at `1`.
+    let mut curves: BTreeMap<[usize; 2], Handle<Curve>> = BTreeMap::new();
+    let mut shells_by_edge = UnionFind::new(faces.len());
+    let mut faces_by_edge: BTreeMap<[usize; 2], usize> = BTreeMap::new();
+
+    let mut built_faces = Vec::new();
+
+    for (i, (face, cycles)) in faces.into_iter().enumerate() {
+        let surface = Surface::from_uv(
+            GlobalPath::Line(Line::from_origin_and_direction(
+                face.frame.origin,
+                face.frame.u,
+            )),
+            face.frame.v,
+            core,
+        );
+
+        let mut cycles = cycles.into_iter().map(|cycle| {
+            let half_edges = (0..cycle.len())
+                .map(|j| {
+                    let start = cycle[j];
+                    let end = cycle[(j + 1) % cycle.len()];
+
+                    let mut edge = [start, end];
+                    edge.sort();
+
+                    if let Some(k) = faces_by_edge.insert(edge, i) {
+                        shells_by_edge.union(i, k);
+                    }
+
+                    let curve = curves
+                        .entry(edge)
+                        .or_insert_with(|| Curve::new().insert(core))
+                        .clone();
+
+                    let path = SurfacePath::line_from_points_with_coords(
+                        edge.map(|index| {
+                            (
+                                if index == edge[0] { [0.] } else { [1.] },
+                                face.frame.project(positions[index]),
+                            )
+                        }),
+                    );
+                    let boundary = if start == edge[0] {
+                        CurveBoundary::default()
+                    } else {
+                        CurveBoundary::default().reverse()
+                    };
+
+                    curve.clone().make_path_on_surface(
+                        path.clone(),
+                        surface.clone(),
+                        &mut core.layers.geometry,
+                    );
+
+                    let vertex = vertices
+                        .entry(start)
+                        .or_insert_with(|| Vertex::new().insert(core))
+                        .clone();
+
+                    HalfEdge::new(curve, vertex).insert(core).set_geometry(
+                        HalfEdgeGeom { path, boundary },
+                        &mut core.layers.geometry,
+                    )
+                })
+                .collect::<Vec<_>>();
+
+            Cycle::new(half_edges).insert(core)
+        });
+
+        let exterior = cycles.next().expect("Face must have exterior");
+        let interiors = cycles.collect::<Vec<_>>();
+
+        let region = Region::new(exterior, interiors)
+            .insert(core)
+            .derive_from(face.original.region(), core);
+
+        built_faces.push(Face::new(surface, region).insert(core));
+    }
+
+    let mut shells: BTreeMap<usize, Vec<Handle<Face>>> = BTreeMap::new();
+    for (i, face) in built_faces.into_iter().enumerate() {
+        shells.entry(shells_by_edge.find(i)).or_default().push(face);
+    }
+
+    Solid::new(
+        shells
+            .into_values()
+            .map(|faces| Shell::new(faces).insert(core)),
+    )
+}
+
+fn weld(
+    positions: &mut Vec<Point<3>>,
+    point: Point<3>,
+    tolerance: Scalar,
+) -> usize {
+    if let Some(index) = positions
+        .iter()
+        .position(|position| (*position - point).magnitude() < tolerance)
+    {
+        return index;
+    }
+
+    positions.push(point);
+    positions.len() - 1
+}
+
+/// Insert all vertices that lie on the edges of the cycle into the cycle
+fn split_edges(
+    cycle: &[usize],
+    positions: &[Point<3>],
+    tolerance: Scalar,
+) -> Vec<usize> {
+    let mut split = Vec::new();
+
+    for (j, &start) in cycle.iter().enumerate() {
+        let end = cycle[(j + 1) % cycle.len()];
+        let [a, b] = [start, end].map(|index| positions[index]);
+
+        let mut on_edge = positions
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| *index != start && *index != end)
+            .filter_map(|(index, position)| {
+                let closest = closest_point_on_segment_3d(*position, [a, b]);
+                let distance = (*position - closest).magnitude();
+
+                (distance < tolerance).then(|| {
+                    let t = (*position - a).magnitude();
+                    (t, index)
+                })
+            })
+            .collect::<Vec<_>>();
+        on_edge.sort();
+
+        split.push(start);
+        split.extend(on_edge.into_iter().map(|(_, index)| index));
+    }
+
+    split
+}
+
+fn closest_point_on_segment_3d(
+    point: Point<3>,
+    [a, b]: [Point<3>; 2],
+) -> Point<3> {
+    let ab = b - a;
+    let length_squared = ab.dot(&ab);
+
+    if length_squared == Scalar::ZERO {
+        return a;
+    }
+
+    let t = (point - a).dot(&ab) / length_squared;
+    let t = t.max(Scalar::ZERO).min(Scalar::ONE);
+
+    a + ab * t
+}
+
+/// Remove repeated vertices and spikes from the cycle
+fn remove_degenerate_vertices(mut cycle: Vec<usize>) -> Vec<usize> {
+    loop {
+        let len = cycle.len();
+
+        cycle.dedup();
+        while cycle.len() > 1 && cycle.first() == cycle.last() {
+            cycle.pop();
+        }
+
+        // Remove spikes, where the cycle goes from one vertex to another and
+        // immediately back again.
+        if cycle.len() >= 3 {
+            if let Some(j) = (0..cycle.len())
+                .find(|&j| cycle[j] == cycle[(j + 2) % cycle.len()])
+            {
+                let spike = (j + 1) % cycle.len();
+                let duplicate = (j + 2) % cycle.len();
+
+                let mut remove = [spike, duplicate];
+                remove.sort();
+                cycle.remove(remove[1]);
+                cycle.remove(remove[0]);
+            }
+        }
+
+        if cycle.len() == len || cycle.len() < 3 {
+            return cycle;
+        }
+    }
+}
+
+/// Tracks which faces belong to the same shell
+struct UnionFind {
+    parents: Vec<usize>,
+}
+
+impl UnionFind {
+    fn new(len: usize) -> Self {
+        Self {
+            parents: (0..len).collect(),
+        }
+    }
+
+    fn find(&mut self, i: usize) -> usize {
+        let mut root = i;
+        while self.parents[root] != root {
+            root = self.parents[root];
+        }
+
+        self.parents[i] = root;
+        root
+    }
+
+    fn union(&mut self, a: usize, b: usize) {
+        let [a, b] = [a, b].map(|i| self.find(i));
+        self.parents[a] = b;
+    }
+}
@@ -1,4 +1,4 @@
-use fj_math::{Arc, Point, Scalar};
+use fj_math::{Arc, Point, Scalar, Spline};
 
 use crate::{
     geometry::{CurveBoundary, HalfEdgeGeom, LocalCurveGeom, SurfacePath},
@@ -31,7 +31,7 @@ pub trait BuildHalfEdge {
         start_vertex: Handle<Vertex>,
         core: &mut Core,
     ) -> Handle<HalfEdge> {
-        let mut geometry = *core.layers.geometry.of_half_edge(sibling);
+        let mut geometry = core.layers.geometry.of_half_edge(sibling).clone();
         geometry.boundary = geometry.boundary.reverse();
 
         HalfEdge::new(sibling.curve().clone(), start_vertex)
@@ -68,7 +68,7 @@ pub trait BuildHalfEdge {
         core.layers.geometry.define_curve(
             half_edge.curve().clone(),
             surface,
-            LocalCurveGeom { path },
+            LocalCurveGeom { path: path.clone() },
         );
         core.layers.geometry.define_half_edge(
             half_edge.clone(),
@@ -97,7 +97,36 @@ pub trait BuildHalfEdge {
         core.layers.geometry.define_curve(
             half_edge.curve().clone(),
             surface,
-            LocalCurveGeom { path },
+            LocalCurveGeom { path: path.clone() },
+        );
+        core.layers.geometry.define_half_edge(
+            half_edge.clone(),
+            HalfEdgeGeom {
+                path,
+                boundary: boundary.into(),
+            },
+        );
+
+        half_edge
+    }
+
+    /// Create a spline
+    ///
+    /// The boundary of the half-edge is the domain of the spline.
+    fn spline(
+        spline: Spline<2>,
+        surface: Handle<Surface>,
+        core: &mut Core,
+    ) -> Handle<HalfEdge> {
+        let boundary = spline.domain().map(|coord| Point::from([coord]));
+        let path = SurfacePath::Spline(spline);
+
+        let half_edge = HalfEdge::unjoined(core).insert(core);
+
+        core.layers.geometry.define_curve(
+            half_edge.curve().clone(),
+            surface,
+            LocalCurveGeom { path: path.clone() },
         );
         core.layers.geometry.define_half_edge(
             half_edge.clone(),
@@ -136,7 +165,8 @@ pub trait BuildHalfEdge {
                     .expect("Curve geometry was just defined in same function")
                     .local_on(&surface)
                     .expect("Curve geometry was just defined in same function")
-                    .path,
+                    .path
+                    .clone(),
                 boundary: boundary.unwrap_or_default(),
             },
         );
@@ -1,10 +1,10 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
 
-use fj_interop::ext::ArrayExt;
-use fj_math::Point;
+use fj_interop::{ext::ArrayExt, Mesh};
+use fj_math::{Line, Point, Scalar, Triangle, Vector};
 
 use crate::{
-    geometry::{CurveBoundary, HalfEdgeGeom},
+    geometry::{CurveBoundary, GlobalPath, HalfEdgeGeom, SurfacePath},
     operations::{
         build::{BuildFace, BuildHalfEdge, BuildSurface, Polygon},
         geometry::{UpdateCurveGeometry, UpdateHalfEdgeGeometry},
@@ -15,7 +15,8 @@ use crate::{
             UpdateCycle, UpdateFace, UpdateHalfEdge, UpdateRegion, UpdateShell,
         },
     },
-    topology::{Curve, Face, HalfEdge, Shell, Surface, Vertex},
+    storage::Handle,
+    topology::{Curve, Cycle, Face, HalfEdge, Region, Shell, Surface, Vertex},
     Core,
 };
 
@@ -116,7 +117,8 @@ pub trait BuildShell {
                                                 "Curve geometry was just \
                                                 defined in same function",
                                             )
-                                            .path,
+                                            .path
+                                            .clone(),
                                         boundary,
                                     },
                                     &mut core.layers.geometry,
@@ -141,6 +143,128 @@ pub trait BuildShell {
         Shell::empty().add_faces(faces, core)
     }
 
+    /// Build a polyhedron from a triangle mesh, merging coplanar triangles
+    ///
+    /// Unlike [`BuildShell::from_vertices_and_indices`], which creates one face
+    /// per triangle, this method merges adjacent triangles that lie in the same
+    /// plane into a single face. Where the merged triangles surround other
+    /// parts of the mesh, the resulting face has holes.
+    ///
+    /// Triangles are considered coplanar, if their vertices are no further than
+    /// half of [`ValidationConfig::identical_max_distance`] from a common
+    /// plane. Triangles that don't span any area are ignored.
+    ///
+    /// Expects the mesh to be closed and consistently oriented, with the
+    /// counter-clockwise sides of all triangles facing outward. This is the
+    /// case for meshes that have been exported by Fornjot, and for most STL
+    /// files. Triangles that share a directed edge with another triangle,
+    /// which happens in non-manifold or inconsistently oriented meshes, are
+    /// not merged with any other triangles.
+    ///
+    /// [`ValidationConfig::identical_max_distance`]:
+    /// crate::validation::ValidationConfig::identical_max_distance
+    fn from_triangle_mesh(mesh: &Mesh<Point<3>>, core: &mut Core) -> Shell {
+        let points = mesh.vertices().collect::<Vec<_>>();
+        let triangles = mesh
+            .indices()
+            .map(|index| index as usize)
+            .collect::<Vec<_>>()
+            .chunks(3)
+            .map(|indices| [indices[0], indices[1], indices[2]])
+            .filter(|triangle| {
+                Triangle::from_points(triangle.map(|index| points[index]))
+                    .is_ok()
+            })
+            .collect::<Vec<_>>();
+
+        let tolerance =
+            core.layers.validation.config.identical_max_distance / 2.;
+        let planar_faces =
+            merge_coplanar_triangles(&points, &triangles, tolerance);
+
+        let mut vertices = BTreeMap::new();
+        let mut curves = BTreeMap::<_, Handle<Curve>>::new();
+
+        let faces = planar_faces
+            .into_iter()
+            .map(|planar_face| {
+                let surface = Surface::from_uv(
+                    GlobalPath::Line(Line::from_origin_and_direction(
+                        planar_face.plane.origin,
+                        planar_face.plane.u,
+                    )),
+                    planar_face.plane.v,
+                    core,
+                );
+
+                let mut cycles = planar_face.cycles.into_iter().map(|cycle| {
+                    let half_edges = cycle
+                        .iter()
+                        .copied()
+                        .zip(cycle.iter().copied().cycle().skip(1))
+                        .map(|(a, b)| {
+                            let vertex = vertices
+                                .entry(a)
+                                .or_insert_with(|| Vertex::new().insert(core))
+                                .clone();
+
+                            // The first half-edge that refers to a curve gets
+                            // the default boundary, its sibling the reversed
+                            // one.
+                            let (curve, boundary) = match curves.get(&[b, a]) {
+                                Some(curve) => (
+                                    curve.clone(),
+                                    CurveBoundary::default().reverse(),
+                                ),
+                                None => {
+                                    let curve = Curve::new().insert(core);
+                                    curves.insert([a, b], curve.clone());
+                                    (curve, CurveBoundary::default())
+                                }
+                            };
+
+                            let path =
+                                SurfacePath::line_from_points_with_coords(
+                                    boundary.inner.zip_ext([a, b].map(
+                                        |index| {
+                                            planar_face
+                                                .plane
+                                                .project(points[index])
+                                        },
+                                    )),
+                                );
+                            let curve = curve.make_path_on_surface(
+                                path.clone(),
+                                surface.clone(),
+                                &mut core.layers.geometry,
+                            );
+
+                            HalfEdge::unjoined(core)
+                                .update_start_vertex(|_, _| vertex, core)
+                                .update_curve(|_, _| curve, core)
+                                .insert(core)
+                                .set_geometry(
+                                    HalfEdgeGeom { path, boundary },
+                                    &mut core.layers.geometry,
+                                )
+                        })
+                        .collect::<Vec<_>>();
+
+                    Cycle::new(half_edges).insert(core)
+                });
+
+                let exterior = cycles
+                    .next()
+                    .expect("Planar face has at least an exterior cycle");
+                let region = Region::new(exterior, cycles).insert(core);
+
+                Face::new(surface, region)
+            })
+            .collect::<Vec<_>>();
+
+        Shell::empty().add_faces(faces, core)
+    }
+
     /// Build a tetrahedron from the provided points
     ///
     /// Accepts 4 points, naturally. For the purposes of the following
@@ -322,6 +446,261 @@ pub trait BuildShell {
 
 impl BuildShell for Shell {}
 
+/// A face made up of coplanar triangles
+struct PlanarFace {
+    plane: Plane,
+
+    /// The vertex indices of the cycles that bound the face
+    ///
+    /// The first cycle is the exterior one, all others are interiors.
+    cycles: Vec<Vec<usize>>,
+}
+
+/// A plane with an orthonormal coordinate system
+struct Plane {
+    origin: Point<3>,
+    u: Vector<3>,
+    v: Vector<3>,
+    normal: Vector<3>,
+}
+
+impl Plane {
+    fn from_triangle([a, b, c]: [Point<3>; 3]) -> Self {
+        let u = (b - a).normalize();
+        let normal = (b - a).cross(&(c - a)).normalize();
+        let v = normal.cross(&u);
+
+        Self {
+            origin: a,
+            u,
+            v,
+            normal,
+        }
+    }
+
+    fn distance_to(&self, point: Point<3>) -> Scalar {
+        (point - self.origin).dot(&self.normal).abs()
+    }
+
+    fn project(&self, point: Point<3>) -> Point<2> {
+        let offset = point - self.origin;
+        Point::from([offset.dot(&self.u), offset.dot(&self.v)])
+    }
+}
+
+/// Group the triangles of a mesh into planar faces
+///
+/// Grows each face from a seed triangle, adding adjacent triangles as long as
+/// they lie in the seed triangle's plane. Larger triangles are used as seeds
+/// first, as they define their plane most precisely.
+///
+/// Triangles that share a directed edge with another triangle have no
+/// well-defined neighbor across that edge. Each of them becomes a face of its
+/// own.
+fn merge_coplanar_triangles(
+    points: &[Point<3>],
+    triangles: &[[usize; 3]],
+    tolerance: Scalar,
+) -> Vec<PlanarFace> {
+    let edges = |[a, b, c]: [usize; 3]| [[a, b], [b, c], [c, a]];
+    let triangle_points = |triangle: [usize; 3]| triangle.map(|i| points[i]);
+
+    let mut triangles_by_edge = BTreeMap::new();
+    let mut is_ambiguous = vec![false; triangles.len()];
+    for (i, &triangle) in triangles.iter().enumerate() {
+        for edge in edges(triangle) {
+            if let Some(other) = triangles_by_edge.insert(edge, i) {
+                is_ambiguous[i] = true;
+                is_ambiguous[other] = true;
+            }
+        }
+    }
+
+    let mut seeds = (0..triangles.len()).collect::<Vec<_>>();
+    seeds.sort_by_key(|&i| {
+        let [a, b, c] = triangle_points(triangles[i]);
+        std::cmp::Reverse((b - a).cross(&(c - a)).magnitude())
+    });
+
+    let mut groups: Vec<Vec<usize>> = Vec::new();
+    let mut group_of = vec![None; triangles.len()];
+
+    for seed in seeds {
+        if group_of[seed].is_some() {
+            continue;
+        }
+
+        let group_index = groups.len();
+        let plane = Plane::from_triangle(triangle_points(triangles[seed]));
+
+        let mut group = vec![seed];
+        group_of[seed] = Some(group_index);
+
+        let mut queue = VecDeque::new();
+        if !is_ambiguous[seed] {
+            queue.push_back(seed);
+        }
+
+        while let Some(i) = queue.pop_front() {
+            for [a, b] in edges(triangles[i]) {
+                let Some(&neighbor) = triangles_by_edge.get(&[b, a]) else {
+                    continue;
+                };
+                if group_of[neighbor].is_some() || is_ambiguous[neighbor] {
+                    continue;
+                }
+
+                let neighbor_points = triangle_points(triangles[neighbor]);
+                let [p, q, r] = neighbor_points;
+                let faces_same_way =
+                    (q - p).cross(&(r - p)).dot(&plane.normal) > Scalar::ZERO;
+                let is_coplanar = neighbor_points
+                    .into_iter()
+                    .all(|point| plane.distance_to(point) <= tolerance);
+
+                if faces_same_way && is_coplanar {
+                    group_of[neighbor] = Some(group_index);
+                    group.push(neighbor);
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+
+        groups.push(group);
+    }
+
+    let mut faces = Vec::new();
+
+    for group in groups {
+        let plane = Plane::from_triangle(triangle_points(triangles[group[0]]));
+        let group_triangles =
+            group.iter().map(|&i| triangles[i]).collect::<Vec<_>>();
+
+        match boundary_cycles(&group_triangles, &plane, points) {
+            Some(cycles) => faces.push(PlanarFace { plane, cycles }),
+            None => {
+                // We couldn't figure out a clean boundary for the merged face.
+                // That is a rare case, and we can always fall back to not
+                // merging the triangles.
+                for triangle in group_triangles {
+                    faces.push(PlanarFace {
+                        plane: Plane::from_triangle(triangle_points(triangle)),
+                        cycles: vec![triangle.to_vec()],
+                    });
+                }
+            }
+        }
+    }
+
+    faces
+}
+
+/// Compute the cycles that bound a group of coplanar triangles
+///
+/// Returns the exterior cycle first, followed by the interior ones. Returns
+/// `None`, if the boundary doesn't consist of exactly one exterior cycle and
+/// any number of interior cycles.
+fn boundary_cycles(
+    triangles: &[[usize; 3]],
+    plane: &Plane,
+    points: &[Point<3>],
+) -> Option<Vec<Vec<usize>>> {
+    let edges = triangles
+        .iter()
+        .flat_map(|&[a, b, c]| [[a, b], [b, c], [c, a]])
+        .collect::<BTreeSet<_>>();
+
+    // Edges that are shared by two triangles of the group are internal to the
+    // face. All others are on its boundary.
+    let mut boundary = BTreeMap::<usize, Vec<usize>>::new();
+    for &[a, b] in &edges {
+        if !edges.contains(&[b, a]) {
+            boundary.entry(a).or_default().push(b);
+        }
+    }
+
+    let position = |index: usize| plane.project(points[index]);
+
+    let mut cycles = Vec::new();
+    while let Some((&start, _)) = boundary.iter().next() {
+        let mut cycle = vec![start];
+        let mut previous = start;
+        let mut current = take_next(&mut boundary, start, None)?;
+
+        while current != start {
+            cycle.push(current);
+
+            // A vertex can be on the boundary multiple times, if multiple
+            // cycles touch there. In that case, pick the outgoing edge that
+            // turns most clockwise from the incoming edge, which keeps the
+            // face on the left of the cycle.
+            let incoming = position(current) - position(previous);
+            let next =
+                take_next(&mut boundary, current, Some((incoming, &position)))?;
+
+            previous = current;
+            current = next;
+        }
+
+        cycles.push(cycle);
+    }
+
+    let signed_area = |cycle: &Vec<usize>| {
+        cycle
+            .iter()
+            .zip(cycle.iter().cycle().skip(1))
+            .map(|(&a, &b)| {
+                let [a, b] = [a, b].map(position);
+                a.u * b.v - b.u * a.v
+            })
+            .fold(Scalar::ZERO, |sum, area| sum + area)
+    };
+
+    let (exteriors, interiors): (Vec<_>, Vec<_>) = cycles
+        .into_iter()
+        .partition(|cycle| signed_area(cycle) > Scalar::ZERO);
+    let [exterior] = <[_; 1]>::try_from(exteriors).ok()?;
+
+    Some([exterior].into_iter().chain(interiors).collect())
+}
+
+/// Remove an outgoing boundary edge from a vertex, returning its end vertex
+fn take_next(
+    boundary: &mut BTreeMap<usize, Vec<usize>>,
+    vertex: usize,
+    incoming: Option<(Vector<2>, &dyn Fn(usize) -> Point<2>)>,
+) -> Option<usize> {
+    let outgoing = boundary.get_mut(&vertex)?;
+
+    let index = match incoming {
+        Some((incoming, position)) if outgoing.len() > 1 => {
+            let back = -incoming;
+            let clockwise_angle = |next: usize| {
+                let direction = position(next) - position(vertex);
+                let [back, direction] = [back, direction].map(|vector| {
+                    vector.v.into_f64().atan2(vector.u.into_f64())
+                });
+                (back - direction).rem_euclid(std::f64::consts::TAU)
+            };
+
+            (0..outgoing.len())
+                .min_by(|&a, &b| {
+                    clockwise_angle(outgoing[a])
+                        .total_cmp(&clockwise_angle(outgoing[b]))
+                })
+                .expect("`outgoing` is not empty")
+        }
+        _ => 0,
+    };
+
+    let next = outgoing.swap_remove(index);
+    if outgoing.is_empty() {
+        boundary.remove(&vertex);
+    }
+
+    Some(next)
+}
+
 /// A tetrahedron
 ///
 /// A tetrahedron is constructed from 4 points and has 4 faces. For the purpose
@@ -345,3 +724,127 @@ pub struct TetrahedronShell<I: IsInserted = IsInsertedNo> {
     /// The face formed by the points `c`, `b`, and `d`.
     pub cbd: Polygon<3, IsInsertedYes>,
 }
+
+#[cfg(test)]
+mod tests {
+    use fj_interop::{Color, Mesh};
+
+    use crate::{
+        algorithms::{approx::Tolerance, triangulate::Triangulate},
+        operations::{
+            build::{BuildCycle, BuildRegion, BuildSketch},
+            insert::Insert,
+            reverse::Reverse,
+            sweep::SweepSketch,
+            update::{UpdateRegion, UpdateSketch},
+        },
+        topology::{Cycle, Region, Shell, Sketch},
+        Core,
+    };
+
+    use super::BuildShell;
+
+    fn sweep_region(region: Region, core: &mut Core) -> Shell {
+        let solid = Sketch::empty(&core.layers.topology)
+            .add_regions([region], core)
+            .sweep_sketch(
+                core.layers.topology.surfaces.xy_plane(),
+                [0., 0., 1.],
+                core,
+            );
+
+        solid.shells().only().clone_object()
+    }
+
+    #[test]
+    fn from_triangle_mesh_merges_coplanar_triangles() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let cuboid = sweep_region(
+            Region::polygon(
+                [[0., 0.], [2., 0.], [2., 1.], [0., 1.]],
+                core.layers.topology.surfaces.space_2d(),
+                &mut core,
+            ),
+            &mut core,
+        );
+        let mesh =
+            (&cuboid, Tolerance::from_scalar(0.01)?).triangulate(&mut core);
+        assert_eq!(mesh.triangles().count(), 12);
+
+        let shell =
+            Shell::from_triangle_mesh(&mesh, &mut core).insert(&mut core);
+        core.layers.validation.take_errors()?;
+
+        assert_eq!(shell.faces().len(), 6);
+        for face in shell.faces() {
+            assert_eq!(face.region().exterior().half_edges().len(), 4);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn from_triangle_mesh_creates_faces_with_holes() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let surface = core.layers.topology.surfaces.space_2d();
+        let region = Region::polygon(
+            [[-2., -2.], [2., -2.], [2., 2.], [-2., 2.]],
+            surface.clone(),
+            &mut core,
+        );
+        let hole = Cycle::polygon(
+            [[-1., -1.], [1., -1.], [1., 1.], [-1., 1.]],
+            surface,
+            &mut core,
+        )
+        .reverse(&mut core);
+        let region = region.add_interiors([hole], &mut core);
+
+        let plate = sweep_region(region, &mut core);
+        let mesh =
+            (&plate, Tolerance::from_scalar(0.01)?).triangulate(&mut core);
+
+        let shell =
+            Shell::from_triangle_mesh(&mesh, &mut core).insert(&mut core);
+        core.layers.validation.take_errors()?;
+
+        assert_eq!(shell.faces().len(), 2 + 4 + 4);
+        let faces_with_holes = shell
+            .faces()
+            .iter()
+            .filter(|face| face.region().interiors().len() == 1)
+            .count();
+        assert_eq!(faces_with_holes, 2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn from_triangle_mesh_does_not_merge_non_manifold_triangles() {
+        let mut core = Core::new();
+
+        let [a, b, c, d, e] = [
+            [0., 0., 0.],
+            [1., 0., 0.],
+            [1., 1., 0.],
+            [0., 1., 0.],
+            [0.5, 2., 0.],
+        ];
+
+        // The last two triangles both have the directed edge `a`-`c`, so it's
+        // unclear which of them is the neighbor of the first one.
+        let mut mesh = Mesh::new();
+        for triangle in [[a, b, c], [a, c, d], [a, c, e]] {
+            mesh.push_triangle(triangle, Color::default());
+        }
+
+        let shell = Shell::from_triangle_mesh(&mesh, &mut core);
+
+        assert_eq!(shell.faces().len(), 3);
+        for face in shell.faces() {
+            assert_eq!(face.region().exterior().half_edges().len(), 3);
+        }
+    }
+}
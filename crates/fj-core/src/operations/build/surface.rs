@@ -42,16 +42,51 @@ pub trait BuildSurface {
         u: impl Into<GlobalPath>,
         v: impl Into<Vector<3>>,
         core: &mut Core,
+    ) -> Handle<Surface> {
+        Surface::from_geometry(SurfaceGeom::swept(u, v), core)
+    }
+
+    /// Build a sphere
+    ///
+    /// See [`RevolvedCurve::sphere`] for the coordinate system of the sphere.
+    ///
+    /// [`RevolvedCurve::sphere`]: crate::geometry::RevolvedCurve::sphere
+    fn sphere(
+        center: impl Into<Point<3>>,
+        radius: impl Into<Scalar>,
+        core: &mut Core,
+    ) -> Handle<Surface> {
+        Surface::from_geometry(SurfaceGeom::sphere(center, radius), core)
+    }
+
+    /// Build a torus
+    ///
+    /// See [`RevolvedCurve::torus`] for the coordinate system of the torus.
+    ///
+    /// [`RevolvedCurve::torus`]: crate::geometry::RevolvedCurve::torus
+    fn torus(
+        center: impl Into<Point<3>>,
+        axis: impl Into<Vector<3>>,
+        major_radius: impl Into<Scalar>,
+        minor_radius: impl Into<Scalar>,
+        core: &mut Core,
+    ) -> Handle<Surface> {
+        Surface::from_geometry(
+            SurfaceGeom::torus(center, axis, major_radius, minor_radius),
+            core,
+        )
+    }
+
+    /// Build a surface from the provided geometry
+    fn from_geometry(
+        geometry: impl Into<SurfaceGeom>,
+        core: &mut Core,
     ) -> Handle<Surface> {
         let surface = Surface::new().insert(core);
 
-        core.layers.geometry.define_surface(
-            surface.clone(),
-            SurfaceGeom {
-                u: u.into(),
-                v: v.into(),
-            },
-        );
+        core.layers
+            .geometry
+            .define_surface(surface.clone(), geometry.into());
 
         surface
     }
@@ -73,7 +73,10 @@ impl AddHole for Shell {
                             [Cycle::empty().add_joined_edges(
                                 [(
                                     entry.clone(),
-                                    *core.layers.geometry.of_half_edge(&entry),
+                                    core.layers
+                                        .geometry
+                                        .of_half_edge(&entry)
+                                        .clone(),
                                 )],
                                 location.face.surface().clone(),
                                 core,
@@ -149,7 +152,10 @@ impl AddHole for Shell {
                             [Cycle::empty().add_joined_edges(
                                 [(
                                     entry.clone(),
-                                    *core.layers.geometry.of_half_edge(&entry),
+                                    core.layers
+                                        .geometry
+                                        .of_half_edge(&entry)
+                                        .clone(),
                                 )],
                                 entry_location.face.surface().clone(),
                                 core,
@@ -171,7 +177,10 @@ impl AddHole for Shell {
                             [Cycle::empty().add_joined_edges(
                                 [(
                                     exit.clone(),
-                                    *core.layers.geometry.of_half_edge(exit),
+                                    core.layers
+                                        .geometry
+                                        .of_half_edge(exit)
+                                        .clone(),
                                 )],
                                 exit_location.face.surface().clone(),
                                 core,
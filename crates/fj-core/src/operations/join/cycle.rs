@@ -111,7 +111,7 @@ impl JoinCycle for Cycle {
                         core,
                     )
                     .insert(core)
-                    .set_geometry(geometry, &mut core.layers.geometry);
+                    .set_geometry(geometry.clone(), &mut core.layers.geometry);
 
                 core.layers.geometry.define_curve(
                     half_edge.curve().clone(),
@@ -191,10 +191,10 @@ impl JoinCycle for Cycle {
                                 )
                                 .insert(core)
                                 .set_geometry(
-                                    *core
-                                        .layers
+                                    core.layers
                                         .geometry
-                                        .of_half_edge(half_edge),
+                                        .of_half_edge(half_edge)
+                                        .clone(),
                                     &mut core.layers.geometry,
                                 )]
                         },
@@ -210,10 +210,10 @@ impl JoinCycle for Cycle {
                                 )
                                 .insert(core)
                                 .set_geometry(
-                                    *core
-                                        .layers
+                                    core.layers
                                         .geometry
-                                        .of_half_edge(half_edge),
+                                        .of_half_edge(half_edge)
+                                        .clone(),
                                     &mut core.layers.geometry,
                                 )]
                         },
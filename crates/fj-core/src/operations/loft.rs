@@ -0,0 +1,651 @@
+//! Loft between multiple profiles
+//!
+//! See [`Loft`].
+
+use fj_interop::{ext::ArrayExt, Color};
+use fj_math::{Point, Scalar, Vector};
+
+use crate::{
+    geometry::{CurveBoundary, HalfEdgeGeom, SurfacePath},
+    operations::{
+        build::BuildSurface,
+        derive::DeriveFrom,
+        geometry::{UpdateCurveGeometry, UpdateHalfEdgeGeometry},
+        insert::Insert,
+        presentation::{GetColor, SetColor},
+        reverse::Reverse,
+        sweep::SweepCache,
+    },
+    storage::Handle,
+    topology::{
+        Curve, Cycle, Face, HalfEdge, Region, Shell, Solid, Surface, Vertex,
+    },
+    Core,
+};
+
+/// Loft between multiple profiles
+///
+/// Creates a solid that transitions between a number of profiles. Each profile
+/// is a region on a surface, and the profiles are connected in the order they
+/// are provided in. The first and the last profile become the caps of the
+/// resulting solid.
+///
+/// The profiles can be located on arbitrary surfaces, and the direction they
+/// point in doesn't matter. It is adjusted as required.
+///
+/// ## Correspondence Between Profiles
+///
+/// All regions must have the same number of cycles, and corresponding cycles
+/// must have the same number of half-edges. Cycles correspond to each other, if
+/// they have the same index within their region (the exterior being the first
+/// cycle). Half-edges correspond to each other, if they have the same index
+/// within their cycle, after that cycle has been wound counter-clockwise, when
+/// viewed from the direction of the loft.
+///
+/// Each pair of corresponding half-edges is connected by a planar side face,
+/// if they are located in the same plane. Otherwise, two triangular side faces
+/// are created.
+///
+/// ## Implementation Note
+///
+/// Only half-edges that are lines are supported right now. Lofting between
+/// arcs would require curved side faces, which can't be represented yet.
+pub trait Loft {
+    /// Loft between the profiles
+    ///
+    /// Returns an error, if fewer than two profiles are provided, or if the
+    /// profiles are not compatible, as described in the trait documentation.
+    fn loft(&self, core: &mut Core) -> Result<Solid, LoftError>;
+}
+
+impl Loft for [(Handle<Region>, Handle<Surface>)] {
+    fn loft(&self, core: &mut Core) -> Result<Solid, LoftError> {
+        if self.len() < 2 {
+            return Err(LoftError::TooFewProfiles);
+        }
+
+        let profiles = self
+            .iter()
+            .enumerate()
+            .map(|(i, (region, surface))| {
+                Profile::from_region(i, region, surface, core)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for (i, profile) in profiles.iter().enumerate().skip(1) {
+            if profile.cycles.len() != profiles[0].cycles.len() {
+                return Err(LoftError::CycleCountMismatch {
+                    profile: i,
+                    expected: profiles[0].cycles.len(),
+                    actual: profile.cycles.len(),
+                });
+            }
+
+            for (j, (cycle, first_cycle)) in
+                profile.cycles.iter().zip(&profiles[0].cycles).enumerate()
+            {
+                if cycle.len() != first_cycle.len() {
+                    return Err(LoftError::HalfEdgeCountMismatch {
+                        profile: i,
+                        cycle: j,
+                        expected: first_cycle.len(),
+                        actual: cycle.len(),
+                    });
+                }
+            }
+        }
+
+        // The direction of the loft at each profile. We use that to make sure
+        // that all profiles are wound the same way.
+        let directions = (0..profiles.len())
+            .map(|i| {
+                let previous = &profiles[i.saturating_sub(1)];
+                let next = &profiles[(i + 1).min(profiles.len() - 1)];
+
+                next.centroid - previous.centroid
+            })
+            .collect::<Vec<_>>();
+
+        let mut faces = Vec::new();
+
+        // The first face needs to point against the direction of the loft,
+        // the last one along it.
+        for (i, direction) in [
+            (0, -directions[0]),
+            (profiles.len() - 1, directions[directions.len() - 1]),
+        ] {
+            let (region, surface) = &self[i];
+
+            let region = if profiles[i].normal().dot(&direction) < Scalar::ZERO
+            {
+                region.reverse(core).insert(core).derive_from(region, core)
+            } else {
+                region.clone()
+            };
+
+            faces.push(Face::new(surface.clone(), region).insert(core));
+        }
+
+        let profiles = profiles
+            .into_iter()
+            .zip(&directions)
+            .map(|(profile, direction)| profile.wound_towards(*direction))
+            .collect::<Vec<_>>();
+
+        let mut cache = SweepCache::default();
+
+        for (i, (bottom, top)) in
+            profiles.iter().zip(&profiles[1..]).enumerate()
+        {
+            let (region, _) = &self[i];
+            let color = region.get_color(core);
+
+            for (bottom, top) in bottom.cycles.iter().zip(&top.cycles) {
+                connect_cycles(
+                    bottom, top, color, &mut cache, &mut faces, core,
+                );
+            }
+        }
+
+        Ok(Solid::new([Shell::new(faces).insert(core)]))
+    }
+}
+
+impl Loft for [Handle<Face>] {
+    fn loft(&self, core: &mut Core) -> Result<Solid, LoftError> {
+        self.iter()
+            .map(|face| (face.region().clone(), face.surface().clone()))
+            .collect::<Vec<_>>()
+            .loft(core)
+    }
+}
+
+/// Error lofting between profiles
+#[derive(Debug, Eq, PartialEq, thiserror::Error)]
+pub enum LoftError {
+    /// Fewer than two profiles were provided
+    #[error("Loft requires at least two profiles")]
+    TooFewProfiles,
+
+    /// A profile has a different number of cycles than the first one
+    #[error(
+        "Profile {profile} of loft has {actual} cycles (expected {expected})"
+    )]
+    CycleCountMismatch {
+        /// The index of the profile
+        profile: usize,
+
+        /// The number of cycles of the first profile
+        expected: usize,
+
+        /// The number of cycles of the profile
+        actual: usize,
+    },
+
+    /// A cycle has a different number of half-edges than the corresponding
+    /// cycle of the first profile
+    #[error(
+        "Cycle {cycle} of profile {profile} of loft has {actual} half-edges \
+        (expected {expected})"
+    )]
+    HalfEdgeCountMismatch {
+        /// The index of the profile
+        profile: usize,
+
+        /// The index of the cycle within the profile
+        cycle: usize,
+
+        /// The number of half-edges of the corresponding cycle of the first
+        /// profile
+        expected: usize,
+
+        /// The number of half-edges of the cycle
+        actual: usize,
+    },
+
+    /// A profile contains a half-edge that is not a line
+    #[error(
+        "Profile {profile} of loft contains a half-edge that is not a line"
+    )]
+    UnsupportedHalfEdge {
+        /// The index of the profile
+        profile: usize,
+    },
+}
+
+/// A region that is used as a profile of a loft
+struct Profile {
+    cycles: Vec<Vec<ProfileEdge>>,
+    centroid: Point<3>,
+}
+
+impl Profile {
+    fn from_region(
+        index: usize,
+        region: &Handle<Region>,
+        surface: &Handle<Surface>,
+        core: &Core,
+    ) -> Result<Self, LoftError> {
+        let surface = core.layers.geometry.of_surface(surface);
+
+        let cycles = region
+            .all_cycles()
+            .map(|cycle| {
+                cycle
+                    .half_edges()
+                    .iter()
+                    .map(|half_edge| {
+                        let geometry =
+                            core.layers.geometry.of_half_edge(half_edge);
+
+                        let SurfacePath::Line(_) = geometry.path else {
+                            return Err(LoftError::UnsupportedHalfEdge {
+                                profile: index,
+                            });
+                        };
+
+                        Ok(ProfileEdge {
+                            vertex: half_edge.start_vertex().clone(),
+                            position: surface.point_from_surface_coords(
+                                geometry.start_position(),
+                            ),
+                            curve: half_edge.curve().clone(),
+                            boundary: geometry.boundary,
+                        })
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let centroid = {
+            let exterior = &cycles[0];
+
+            let sum = exterior
+                .iter()
+                .fold(Vector::from([0., 0., 0.]), |sum, edge| {
+                    sum + edge.position.coords
+                });
+
+            Point {
+                coords: sum / exterior.len() as f64,
+            }
+        };
+
+        Ok(Self { cycles, centroid })
+    }
+
+    /// The normal of the exterior, computed using Newell's method
+    ///
+    /// The exterior is counter-clockwise, when viewed from the direction the
+    /// normal points to.
+    fn normal(&self) -> Vector<3> {
+        let exterior = &self.cycles[0];
+
+        (0..exterior.len())
+            .map(|i| {
+                let a = exterior[i].position - self.centroid;
+                let b =
+                    exterior[(i + 1) % exterior.len()].position - self.centroid;
+
+                a.cross(&b)
+            })
+            .fold(Vector::from([0., 0., 0.]), |sum, normal| sum + normal)
+    }
+
+    /// Make sure the exterior is wound counter-clockwise, when viewed from the
+    /// provided direction
+    fn wound_towards(self, direction: Vector<3>) -> Self {
+        if self.normal().dot(&direction) >= Scalar::ZERO {
+            return self;
+        }
+
+        // We need to reverse the cycles, but we want the first vertex to stay
+        // the same, so the correspondence between the profiles is predictable.
+        let cycles = self
+            .cycles
+            .into_iter()
+            .map(|cycle| {
+                let n = cycle.len();
+
+                (0..n)
+                    .map(|i| {
+                        let start = &cycle[(n - i) % n];
+                        let edge = &cycle[(2 * n - i - 1) % n];
+
+                        ProfileEdge {
+                            vertex: start.vertex.clone(),
+                            position: start.position,
+                            curve: edge.curve.clone(),
+                            boundary: edge.boundary.reverse(),
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+
+        Self {
+            cycles,
+            centroid: self.centroid,
+        }
+    }
+}
+
+/// A half-edge of a profile
+///
+/// The curve and boundary are those of the half-edge that starts at the vertex.
+#[derive(Clone)]
+struct ProfileEdge {
+    vertex: Handle<Vertex>,
+    position: Point<3>,
+    curve: Handle<Curve>,
+    boundary: CurveBoundary<Point<1>>,
+}
+
+/// Create the side faces that connect two corresponding cycles
+fn connect_cycles(
+    bottom: &[ProfileEdge],
+    top: &[ProfileEdge],
+    color: Option<Color>,
+    cache: &mut SweepCache,
+    faces: &mut Vec<Handle<Face>>,
+    core: &mut Core,
+) {
+    let tolerance = core.layers.validation.config.identical_max_distance;
+    let n = bottom.len();
+
+    // The curves that connect corresponding vertices of both cycles. Each of
+    // those starts at the bottom cycle (at `0`) and ends at the top cycle (at
+    // `1`). Like when sweeping, the curve is cached per vertex, so each vertex
+    // is connected by exactly one curve.
+    let rungs = bottom
+        .iter()
+        .map(|edge| {
+            cache
+                .curves
+                .entry(edge.vertex.id())
+                .or_insert_with(|| Curve::new().insert(core))
+                .clone()
+        })
+        .collect::<Vec<_>>();
+
+    let up = CurveBoundary::default();
+    let down = up.reverse();
+
+    for i in 0..n {
+        let j = (i + 1) % n;
+
+        let edge_bottom = bottom[i].clone();
+        let edge_up = ProfileEdge {
+            curve: rungs[j].clone(),
+            boundary: up,
+            ..bottom[j].clone()
+        };
+        let edge_top = ProfileEdge {
+            curve: top[i].curve.clone(),
+            boundary: top[i].boundary.reverse(),
+            ..top[j].clone()
+        };
+        let edge_down = ProfileEdge {
+            curve: rungs[i].clone(),
+            boundary: down,
+            ..top[i].clone()
+        };
+
+        let is_planar = {
+            let [a, b, c, d] = [&bottom[i], &bottom[j], &top[j], &top[i]]
+                .map(|edge| edge.position);
+            let normal = (b - a).cross(&(c - a)).normalize();
+
+            (d - a).dot(&normal).abs() < tolerance
+        };
+
+        if is_planar {
+            faces.push(planar_face(
+                [edge_bottom, edge_up, edge_top, edge_down],
+                color,
+                core,
+            ));
+        } else {
+            // The diagonal starts at the start of the bottom edge (at `0`) and
+            // ends at the end of the top edge (at `1`).
+            let diagonal = Curve::new().insert(core);
+
+            faces.push(planar_face(
+                [
+                    edge_bottom,
+                    edge_up,
+                    ProfileEdge {
+                        curve: diagonal.clone(),
+                        boundary: down,
+                        ..top[j].clone()
+                    },
+                ],
+                color,
+                core,
+            ));
+            faces.push(planar_face(
+                [
+                    ProfileEdge {
+                        curve: diagonal,
+                        boundary: up,
+                        ..bottom[i].clone()
+                    },
+                    edge_top,
+                    edge_down,
+                ],
+                color,
+                core,
+            ));
+        }
+    }
+}
+
+/// Build a planar face from the provided edges
+///
+/// Each edge ends where the next one starts. The first three edges must start
+/// at points that are not collinear, and define the orientation of the face.
+fn planar_face<const N: usize>(
+    edges: [ProfileEdge; N],
+    color: Option<Color>,
+    core: &mut Core,
+) -> Handle<Face> {
+    let (surface, _) =
+        Surface::plane_from_points([0, 1, 2].map(|i| edges[i].position), core);
+    let surface_geom = core.layers.geometry.of_surface(&surface).clone();
+
+    let half_edges = (0..N)
+        .map(|i| {
+            let edge = &edges[i];
+            let next = &edges[(i + 1) % N];
+
+            let path = SurfacePath::line_from_points_with_coords(
+                edge.boundary
+                    .inner
+                    .zip_ext([edge.position, next.position])
+                    .map(|(coords, position)| {
+                        (coords, surface_geom.project_global_point(position))
+                    }),
+            );
+
+            let curve = edge.curve.clone().make_path_on_surface(
+                path.clone(),
+                surface.clone(),
+                &mut core.layers.geometry,
+            );
+
+            HalfEdge::new(curve, edge.vertex.clone())
+                .insert(core)
+                .set_geometry(
+                    HalfEdgeGeom {
+                        path,
+                        boundary: edge.boundary,
+                    },
+                    &mut core.layers.geometry,
+                )
+        })
+        .collect::<Vec<_>>();
+
+    let region =
+        Region::new(Cycle::new(half_edges).insert(core), []).insert(core);
+
+    if let Some(color) = color {
+        region.set_color(color, core);
+    }
+
+    Face::new(surface, region).insert(core)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::FRAC_PI_4;
+
+    use fj_math::Vector;
+
+    use crate::{
+        operations::{
+            build::BuildFace, insert::Insert, transform::TransformObject,
+        },
+        storage::Handle,
+        topology::{Face, Solid},
+        Core,
+    };
+
+    use super::{Loft, LoftError};
+
+    fn square(
+        size: f64,
+        z: f64,
+        rotation: f64,
+        core: &mut Core,
+    ) -> Handle<Face> {
+        let half = size / 2.;
+        let surface = core.layers.topology.surfaces.xy_plane();
+
+        Face::polygon(
+            surface,
+            [[-half, -half], [half, -half], [half, half], [-half, half]],
+            core,
+        )
+        .rotate(Vector::from([0., 0., rotation]), core)
+        .translate([0., 0., z], core)
+        .insert(core)
+    }
+
+    fn num_faces(solid: &Solid) -> usize {
+        solid.shells().iter().map(|shell| shell.faces().len()).sum()
+    }
+
+    #[test]
+    fn loft_frustum() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let bottom = square(2., 0., 0., &mut core);
+        let top = square(1., 1., 0., &mut core);
+
+        let solid = [bottom, top].loft(&mut core)?.insert(&mut core);
+        core.layers.validation.take_errors()?;
+
+        // The side faces are planar, so each one is a single face.
+        assert_eq!(num_faces(&solid), 2 + 4);
+
+        Ok(())
+    }
+
+    #[test]
+    fn loft_twisted() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let bottom = square(2., 0., 0., &mut core);
+        let top = square(2., 1., FRAC_PI_4, &mut core);
+
+        let solid = [bottom, top].loft(&mut core)?.insert(&mut core);
+        core.layers.validation.take_errors()?;
+
+        // The side faces aren't planar, so each is split into two triangles.
+        assert_eq!(num_faces(&solid), 2 + 4 * 2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn loft_multiple_profiles() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let profiles = [
+            square(2., 0., 0., &mut core),
+            square(1., 1., 0., &mut core),
+            square(2., 2., 0., &mut core),
+        ];
+
+        let solid = profiles.loft(&mut core)?.insert(&mut core);
+        core.layers.validation.take_errors()?;
+
+        assert_eq!(num_faces(&solid), 2 + 4 * 2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn loft_regions() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let profiles =
+            [square(2., 0., 0., &mut core), square(1., 1., 0., &mut core)]
+                .map(|face| (face.region().clone(), face.surface().clone()));
+
+        let solid = profiles.loft(&mut core)?.insert(&mut core);
+        core.layers.validation.take_errors()?;
+
+        assert_eq!(num_faces(&solid), 2 + 4);
+
+        Ok(())
+    }
+
+    #[test]
+    fn loft_incompatible_profiles() {
+        let mut core = Core::new();
+
+        let bottom = square(2., 0., 0., &mut core);
+        let top = Face::polygon(
+            core.layers.topology.surfaces.xy_plane(),
+            [[0., 0.], [1., 0.], [0., 1.]],
+            &mut core,
+        )
+        .translate([0., 0., 1.], &mut core)
+        .insert(&mut core);
+
+        assert_eq!(
+            [bottom.clone()].loft(&mut core).unwrap_err(),
+            LoftError::TooFewProfiles
+        );
+        assert_eq!(
+            [bottom, top].loft(&mut core).unwrap_err(),
+            LoftError::HalfEdgeCountMismatch {
+                profile: 1,
+                cycle: 0,
+                expected: 4,
+                actual: 3,
+            }
+        );
+    }
+
+    #[test]
+    fn loft_arcs() {
+        let mut core = Core::new();
+
+        let bottom = square(2., 0., 0., &mut core);
+        let top = Face::circle(
+            core.layers.topology.surfaces.xy_plane(),
+            [0., 0.],
+            1.,
+            &mut core,
+        )
+        .translate([0., 0., 1.], &mut core)
+        .insert(&mut core);
+
+        assert_eq!(
+            [bottom, top].loft(&mut core).unwrap_err(),
+            LoftError::UnsupportedHalfEdge { profile: 1 }
+        );
+    }
+}
@@ -38,16 +38,20 @@
 //! assume that the code in question is outdated. Feel free to open an issue or
 //! send a pull request!
 
+pub mod blend;
+pub mod boolean;
 pub mod build;
 pub mod derive;
 pub mod geometry;
 pub mod holes;
 pub mod insert;
 pub mod join;
+pub mod loft;
 pub mod merge;
 pub mod presentation;
 pub mod replace;
 pub mod reverse;
+pub mod revolve;
 pub mod split;
 pub mod sweep;
 pub mod transform;
@@ -15,7 +15,8 @@ impl Reverse for Cycle {
             .half_edges()
             .pairs()
             .map(|(current, next)| {
-                let mut geometry = *core.layers.geometry.of_half_edge(current);
+                let mut geometry =
+                    core.layers.geometry.of_half_edge(current).clone();
                 geometry.boundary = geometry.boundary.reverse();
 
                 HalfEdge::new(
@@ -16,7 +16,8 @@ impl ReverseCurveCoordinateSystems for (&Handle<HalfEdge>, &Handle<Surface>) {
     ) -> Self::Reversed {
         let (half_edge, surface) = self;
 
-        let mut half_edge_geom = *core.layers.geometry.of_half_edge(half_edge);
+        let mut half_edge_geom =
+            core.layers.geometry.of_half_edge(half_edge).clone();
         half_edge_geom.path = half_edge_geom.path.reverse();
         half_edge_geom.boundary = half_edge_geom.boundary.reverse();
 
@@ -0,0 +1,459 @@
+use fj_interop::Color;
+use fj_math::{Circle, Line, Point, Scalar, Vector};
+
+use crate::{
+    geometry::{
+        CurveBoundary, GlobalPath, HalfEdgeGeom, RevolvedCurve, SurfacePath,
+    },
+    operations::{
+        build::BuildSurface,
+        geometry::{UpdateCurveGeometry, UpdateHalfEdgeGeometry},
+        insert::Insert,
+        presentation::SetColor,
+        sweep::SweepCache,
+    },
+    storage::Handle,
+    topology::{Curve, Cycle, Face, HalfEdge, Region, Surface, Vertex},
+    Core,
+};
+
+use super::{arc_curve, end_curve, end_vertex, Revolution, RevolveError};
+
+/// # Revolve a [`HalfEdge`]
+///
+/// See [module documentation] for more information.
+///
+/// [module documentation]: super
+pub trait RevolveHalfEdge {
+    /// # Revolve the [`HalfEdge`]
+    ///
+    /// Returns the face that is the result of revolving the half-edge, or
+    /// `None`, if the half-edge lies on the axis and doesn't create a face.
+    /// Returns an error, if revolving the half-edge is not supported. See
+    /// [`RevolveError`].
+    ///
+    /// The half-edge must be defined in a surface that contains the axis. If
+    /// its cycle is wound counter-clockwise, when viewed from the direction
+    /// that the revolution moves it towards, the resulting face points
+    /// outwards.
+    ///
+    /// In addition to the usual arguments that many revolve operations
+    /// require, some other ones are needed:
+    ///
+    /// - `end_vertex`, the vertex where the half-edge ends. This is the start
+    ///   vertex of the next half-edge in the cycle.
+    /// - The `surface` that the half-edge is defined on.
+    /// - The `color` of the resulting face, if applicable
+    #[allow(clippy::too_many_arguments)]
+    fn revolve_half_edge(
+        &self,
+        end_vertex: Handle<Vertex>,
+        surface: Handle<Surface>,
+        color: Option<Color>,
+        axis: Line<3>,
+        angle: impl Into<Scalar>,
+        cache: &mut SweepCache,
+        core: &mut Core,
+    ) -> Result<Option<Face>, RevolveError>;
+}
+
+impl RevolveHalfEdge for Handle<HalfEdge> {
+    fn revolve_half_edge(
+        &self,
+        end_vertex: Handle<Vertex>,
+        surface: Handle<Surface>,
+        color: Option<Color>,
+        axis: Line<3>,
+        angle: impl Into<Scalar>,
+        cache: &mut SweepCache,
+        core: &mut Core,
+    ) -> Result<Option<Face>, RevolveError> {
+        let revolution = Revolution::new(axis, angle.into());
+        let tolerance = core.layers.validation.config.identical_max_distance;
+
+        let half_edge_geom = core.layers.geometry.of_half_edge(self).clone();
+        let surface_geom = core.layers.geometry.of_surface(&surface).clone();
+
+        // Let's start with the global positions of the half-edge's vertices,
+        // and their distances from the axis.
+        let [position_a, position_b] =
+            half_edge_geom.boundary.inner.map(|point| {
+                surface_geom.point_from_surface_coords(
+                    half_edge_geom.path.point_from_path_coords(point),
+                )
+            });
+        let [radius_a, radius_b] =
+            [position_a, position_b].map(|point| revolution.radius_of(point));
+
+        let is_line = matches!(half_edge_geom.path, SurfacePath::Line(_));
+
+        if is_line && radius_a < tolerance && radius_b < tolerance {
+            // The half-edge lies on the axis. Revolving it doesn't result in a
+            // face.
+            return Ok(None);
+        }
+
+        // Next, the global vertices and curves. The vertices are named in the
+        // order they appear in the cycle of the new face: `a` and `b` are the
+        // original ones, `c` and `d` their counterparts at the end of the
+        // revolution.
+        let [a, b] = [self.start_vertex().clone(), end_vertex];
+        let c = self::end_vertex(
+            &b,
+            position_b,
+            &revolution,
+            tolerance,
+            cache,
+            core,
+        );
+        let d = self::end_vertex(
+            &a,
+            position_a,
+            &revolution,
+            tolerance,
+            cache,
+            core,
+        );
+
+        let curve_bottom = self.curve().clone();
+        let curve_top = end_curve(self, false, &revolution, cache, core);
+        let curve_a = arc_curve(&a, cache, core);
+        let curve_b = arc_curve(&b, cache, core);
+
+        let [t_a, t_b] = half_edge_geom.boundary.inner.map(|point| point.t);
+        let angle = revolution.angle;
+        let zero = Scalar::ZERO;
+
+        let offset_along_axis =
+            (position_b - position_a).dot(&revolution.direction);
+
+        let (surface, cycles) = if is_line
+            && (radius_a - radius_b).abs() < tolerance
+        {
+            // The half-edge is parallel to the axis, and revolving it results
+            // in a cylindrical face. The u-axis of the surface follows the
+            // revolution, while the v-axis follows the half-edge.
+            let center = revolution.center_of(position_a);
+            let radial = position_a - center;
+            let tangential = revolution.direction.cross(&radial);
+
+            let surface = Surface::from_uv(
+                GlobalPath::Circle(Circle::new(center, radial, tangential)),
+                position_b - position_a,
+                core,
+            );
+
+            let one = Scalar::ONE;
+            let line = |start: (Scalar, [Scalar; 2]),
+                        end: (Scalar, [Scalar; 2])| {
+                let path = SurfacePath::line_from_points_with_coords(
+                    [start, end].map(|(t, point)| ([t], point)),
+                );
+                (path, [start.0, end.0])
+            };
+
+            // If this is a full revolution, the top and bottom half-edges are
+            // the same, and form the seam of the cylinder.
+            let exterior = vec![
+                (
+                    curve_bottom,
+                    a,
+                    line((t_a, [zero, zero]), (t_b, [zero, one])),
+                ),
+                (curve_b, b, line((zero, [zero, one]), (angle, [angle, one]))),
+                (
+                    curve_top,
+                    c,
+                    line((t_b, [angle, one]), (t_a, [angle, zero])),
+                ),
+                (
+                    curve_a,
+                    d,
+                    line((angle, [angle, zero]), (zero, [zero, zero])),
+                ),
+            ];
+
+            (surface, vec![exterior])
+        } else if is_line && offset_along_axis.abs() < tolerance {
+            // The half-edge is perpendicular to the axis, and revolving it
+            // results in a planar face. The surface is defined such that its
+            // origin is on the axis, which means the vertices are revolved
+            // along circles around that origin.
+            let center = revolution.center_of(position_a);
+            let radial = if radius_a > radius_b {
+                position_a - center
+            } else {
+                position_b - center
+            }
+            .normalize();
+            let tangential = revolution.direction.cross(&radial);
+
+            let surface = Surface::from_uv(
+                GlobalPath::Line(Line::from_origin_and_direction(
+                    center, radial,
+                )),
+                tangential,
+                core,
+            );
+
+            let line = |start: (Scalar, Point<2>), end: (Scalar, Point<2>)| {
+                let path = SurfacePath::line_from_points_with_coords(
+                    [start, end].map(|(t, point)| ([t], point)),
+                );
+                (path, [start.0, end.0])
+            };
+            let arc = |radius: Scalar, boundary: [Scalar; 2]| {
+                let path = SurfacePath::circle_from_center_and_radius(
+                    Point::origin(),
+                    radius,
+                );
+                (path, boundary)
+            };
+            let rotated = |radius: Scalar| {
+                let (sin, cos) = angle.sin_cos();
+                Point::from([radius * cos, radius * sin])
+            };
+
+            let cycles = if revolution.is_full() {
+                // A full revolution results in an annulus (or a disc, if one
+                // of the vertices is on the axis). We don't need a seam here,
+                // as we do for cylinders, so the original half-edge doesn't
+                // end up being part of the face.
+                let mut circles = [
+                    (curve_a, a, radius_a, [Scalar::TAU, zero]),
+                    (curve_b, b, radius_b, [zero, Scalar::TAU]),
+                ];
+
+                // The outer circle is the exterior.
+                circles.sort_by_key(|(_, _, radius, _)| -*radius);
+
+                circles
+                    .into_iter()
+                    .filter(|(_, _, radius, _)| *radius >= tolerance)
+                    .map(|(curve, vertex, radius, boundary)| {
+                        vec![(curve, vertex, arc(radius, boundary))]
+                    })
+                    .collect()
+            } else {
+                // A vertex on the axis stays in place, so the half-edge that
+                // it would have been revolved along is left out.
+                let mut exterior = vec![(
+                    curve_bottom,
+                    a,
+                    line(
+                        (t_a, Point::from([radius_a, zero])),
+                        (t_b, Point::from([radius_b, zero])),
+                    ),
+                )];
+                if radius_b >= tolerance {
+                    exterior.push((curve_b, b, arc(radius_b, [zero, angle])));
+                }
+                exterior.push((
+                    curve_top,
+                    c,
+                    line((t_b, rotated(radius_b)), (t_a, rotated(radius_a))),
+                ));
+                if radius_a >= tolerance {
+                    exterior.push((curve_a, d, arc(radius_a, [angle, zero])));
+                }
+
+                vec![exterior]
+            };
+
+            (surface, cycles)
+        } else {
+            // Revolving any other half-edge results in a surface of
+            // revolution, like a cone or a torus. The u-axis of the surface
+            // follows the revolution, while the v-axis follows the half-edge,
+            // using the same coordinates.
+            let radial = if radius_a > radius_b {
+                position_a - revolution.center_of(position_a)
+            } else {
+                position_b - revolution.center_of(position_b)
+            }
+            .normalize();
+
+            // The profile of the surface is the path of the half-edge, in the
+            // half-plane that is bounded by the axis and extends towards the
+            // half-edge.
+            let to_profile = |vector: Vector<3>| {
+                Vector::from([
+                    vector.dot(&radial),
+                    vector.dot(&revolution.direction),
+                ])
+            };
+            let profile = match &half_edge_geom.path {
+                SurfacePath::Circle(circle) => {
+                    let center =
+                        surface_geom.point_from_surface_coords(circle.center());
+                    let [a, b] = [circle.a(), circle.b()].map(|vector| {
+                        to_profile(surface_geom.vector_from_surface_coords(
+                            circle.center(),
+                            vector,
+                        ))
+                    });
+
+                    SurfacePath::Circle(Circle::new(
+                        Point::origin()
+                            + to_profile(center - revolution.origin),
+                        a,
+                        b,
+                    ))
+                }
+                SurfacePath::Spline(_) => {
+                    return Err(RevolveError::UnsupportedHalfEdge {
+                        half_edge: self.clone(),
+                    });
+                }
+                SurfacePath::Line(_) => {
+                    SurfacePath::line_from_points_with_coords(
+                        [(t_a, position_a), (t_b, position_b)].map(
+                            |(t, position)| {
+                                (
+                                    [t],
+                                    Point::origin()
+                                        + to_profile(
+                                            position - revolution.origin,
+                                        ),
+                                )
+                            },
+                        ),
+                    )
+                }
+            };
+
+            if min_distance_to_axis(&profile, [t_a, t_b]) < tolerance {
+                return Err(RevolveError::HalfEdgeTouchesAxis {
+                    half_edge: self.clone(),
+                });
+            }
+
+            let surface = Surface::from_geometry(
+                RevolvedCurve {
+                    axis: Line::from_origin_and_direction(
+                        revolution.origin,
+                        revolution.direction,
+                    ),
+                    radial,
+                    profile,
+                },
+                core,
+            );
+
+            let line = |start: (Scalar, [Scalar; 2]),
+                        end: (Scalar, [Scalar; 2])| {
+                let path = SurfacePath::line_from_points_with_coords(
+                    [start, end].map(|(t, point)| ([t], point)),
+                );
+                (path, [start.0, end.0])
+            };
+
+            // Same as for cylinders, a full revolution results in a seam,
+            // formed by the top and bottom half-edges.
+            let exterior = vec![
+                (
+                    curve_bottom,
+                    a,
+                    line((t_a, [zero, t_a]), (t_b, [zero, t_b])),
+                ),
+                (curve_b, b, line((zero, [zero, t_b]), (angle, [angle, t_b]))),
+                (curve_top, c, line((t_b, [angle, t_b]), (t_a, [angle, t_a]))),
+                (curve_a, d, line((angle, [angle, t_a]), (zero, [zero, t_a]))),
+            ];
+
+            (surface, vec![exterior])
+        };
+
+        let mut cycles = cycles.into_iter().map(|half_edges| {
+            let half_edges = half_edges.into_iter().map(
+                |(curve, start_vertex, (path, boundary))| {
+                    half_edge(
+                        curve,
+                        start_vertex,
+                        path,
+                        boundary,
+                        &surface,
+                        core,
+                    )
+                },
+            );
+
+            Cycle::new(half_edges.collect::<Vec<_>>()).insert(core)
+        });
+
+        let exterior = cycles.next().expect("Face must have an exterior");
+        let interiors = cycles.collect::<Vec<_>>();
+
+        let region = Region::new(exterior, interiors).insert(core);
+
+        if let Some(color) = color {
+            region.set_color(color, core);
+        }
+
+        Ok(Some(Face::new(surface, region)))
+    }
+}
+
+/// The minimum signed distance of the profile from the axis, within the boundary
+fn min_distance_to_axis(
+    profile: &SurfacePath,
+    boundary: [Scalar; 2],
+) -> Scalar {
+    let [min, max] = {
+        let [a, b] = boundary;
+        if a < b {
+            [a, b]
+        } else {
+            [b, a]
+        }
+    };
+    let at_boundary = boundary
+        .map(|t| profile.point_from_path_coords([t]).u)
+        .into_iter()
+        .fold(Scalar::MAX, Scalar::min);
+
+    match profile {
+        SurfacePath::Circle(circle) => {
+            // The distance from the axis along the circle is a sinusoid. Check
+            // whether its minimum is within the boundary.
+            let [a, b] = [circle.a().u, circle.b().u];
+            let amplitude = Vector::from([a, b]).magnitude();
+            let t_min = (-b).atan2(-a);
+
+            let k = ((min - t_min) / Scalar::TAU).ceil();
+            if t_min + k * Scalar::TAU <= max {
+                circle.center().u - amplitude
+            } else {
+                at_boundary
+            }
+        }
+        SurfacePath::Line(_) | SurfacePath::Spline(_) => at_boundary,
+    }
+}
+
+fn half_edge(
+    curve: Handle<Curve>,
+    start_vertex: Handle<Vertex>,
+    path: SurfacePath,
+    boundary: [Scalar; 2],
+    surface: &Handle<Surface>,
+    core: &mut Core,
+) -> Handle<HalfEdge> {
+    let curve = curve.make_path_on_surface(
+        path.clone(),
+        surface.clone(),
+        &mut core.layers.geometry,
+    );
+
+    HalfEdge::new(curve, start_vertex)
+        .insert(core)
+        .set_geometry(
+            HalfEdgeGeom {
+                path,
+                boundary: CurveBoundary {
+                    inner: boundary.map(|t| Point::from([t])),
+                },
+            },
+            &mut core.layers.geometry,
+        )
+}
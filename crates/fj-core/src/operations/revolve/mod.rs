@@ -0,0 +1,197 @@
+//! Revolve objects around an axis to create new objects
+//!
+//! Revolves 2D objects around an axis, creating 3D objects. The revolution can
+//! be a full one (by an angle of 2π), or a partial one. In the latter case, the
+//! resulting solid is closed off by faces at the start and end of the
+//! revolution.
+//!
+//! All revolve operations take the axis as a [`Line`], and the angle in
+//! radians. Positive angles revolve counter-clockwise around the direction of
+//! the axis, negative angles revolve clockwise.
+//!
+//!
+//! ## Implementation Note
+//!
+//! Half-edges that are lines parallel to the axis result in cylindrical faces,
+//! lines perpendicular to it in planar faces. All other half-edges result in
+//! faces on a surface of revolution ([`RevolvedCurve`]), like cones or tori.
+//!
+//! Revolving a half-edge that touches the axis, without being perpendicular to
+//! it, would result in a face with a degenerate edge at the tip of a cone or
+//! the pole of a sphere. This is not supported yet, and results in a
+//! [`RevolveError`], as do half-edges that are splines.
+//!
+//! [`RevolvedCurve`]: crate::geometry::RevolvedCurve
+
+mod half_edge;
+mod region;
+mod sketch;
+
+pub use self::{
+    half_edge::RevolveHalfEdge,
+    region::{RevolveRegion, RevolvedRegion},
+    sketch::RevolveSketch,
+};
+
+use fj_math::{Line, Point, Scalar, Transform, Vector};
+
+use crate::{
+    geometry::Geometry,
+    operations::{insert::Insert, sweep::SweepCache},
+    storage::Handle,
+    topology::{Curve, HalfEdge, Surface, Vertex},
+    Core,
+};
+
+/// Error revolving an object
+#[derive(Debug, Eq, PartialEq, thiserror::Error)]
+pub enum RevolveError {
+    /// The sketch is defined on a surface that is not planar
+    #[error(
+        "Revolving sketches on surfaces that are not planar is not supported"
+    )]
+    UnsupportedSurface,
+
+    /// A half-edge is a spline
+    #[error("Revolving splines is not supported")]
+    UnsupportedHalfEdge {
+        /// The half-edge that is a spline
+        half_edge: Handle<HalfEdge>,
+    },
+
+    /// A half-edge touches the axis, without being perpendicular to it
+    ///
+    /// Revolving it would result in the tip of a cone, or the pole of a sphere.
+    #[error(
+        "Revolving half-edges that touch the axis, without being perpendicular \
+        to it, is not supported"
+    )]
+    HalfEdgeTouchesAxis {
+        /// The half-edge that touches the axis
+        half_edge: Handle<HalfEdge>,
+    },
+}
+
+/// The normalized parameters of a revolution
+///
+/// The direction of the axis is normalized, and its sign is chosen such that
+/// the angle is always positive.
+#[derive(Clone, Copy)]
+struct Revolution {
+    origin: Point<3>,
+    direction: Vector<3>,
+    angle: Scalar,
+}
+
+impl Revolution {
+    fn new(axis: Line<3>, angle: Scalar) -> Self {
+        assert!(
+            angle != Scalar::ZERO && angle.abs() <= Scalar::TAU,
+            "Revolve angle must be in the range [-2π, 2π], excluding zero"
+        );
+
+        let direction = axis.direction().normalize();
+        let (direction, angle) = if angle < Scalar::ZERO {
+            (-direction, -angle)
+        } else {
+            (direction, angle)
+        };
+
+        Self {
+            origin: axis.origin(),
+            direction,
+            angle,
+        }
+    }
+
+    fn is_full(&self) -> bool {
+        self.angle == Scalar::TAU
+    }
+
+    /// The point on the axis that is closest to the provided point
+    fn center_of(&self, point: Point<3>) -> Point<3> {
+        self.origin
+            + self.direction * (point - self.origin).dot(&self.direction)
+    }
+
+    /// The distance of the provided point from the axis
+    fn radius_of(&self, point: Point<3>) -> Scalar {
+        (point - self.center_of(point)).magnitude()
+    }
+
+    /// The transform that rotates objects by the angle of the revolution
+    fn transform(&self) -> Transform {
+        Transform::translation(self.origin.coords)
+            * Transform::rotation(self.direction * self.angle)
+            * Transform::translation(-self.origin.coords)
+    }
+}
+
+/// The position of the start vertex of a half-edge, in global coordinates
+fn start_position(
+    half_edge: &Handle<HalfEdge>,
+    surface: &Handle<Surface>,
+    geometry: &Geometry,
+) -> Point<3> {
+    geometry.of_surface(surface).point_from_surface_coords(
+        geometry.of_half_edge(half_edge).start_position(),
+    )
+}
+
+/// The vertex that a vertex ends up at, at the end of the revolution
+///
+/// Vertices on the axis don't move, and a full revolution moves every vertex
+/// back to where it started.
+fn end_vertex(
+    vertex: &Handle<Vertex>,
+    position: Point<3>,
+    revolution: &Revolution,
+    tolerance: Scalar,
+    cache: &mut SweepCache,
+    core: &mut Core,
+) -> Handle<Vertex> {
+    if revolution.is_full() || revolution.radius_of(position) < tolerance {
+        return vertex.clone();
+    }
+
+    cache
+        .vertices
+        .entry(vertex.id())
+        .or_insert_with(|| Vertex::new().insert(core))
+        .clone()
+}
+
+/// The curve that a half-edge ends up on, at the end of the revolution
+///
+/// Half-edges on the axis don't move, and a full revolution moves every
+/// half-edge back to where it started.
+fn end_curve(
+    half_edge: &Handle<HalfEdge>,
+    is_on_axis: bool,
+    revolution: &Revolution,
+    cache: &mut SweepCache,
+    core: &mut Core,
+) -> Handle<Curve> {
+    if revolution.is_full() || is_on_axis {
+        return half_edge.curve().clone();
+    }
+
+    cache
+        .curves
+        .entry(half_edge.curve().id())
+        .or_insert_with(|| Curve::new().insert(core))
+        .clone()
+}
+
+/// The curve that a vertex is revolved along
+fn arc_curve(
+    vertex: &Handle<Vertex>,
+    cache: &mut SweepCache,
+    core: &mut Core,
+) -> Handle<Curve> {
+    cache
+        .curves
+        .entry(vertex.id())
+        .or_insert_with(|| Curve::new().insert(core))
+        .clone()
+}
@@ -0,0 +1,195 @@
+use fj_interop::Color;
+use fj_math::{Line, Scalar};
+
+use crate::{
+    geometry::LocalCurveGeom,
+    operations::{
+        geometry::UpdateHalfEdgeGeometry, insert::Insert,
+        presentation::SetColor, reverse::Reverse, sweep::SweepCache,
+        transform::TransformObject,
+    },
+    storage::Handle,
+    topology::{Cycle, Face, HalfEdge, Region, Surface},
+    Core,
+};
+
+use super::{
+    end_curve, end_vertex, start_position, Revolution, RevolveError,
+    RevolveHalfEdge,
+};
+
+/// # Revolve a [`Region`]
+///
+/// See [module documentation] for more information.
+///
+/// [module documentation]: super
+pub trait RevolveRegion {
+    /// # Revolve the [`Region`]
+    ///
+    /// Revolve the region into a set of side faces, one for each half-edge
+    /// that doesn't lie on the axis. If the revolution is a partial one, an
+    /// end face is added, which is the region rotated by the angle of the
+    /// revolution.
+    ///
+    /// Requires the surface that the face that the region belongs to is defined
+    /// in. That surface must contain the axis, and the face must point against
+    /// the direction of the revolution. This makes it the start face of the
+    /// revolution.
+    ///
+    /// There is no start face in the result. Whether having one is desirable
+    /// depends on the context of the caller of this operation, and falls
+    /// outside of this operation's scope.
+    ///
+    /// Returns an error, if revolving any of the half-edges is not supported.
+    /// See [`RevolveError`].
+    #[allow(clippy::too_many_arguments)]
+    fn revolve_region(
+        &self,
+        surface: Handle<Surface>,
+        color: Option<Color>,
+        axis: Line<3>,
+        angle: impl Into<Scalar>,
+        cache: &mut SweepCache,
+        core: &mut Core,
+    ) -> Result<RevolvedRegion, RevolveError>;
+}
+
+impl RevolveRegion for Region {
+    fn revolve_region(
+        &self,
+        surface: Handle<Surface>,
+        color: Option<Color>,
+        axis: Line<3>,
+        angle: impl Into<Scalar>,
+        cache: &mut SweepCache,
+        core: &mut Core,
+    ) -> Result<RevolvedRegion, RevolveError> {
+        let angle = angle.into();
+        let revolution = Revolution::new(axis, angle);
+        let tolerance = core.layers.validation.config.identical_max_distance;
+
+        let end_surface = (!revolution.is_full())
+            .then(|| surface.transform(&revolution.transform(), core));
+
+        let mut side_faces = Vec::new();
+        let mut end_cycles = Vec::new();
+
+        for cycle in self.all_cycles() {
+            let cycle = cycle.reverse(core);
+            let mut end_half_edges = Vec::new();
+
+            for (half_edge, next) in cycle.half_edges().pairs() {
+                let side_face = half_edge.revolve_half_edge(
+                    next.start_vertex().clone(),
+                    surface.clone(),
+                    color,
+                    axis,
+                    angle,
+                    cache,
+                    core,
+                )?;
+                let is_on_axis = side_face.is_none();
+
+                side_faces.extend(side_face);
+
+                if let Some(end_surface) = &end_surface {
+                    end_half_edges.push(end_half_edge(
+                        half_edge,
+                        is_on_axis,
+                        &surface,
+                        end_surface,
+                        &revolution,
+                        tolerance,
+                        cache,
+                        core,
+                    ));
+                }
+            }
+
+            if end_surface.is_some() {
+                end_cycles.push(Cycle::new(end_half_edges).insert(core));
+            }
+        }
+
+        let end_face = end_surface.map(|end_surface| {
+            let mut end_cycles = end_cycles.into_iter();
+
+            let exterior =
+                end_cycles.next().expect("Region must have an exterior");
+            let region = Region::new(exterior, end_cycles).insert(core);
+
+            if let Some(color) = color {
+                region.set_color(color, core);
+            }
+
+            Face::new(end_surface, region)
+        });
+
+        Ok(RevolvedRegion {
+            side_faces,
+            end_face,
+        })
+    }
+}
+
+/// Create the half-edge of the end face that corresponds to the provided one
+#[allow(clippy::too_many_arguments)]
+fn end_half_edge(
+    half_edge: &Handle<HalfEdge>,
+    is_on_axis: bool,
+    surface: &Handle<Surface>,
+    end_surface: &Handle<Surface>,
+    revolution: &Revolution,
+    tolerance: Scalar,
+    cache: &mut SweepCache,
+    core: &mut Core,
+) -> Handle<HalfEdge> {
+    // The end surface is the original surface, rotated by the angle of the
+    // revolution. That means the geometry of the half-edge, which is defined
+    // in surface coordinates, is still valid there.
+    let geometry = core.layers.geometry.of_half_edge(half_edge).clone();
+
+    let position = start_position(half_edge, surface, &core.layers.geometry);
+    let start_vertex = end_vertex(
+        half_edge.start_vertex(),
+        position,
+        revolution,
+        tolerance,
+        cache,
+        core,
+    );
+    let curve = end_curve(half_edge, is_on_axis, revolution, cache, core);
+
+    core.layers.geometry.define_curve(
+        curve.clone(),
+        end_surface.clone(),
+        LocalCurveGeom {
+            path: geometry.path.clone(),
+        },
+    );
+
+    HalfEdge::new(curve, start_vertex)
+        .insert(core)
+        .set_geometry(geometry, &mut core.layers.geometry)
+}
+
+/// The result of revolving a [`Region`]
+///
+/// See [`RevolveRegion`].
+#[derive(Clone)]
+pub struct RevolvedRegion {
+    /// The side faces created by the revolution
+    pub side_faces: Vec<Face>,
+
+    /// The end face created by the revolution
+    ///
+    /// This is `None`, if the revolution is a full one.
+    pub end_face: Option<Face>,
+}
+
+impl RevolvedRegion {
+    /// Return an iterator over all of the faces
+    pub fn all_faces(self) -> impl Iterator<Item = Face> {
+        self.side_faces.into_iter().chain(self.end_face)
+    }
+}
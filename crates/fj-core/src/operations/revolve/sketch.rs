@@ -0,0 +1,383 @@
+use fj_math::{Line, Scalar};
+
+use crate::{
+    geometry::{GlobalPath, SurfaceGeom, SweptCurve},
+    operations::{
+        derive::DeriveFrom, insert::Insert, presentation::GetColor,
+        reverse::Reverse, sweep::SweepCache,
+    },
+    storage::Handle,
+    topology::{Face, Shell, Sketch, Solid, Surface},
+    Core,
+};
+
+use super::{start_position, Revolution, RevolveError, RevolveRegion};
+
+/// # Revolve a [`Sketch`]
+///
+/// See [module documentation] for more information.
+///
+/// [module documentation]: super
+pub trait RevolveSketch {
+    /// # Revolve the [`Sketch`]
+    ///
+    /// The axis must lie within the provided surface, and none of the
+    /// sketch's regions may cross it.
+    ///
+    /// Returns an error, if the surface is not planar, or if revolving any of
+    /// the sketch's half-edges is not supported. See [`RevolveError`].
+    fn revolve_sketch(
+        &self,
+        surface: Handle<Surface>,
+        axis: Line<3>,
+        angle: impl Into<Scalar>,
+        core: &mut Core,
+    ) -> Result<Solid, RevolveError>;
+}
+
+impl RevolveSketch for Sketch {
+    fn revolve_sketch(
+        &self,
+        surface: Handle<Surface>,
+        axis: Line<3>,
+        angle: impl Into<Scalar>,
+        core: &mut Core,
+    ) -> Result<Solid, RevolveError> {
+        let angle = angle.into();
+        let revolution = Revolution::new(axis, angle);
+        let mut cache = SweepCache::default();
+
+        let normal = {
+            let SurfaceGeom::Swept(SweptCurve {
+                u: GlobalPath::Line(line),
+                v,
+            }) = core.layers.geometry.of_surface(&surface)
+            else {
+                return Err(RevolveError::UnsupportedSurface);
+            };
+
+            let normal = line.direction().cross(v).normalize();
+            let distance_to_axis =
+                (revolution.origin - line.origin()).dot(&normal);
+
+            let tolerance =
+                core.layers.validation.config.identical_max_distance;
+            assert!(
+                normal.dot(&revolution.direction).abs() < tolerance
+                    && distance_to_axis.abs() < tolerance,
+                "Axis of revolution must lie within the surface of the sketch"
+            );
+
+            normal
+        };
+
+        let mut shells = Vec::new();
+        for region in self.regions() {
+            let region = {
+                // The following code assumes that the sketch is wound counter-
+                // clockwise. Let's check that real quick.
+                assert!(region
+                    .exterior()
+                    .winding(&core.layers.geometry)
+                    .is_ccw());
+
+                // The region needs to become the start face of the revolution,
+                // which means it has to point against the direction in which
+                // the revolution moves it. We can figure out that direction
+                // from the vertex that is furthest from the axis.
+                let is_against_revolution = {
+                    let position = region
+                        .exterior()
+                        .half_edges()
+                        .iter()
+                        .map(|half_edge| {
+                            start_position(
+                                half_edge,
+                                &surface,
+                                &core.layers.geometry,
+                            )
+                        })
+                        .max_by_key(|position| revolution.radius_of(*position))
+                        .expect("Region must have half-edges");
+                    let tangent = revolution
+                        .direction
+                        .cross(&(position - revolution.center_of(position)));
+
+                    normal.dot(&tangent) < Scalar::ZERO
+                };
+
+                if is_against_revolution {
+                    region.clone()
+                } else {
+                    region.reverse(core).insert(core).derive_from(region, core)
+                }
+            };
+
+            for cycle in region.all_cycles() {
+                for half_edge in cycle.half_edges() {
+                    let curve_geom = core
+                        .layers
+                        .geometry
+                        .of_curve(half_edge.curve())
+                        .unwrap()
+                        .local_on(self.surface())
+                        .unwrap();
+
+                    core.layers.geometry.define_curve(
+                        half_edge.curve().clone(),
+                        surface.clone(),
+                        curve_geom.clone(),
+                    );
+                }
+            }
+
+            let mut faces = Vec::new();
+            if !revolution.is_full() {
+                faces.push(
+                    Face::new(surface.clone(), region.clone()).insert(core),
+                );
+            }
+            faces.extend(
+                region
+                    .revolve_region(
+                        surface.clone(),
+                        region.get_color(core),
+                        axis,
+                        angle,
+                        &mut cache,
+                        core,
+                    )?
+                    .all_faces()
+                    .map(|face| face.insert(core)),
+            );
+
+            shells.push(Shell::new(faces).insert(core));
+        }
+
+        Ok(Solid::new(shells))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_math::{Line, Point, Scalar, Vector};
+
+    use crate::{
+        operations::{
+            build::{BuildRegion, BuildSketch},
+            insert::Insert,
+            update::UpdateSketch,
+        },
+        topology::{Region, Sketch, Solid},
+        Core,
+    };
+
+    use super::{RevolveError, RevolveSketch};
+
+    fn revolve_rectangle(
+        min: impl Into<Point<2>>,
+        max: impl Into<Point<2>>,
+        angle: impl Into<Scalar>,
+        core: &mut Core,
+    ) -> Solid {
+        let [min, max] = [min.into(), max.into()];
+
+        let surface = core.layers.topology.surfaces.xy_plane();
+        let axis =
+            Line::from_origin_and_direction(Point::origin(), Vector::unit_y());
+
+        Sketch::empty(&core.layers.topology)
+            .add_regions(
+                [Region::polygon(
+                    [
+                        [min.u, min.v],
+                        [max.u, min.v],
+                        [max.u, max.v],
+                        [min.u, max.v],
+                    ],
+                    core.layers.topology.surfaces.space_2d(),
+                    core,
+                )],
+                core,
+            )
+            .revolve_sketch(surface, axis, angle, core)
+            .unwrap()
+    }
+
+    fn num_faces(solid: &Solid) -> usize {
+        solid.shells().iter().map(|shell| shell.faces().len()).sum()
+    }
+
+    #[test]
+    fn full_revolution() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let solid =
+            revolve_rectangle([1., 0.], [2., 1.], Scalar::TAU, &mut core)
+                .insert(&mut core);
+        core.layers.validation.take_errors()?;
+
+        // Inner and outer cylinder, top and bottom annulus.
+        assert_eq!(num_faces(&solid), 4);
+
+        Ok(())
+    }
+
+    #[test]
+    fn partial_revolution() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let solid =
+            revolve_rectangle([1., 0.], [2., 1.], Scalar::PI / 2., &mut core)
+                .insert(&mut core);
+        core.layers.validation.take_errors()?;
+
+        // The faces of a full revolution, plus start and end face.
+        assert_eq!(num_faces(&solid), 6);
+
+        Ok(())
+    }
+
+    #[test]
+    fn negative_partial_revolution() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let solid =
+            revolve_rectangle([1., 0.], [2., 1.], -Scalar::PI / 2., &mut core)
+                .insert(&mut core);
+        core.layers.validation.take_errors()?;
+
+        assert_eq!(num_faces(&solid), 6);
+
+        Ok(())
+    }
+
+    #[test]
+    fn full_revolution_touching_axis() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let solid =
+            revolve_rectangle([0., 0.], [1., 1.], Scalar::TAU, &mut core)
+                .insert(&mut core);
+        core.layers.validation.take_errors()?;
+
+        // A cylinder, with top and bottom disc.
+        assert_eq!(num_faces(&solid), 3);
+
+        Ok(())
+    }
+
+    #[test]
+    fn partial_revolution_touching_axis() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let solid =
+            revolve_rectangle([0., 0.], [1., 1.], Scalar::PI, &mut core)
+                .insert(&mut core);
+        core.layers.validation.take_errors()?;
+
+        // Half a cylinder, with top and bottom sector, and start and end face.
+        assert_eq!(num_faces(&solid), 5);
+
+        Ok(())
+    }
+
+    fn revolve_circle(angle: impl Into<Scalar>, core: &mut Core) -> Solid {
+        let surface = core.layers.topology.surfaces.xy_plane();
+        let axis =
+            Line::from_origin_and_direction(Point::origin(), Vector::unit_y());
+
+        Sketch::empty(&core.layers.topology)
+            .add_regions(
+                [Region::circle(
+                    [2., 0.],
+                    0.5,
+                    core.layers.topology.surfaces.space_2d(),
+                    core,
+                )],
+                core,
+            )
+            .revolve_sketch(surface, axis, angle, core)
+            .unwrap()
+    }
+
+    #[test]
+    fn full_revolution_of_circle() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let solid = revolve_circle(Scalar::TAU, &mut core).insert(&mut core);
+        core.layers.validation.take_errors()?;
+
+        // A torus.
+        assert_eq!(num_faces(&solid), 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn partial_revolution_of_circle() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let solid = revolve_circle(Scalar::PI, &mut core).insert(&mut core);
+        core.layers.validation.take_errors()?;
+
+        // Half a torus, with start and end disc.
+        assert_eq!(num_faces(&solid), 3);
+
+        Ok(())
+    }
+
+    #[test]
+    fn full_revolution_of_slanted_edge() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let surface = core.layers.topology.surfaces.xy_plane();
+        let axis =
+            Line::from_origin_and_direction(Point::origin(), Vector::unit_y());
+
+        let solid = Sketch::empty(&core.layers.topology)
+            .add_regions(
+                [Region::polygon(
+                    [[1., 0.], [2., 0.], [1.5, 1.], [1., 1.]],
+                    core.layers.topology.surfaces.space_2d(),
+                    &mut core,
+                )],
+                &mut core,
+            )
+            .revolve_sketch(surface, axis, Scalar::TAU, &mut core)?
+            .insert(&mut core);
+        core.layers.validation.take_errors()?;
+
+        // Inner cylinder, outer cone, top and bottom annulus.
+        assert_eq!(num_faces(&solid), 4);
+
+        Ok(())
+    }
+
+    #[test]
+    fn revolution_of_edge_touching_axis() {
+        let mut core = Core::new();
+
+        let surface = core.layers.topology.surfaces.xy_plane();
+        let axis =
+            Line::from_origin_and_direction(Point::origin(), Vector::unit_y());
+
+        // Revolving this triangle would result in a cone, whose tip is on the
+        // axis.
+        let result = Sketch::empty(&core.layers.topology)
+            .add_regions(
+                [Region::polygon(
+                    [[0., 0.], [1., 0.], [0., 1.]],
+                    core.layers.topology.surfaces.space_2d(),
+                    &mut core,
+                )],
+                &mut core,
+            )
+            .revolve_sketch(surface, axis, Scalar::TAU, &mut core);
+
+        assert!(matches!(
+            result,
+            Err(RevolveError::HalfEdgeTouchesAxis { .. })
+        ));
+    }
+}
@@ -42,7 +42,7 @@ impl SplitHalfEdge for Handle<HalfEdge> {
     ) -> [Handle<HalfEdge>; 2] {
         let point = point.into();
 
-        let geometry = *core.layers.geometry.of_half_edge(self);
+        let geometry = core.layers.geometry.of_half_edge(self).clone();
         let [start, end] = geometry.boundary.inner;
 
         let a =
@@ -50,7 +50,7 @@ impl SplitHalfEdge for Handle<HalfEdge> {
                 .insert(core)
                 .derive_from(self, core)
                 .set_geometry(
-                    geometry.with_boundary([start, point]),
+                    geometry.clone().with_boundary([start, point]),
                     &mut core.layers.geometry,
                 );
         let b = HalfEdge::new(self.curve().clone(), Vertex::new().insert(core))
@@ -0,0 +1,469 @@
+use fj_interop::Color;
+use fj_math::{Line, Point, Scalar, Transform, Vector};
+
+use crate::{
+    geometry::{GlobalPath, SurfaceGeom, SweptCurve},
+    operations::{
+        derive::DeriveFrom, insert::Insert, presentation::GetColor,
+        reverse::Reverse, revolve::RevolveRegion,
+    },
+    storage::Handle,
+    topology::{Face, Region, Shell, Sketch, Solid, Surface},
+    Core,
+};
+
+use super::{SweepCache, SweepRegion, SweptRegion};
+
+/// # A path of lines and arcs, that objects can be swept along
+///
+/// The path must be tangent-continuous, meaning each segment must start in the
+/// direction that the previous one ended in. This is checked when sweeping
+/// along the path.
+///
+/// Sweeping along a line works the same as the straight sweep operations in
+/// this module. Sweeping along an arc is a partial revolution around the axis
+/// of the arc, and has the same limitations as the operations in the
+/// [`revolve`] module.
+///
+/// [`revolve`]: crate::operations::revolve
+#[derive(Clone, Debug)]
+pub struct SweepPath {
+    start: Point<3>,
+    segments: Vec<SweepPathSegment>,
+}
+
+impl SweepPath {
+    /// Create an empty path that starts at the provided point
+    pub fn from_start(start: impl Into<Point<3>>) -> Self {
+        Self {
+            start: start.into(),
+            segments: Vec::new(),
+        }
+    }
+
+    /// Add a line from the end of the path to the provided point
+    #[must_use]
+    pub fn line_to(mut self, end: impl Into<Point<3>>) -> Self {
+        self.segments
+            .push(SweepPathSegment::Line { end: end.into() });
+        self
+    }
+
+    /// Add an arc around the provided axis to the end of the path
+    ///
+    /// The arc is defined by the center and axis of its circle, as well as its
+    /// angle in radians. Positive angles go counter-clockwise around the
+    /// direction of the axis, negative angles go clockwise.
+    #[must_use]
+    pub fn arc(
+        mut self,
+        center: impl Into<Point<3>>,
+        axis: impl Into<Vector<3>>,
+        angle: impl Into<Scalar>,
+    ) -> Self {
+        self.segments.push(SweepPathSegment::Arc {
+            center: center.into(),
+            axis: axis.into(),
+            angle: angle.into(),
+        });
+        self
+    }
+
+    /// Access the start point of the path
+    pub fn start(&self) -> Point<3> {
+        self.start
+    }
+
+    /// Access the segments of the path
+    pub fn segments(&self) -> &[SweepPathSegment] {
+        &self.segments
+    }
+
+    /// Compute the direction in which the path starts
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the path has no segments.
+    pub fn start_direction(&self) -> Vector<3> {
+        let segment = self
+            .segments
+            .first()
+            .expect("Sweep path must have at least one segment");
+
+        segment.start_direction(self.start)
+    }
+
+    /// Compute the sweep steps that make up the path
+    ///
+    /// # Panics
+    ///
+    /// Panics, if the path has no segments, or if it isn't tangent-continuous.
+    fn steps(&self) -> Vec<SweepStep> {
+        let mut position = self.start;
+        let mut direction = self.start_direction();
+
+        self.segments
+            .iter()
+            .map(|segment| {
+                let start_direction = segment.start_direction(position);
+                assert!(
+                    (start_direction - direction).magnitude()
+                        < Scalar::from(1e-9),
+                    "Sweep path must be tangent-continuous"
+                );
+
+                match *segment {
+                    SweepPathSegment::Line { end } => {
+                        let path = end - position;
+                        position = end;
+
+                        SweepStep::Line(path)
+                    }
+                    SweepPathSegment::Arc {
+                        center,
+                        axis,
+                        angle,
+                    } => {
+                        let rotation = Transform::translation(center.coords)
+                            * Transform::rotation(axis.normalize() * angle)
+                            * Transform::translation(-center.coords);
+
+                        position = rotation.transform_point(&position);
+                        direction = rotation.transform_vector(&direction);
+
+                        SweepStep::Arc {
+                            axis: Line::from_origin_and_direction(center, axis),
+                            angle,
+                        }
+                    }
+                }
+            })
+            .collect()
+    }
+}
+
+/// A segment of a [`SweepPath`]
+#[derive(Clone, Copy, Debug)]
+pub enum SweepPathSegment {
+    /// A line from the end of the previous segment to the provided point
+    Line {
+        /// The end point of the line
+        end: Point<3>,
+    },
+
+    /// An arc, starting at the end of the previous segment
+    Arc {
+        /// The center of the arc's circle
+        center: Point<3>,
+
+        /// The axis of the arc's circle
+        axis: Vector<3>,
+
+        /// The angle of the arc, in radians
+        angle: Scalar,
+    },
+}
+
+impl SweepPathSegment {
+    /// Compute the direction of the segment at its start
+    ///
+    /// Requires the point where the segment starts.
+    pub fn start_direction(&self, start: Point<3>) -> Vector<3> {
+        match *self {
+            Self::Line { end } => (end - start).normalize(),
+            Self::Arc {
+                center,
+                axis,
+                angle,
+            } => {
+                let direction = axis.cross(&(start - center)).normalize();
+
+                if angle < Scalar::ZERO {
+                    -direction
+                } else {
+                    direction
+                }
+            }
+        }
+    }
+}
+
+enum SweepStep {
+    Line(Vector<3>),
+    Arc { axis: Line<3>, angle: Scalar },
+}
+
+/// # Sweep a [`Region`] along a [`SweepPath`]
+///
+/// See [module documentation] for more information.
+///
+/// [module documentation]: super
+pub trait SweepRegionAlongPath {
+    /// # Sweep the [`Region`] along the [`SweepPath`]
+    ///
+    /// Sweeps the region along each segment of the path, one after the other.
+    /// The top face of one segment is the starting point of the next one, and
+    /// is not part of the result.
+    ///
+    /// Requires the surface that the face that the region belongs to is defined
+    /// in. That face must point against the start direction of the path, and
+    /// the start point of the path must be located within the surface.
+    ///
+    /// Arcs of the path are swept along using [`RevolveRegion`]. The region
+    /// must be one that can be revolved around their axes, or this method
+    /// panics.
+    ///
+    /// There no "bottom" face. Whether having one is desirable depends on the
+    /// context of the caller of this operation, and falls outside of this
+    /// operation's scope.
+    fn sweep_region_along_path(
+        &self,
+        surface: Handle<Surface>,
+        color: Option<Color>,
+        path: &SweepPath,
+        core: &mut Core,
+    ) -> SweptRegion;
+}
+
+impl SweepRegionAlongPath for Region {
+    fn sweep_region_along_path(
+        &self,
+        surface: Handle<Surface>,
+        color: Option<Color>,
+        path: &SweepPath,
+        core: &mut Core,
+    ) -> SweptRegion {
+        let mut side_faces = Vec::new();
+        let mut top_face: Option<Face> = None;
+
+        for step in path.steps() {
+            // The top face of the previous segment points in the direction of
+            // the path, but the next segment expects a face that points
+            // against it.
+            let (region, surface) = match top_face.take() {
+                Some(face) => {
+                    (face.region().reverse(core), face.surface().clone())
+                }
+                None => (self.clone(), surface.clone()),
+            };
+
+            let mut cache = SweepCache::default();
+
+            let swept_region = match step {
+                SweepStep::Line(path) => {
+                    region.sweep_region(surface, color, path, &mut cache, core)
+                }
+                SweepStep::Arc { axis, angle } => {
+                    let revolved_region = region
+                        .revolve_region(
+                            surface, color, axis, angle, &mut cache, core,
+                        )
+                        .expect("Region must be revolvable along arcs of path");
+
+                    SweptRegion {
+                        side_faces: revolved_region.side_faces,
+                        top_face: revolved_region.end_face.expect(
+                            "Arcs of sweep paths must not be full circles",
+                        ),
+                    }
+                }
+            };
+
+            side_faces.extend(swept_region.side_faces);
+            top_face = Some(swept_region.top_face);
+        }
+
+        SweptRegion {
+            side_faces,
+            top_face: top_face
+                .expect("Sweep path must have at least one segment"),
+        }
+    }
+}
+
+/// # Sweep a [`Sketch`] along a [`SweepPath`]
+///
+/// See [module documentation] for more information.
+///
+/// [module documentation]: super
+pub trait SweepSketchAlongPath {
+    /// # Sweep the [`Sketch`] along the [`SweepPath`]
+    ///
+    /// The start point of the path must be located within the provided
+    /// surface, and the path must start perpendicular to it.
+    fn sweep_sketch_along_path(
+        &self,
+        surface: Handle<Surface>,
+        path: &SweepPath,
+        core: &mut Core,
+    ) -> Solid;
+}
+
+impl SweepSketchAlongPath for Sketch {
+    fn sweep_sketch_along_path(
+        &self,
+        surface: Handle<Surface>,
+        path: &SweepPath,
+        core: &mut Core,
+    ) -> Solid {
+        let start_direction = path.start_direction();
+
+        let normal = {
+            let SurfaceGeom::Swept(SweptCurve {
+                u: GlobalPath::Line(line),
+                v,
+            }) = core.layers.geometry.of_surface(&surface)
+            else {
+                todo!(
+                    "Sweeping sketch from a rounded surfaces is not supported"
+                )
+            };
+
+            line.direction().cross(v).normalize()
+        };
+
+        assert!(
+            normal.cross(&start_direction).magnitude() < Scalar::from(1e-9),
+            "Sweep path must start perpendicular to the surface of the sketch"
+        );
+
+        let mut shells = Vec::new();
+        for region in self.regions() {
+            let region = {
+                // The following code assumes that the sketch is wound counter-
+                // clockwise. Let's check that real quick.
+                assert!(region
+                    .exterior()
+                    .winding(&core.layers.geometry)
+                    .is_ccw());
+
+                let is_negative_sweep =
+                    normal.dot(&start_direction) < Scalar::ZERO;
+
+                if is_negative_sweep {
+                    region.clone()
+                } else {
+                    region.reverse(core).insert(core).derive_from(region, core)
+                }
+            };
+
+            for cycle in region.all_cycles() {
+                for half_edge in cycle.half_edges() {
+                    let curve_geom = core
+                        .layers
+                        .geometry
+                        .of_curve(half_edge.curve())
+                        .unwrap()
+                        .local_on(self.surface())
+                        .unwrap();
+
+                    core.layers.geometry.define_curve(
+                        half_edge.curve().clone(),
+                        surface.clone(),
+                        curve_geom.clone(),
+                    );
+                }
+            }
+
+            let bottom_face =
+                Face::new(surface.clone(), region.clone()).insert(core);
+            let other_faces = region
+                .sweep_region_along_path(
+                    surface.clone(),
+                    region.get_color(core),
+                    path,
+                    core,
+                )
+                .all_faces()
+                .map(|face| face.insert(core));
+
+            let mut faces = vec![bottom_face];
+            faces.extend(other_faces);
+
+            shells.push(Shell::new(faces).insert(core));
+        }
+
+        Solid::new(shells)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::FRAC_PI_2;
+
+    use fj_math::Point;
+
+    use crate::{
+        operations::{
+            build::{BuildRegion, BuildSketch},
+            insert::Insert,
+            update::UpdateSketch,
+        },
+        topology::{Region, Sketch, Solid},
+        Core,
+    };
+
+    use super::{SweepPath, SweepSketchAlongPath};
+
+    fn sweep_square(path: &SweepPath, core: &mut Core) -> Solid {
+        let surface = core.layers.topology.surfaces.xy_plane();
+
+        Sketch::empty(&core.layers.topology)
+            .add_regions(
+                [Region::polygon(
+                    [[-0.5, -0.5], [0.5, -0.5], [0.5, 0.5], [-0.5, 0.5]],
+                    core.layers.topology.surfaces.space_2d(),
+                    core,
+                )],
+                core,
+            )
+            .sweep_sketch_along_path(surface, path, core)
+    }
+
+    fn num_faces(solid: &Solid) -> usize {
+        solid.shells().iter().map(|shell| shell.faces().len()).sum()
+    }
+
+    #[test]
+    fn sweep_along_lines() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let path = SweepPath::from_start(Point::origin())
+            .line_to([0., 0., 1.])
+            .line_to([0., 0., 3.]);
+        let solid = sweep_square(&path, &mut core).insert(&mut core);
+        core.layers.validation.take_errors()?;
+
+        assert_eq!(num_faces(&solid), 2 + 4 * 2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn sweep_along_lines_and_arcs() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let path = SweepPath::from_start(Point::origin())
+            .line_to([0., 0., 2.])
+            .arc([2., 0., 2.], [0., 1., 0.], FRAC_PI_2)
+            .line_to([4., 0., 4.])
+            .arc([4., 0., 2.], [0., -1., 0.], -FRAC_PI_2);
+        let solid = sweep_square(&path, &mut core).insert(&mut core);
+        core.layers.validation.take_errors()?;
+
+        assert_eq!(num_faces(&solid), 2 + 4 * 4);
+
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic(expected = "tangent-continuous")]
+    fn sweep_along_path_with_corner() {
+        let mut core = Core::new();
+
+        let path = SweepPath::from_start(Point::origin())
+            .line_to([0., 0., 1.])
+            .line_to([1., 0., 1.]);
+        let _ = sweep_square(&path, &mut core);
+    }
+}
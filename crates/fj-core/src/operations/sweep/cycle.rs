@@ -79,7 +79,7 @@ impl SweepCycle for Cycle {
 
             top_edges.push((
                 top_half_edge,
-                *core.layers.geometry.of_half_edge(bottom_half_edge),
+                core.layers.geometry.of_half_edge(bottom_half_edge).clone(),
             ));
         }
 
@@ -59,8 +59,8 @@ impl SweepHalfEdge for Handle<HalfEdge> {
     ) -> (Face, Handle<HalfEdge>) {
         let path = path.into();
 
-        let half_edge_geom = *core.layers.geometry.of_half_edge(self);
-        let surface_geom = *core.layers.geometry.of_surface(&surface);
+        let half_edge_geom = core.layers.geometry.of_half_edge(self).clone();
+        let surface_geom = core.layers.geometry.of_surface(&surface).clone();
         let surface =
             half_edge_geom
                 .path
@@ -149,7 +149,8 @@ impl SweepHalfEdge for Handle<HalfEdge> {
                                     "Curve geometry was just defined in same \
                                     function",
                                 )
-                                .path,
+                                .path
+                                .clone(),
                             boundary,
                         },
                         &mut core.layers.geometry,
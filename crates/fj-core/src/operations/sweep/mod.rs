@@ -2,7 +2,11 @@
 //!
 //! Sweeps 1D or 2D objects along a straight path, creating a 2D or 3D object,
 //! respectively.
+//!
+//! 2D objects can also be swept along a [`SweepPath`], which is made up of
+//! lines and arcs.
 
+mod along_path;
 mod cycle;
 mod face;
 mod half_edge;
@@ -13,6 +17,9 @@ mod sketch;
 mod vertex;
 
 pub use self::{
+    along_path::{
+        SweepPath, SweepPathSegment, SweepRegionAlongPath, SweepSketchAlongPath,
+    },
     cycle::{SweepCycle, SweptCycle},
     face::SweepFace,
     half_edge::SweepHalfEdge,
@@ -1,7 +1,7 @@
 use fj_math::{Circle, Line, Vector};
 
 use crate::{
-    geometry::{GlobalPath, SurfaceGeom, SurfacePath},
+    geometry::{GlobalPath, SurfaceGeom, SurfacePath, SweptCurve},
     operations::build::BuildSurface,
     storage::Handle,
     topology::Surface,
@@ -39,8 +39,15 @@ impl SweepSurfacePath for SurfacePath {
         path: impl Into<Vector<3>>,
         core: &mut Core,
     ) -> Handle<Surface> {
-        match surface.u {
-            GlobalPath::Circle(_) => {
+        match surface {
+            SurfaceGeom::Swept(SweptCurve {
+                u: GlobalPath::Line(_),
+                ..
+            }) => {
+                // We're sweeping from a curve on a flat surface, which is
+                // supported. Carry on.
+            }
+            _ => {
                 // Sweeping a `Curve` creates a `Surface`. The u-axis of that
                 // `Surface` is a `GlobalPath`, which we are computing below.
                 // That computation might or might not work with an arbitrary
@@ -57,17 +64,15 @@ impl SweepSurfacePath for SurfacePath {
                     not supported yet."
                 )
             }
-            GlobalPath::Line(_) => {
-                // We're sweeping from a curve on a flat surface, which is
-                // supported. Carry on.
-            }
         }
 
         let u = match self {
             SurfacePath::Circle(circle) => {
                 let center = surface.point_from_surface_coords(circle.center());
-                let a = surface.vector_from_surface_coords(circle.a());
-                let b = surface.vector_from_surface_coords(circle.b());
+                let a = surface
+                    .vector_from_surface_coords(circle.center(), circle.a());
+                let b = surface
+                    .vector_from_surface_coords(circle.center(), circle.b());
 
                 let circle = Circle::new(center, a, b);
 
@@ -75,13 +80,25 @@ impl SweepSurfacePath for SurfacePath {
             }
             SurfacePath::Line(line) => {
                 let origin = surface.point_from_surface_coords(line.origin());
-                let direction =
-                    surface.vector_from_surface_coords(line.direction());
+                let direction = surface.vector_from_surface_coords(
+                    line.origin(),
+                    line.direction(),
+                );
 
                 let line = Line::from_origin_and_direction(origin, direction);
 
                 GlobalPath::Line(line)
             }
+            SurfacePath::Spline(spline) => {
+                // The surface is flat, so mapping the control points into
+                // global coordinates is an affine map, which maps the spline
+                // exactly.
+                let spline = spline.map_control_points(|point| {
+                    surface.point_from_surface_coords(point)
+                });
+
+                GlobalPath::Spline(spline)
+            }
         };
 
         Surface::from_uv(u, path, core)
@@ -1,7 +1,7 @@
 use fj_math::{Scalar, Vector};
 
 use crate::{
-    geometry::GlobalPath,
+    geometry::{GlobalPath, SurfaceGeom, SweptCurve},
     operations::{derive::DeriveFrom, insert::Insert, reverse::Reverse},
     storage::Handle,
     topology::{Face, Sketch, Solid, Surface},
@@ -46,16 +46,18 @@ impl SweepSketch for Sketch {
                     .is_ccw());
 
                 let is_negative_sweep = {
-                    let u = match core.layers.geometry.of_surface(&surface).u {
-                        GlobalPath::Circle(_) => todo!(
+                    let SurfaceGeom::Swept(SweptCurve {
+                        u: GlobalPath::Line(line),
+                        v,
+                    }) = core.layers.geometry.of_surface(&surface)
+                    else {
+                        todo!(
                             "Sweeping sketch from a rounded surfaces is not \
                             supported"
-                        ),
-                        GlobalPath::Line(line) => line.direction(),
+                        )
                     };
-                    let v = core.layers.geometry.of_surface(&surface).v;
 
-                    let normal = u.cross(&v);
+                    let normal = line.direction().cross(v);
 
                     normal.dot(&path) < Scalar::ZERO
                 };
@@ -93,3 +95,62 @@ impl SweepSketch for Sketch {
         Solid::new(shells)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use fj_math::Spline;
+
+    use crate::{
+        algorithms::{approx::Tolerance, triangulate::Triangulate},
+        operations::{
+            build::{BuildHalfEdge, BuildSketch},
+            insert::Insert,
+            update::UpdateSketch,
+        },
+        topology::{Cycle, HalfEdge, Region, Sketch},
+        Core,
+    };
+
+    use super::SweepSketch;
+
+    #[test]
+    fn sweep_sketch_with_spline() -> anyhow::Result<()> {
+        let mut core = Core::new();
+
+        let surface = core.layers.topology.surfaces.space_2d();
+        let exterior = Cycle::new([
+            HalfEdge::line_segment(
+                [[0., 0.], [2., 0.]],
+                None,
+                surface.clone(),
+                &mut core,
+            ),
+            HalfEdge::spline(
+                Spline::bezier([[2., 0.], [2., 2.], [0., 2.], [0., 0.]]),
+                surface,
+                &mut core,
+            ),
+        ])
+        .insert(&mut core);
+
+        let solid = Sketch::empty(&core.layers.topology)
+            .add_regions([Region::new(exterior, [])], &mut core)
+            .sweep_sketch(
+                core.layers.topology.surfaces.xy_plane(),
+                [0., 0., 1.],
+                &mut core,
+            )
+            .insert(&mut core);
+        core.layers.validation.take_errors()?;
+
+        let num_faces: usize =
+            solid.shells().iter().map(|shell| shell.faces().len()).sum();
+        assert_eq!(num_faces, 2 + 2);
+
+        let mesh =
+            (&*solid, Tolerance::from_scalar(0.01)?).triangulate(&mut core);
+        assert!(mesh.triangles().count() > 0);
+
+        Ok(())
+    }
+}
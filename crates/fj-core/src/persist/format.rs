@@ -0,0 +1,354 @@
+//! The data model of the native file format
+//!
+//! Objects refer to each other by their index within the list of objects of
+//! their type. Objects are only ever listed after all objects they refer to,
+//! so a file can be read in a single pass.
+
+use fj_math::{Circle, Line, Point, Scalar, Spline, Vector};
+use serde::{Deserialize, Serialize};
+
+use crate::geometry::{GlobalPath, SurfacePath};
+
+use super::PersistError;
+
+/// Identifies files in the native format
+pub const FORMAT: &str = "fornjot-brep";
+
+/// The version of the format that is written by this version of `fj-core`
+///
+/// Increment this, whenever the data model changes in a way that older versions
+/// can't read, or that this version can't read files written by older versions.
+pub const VERSION: u64 = 1;
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct File {
+    pub format: String,
+    pub version: u64,
+
+    pub surfaces: Vec<SurfaceData>,
+    pub curves: Vec<CurveData>,
+    pub vertices: usize,
+    pub half_edges: Vec<HalfEdgeData>,
+    pub cycles: Vec<CycleData>,
+    pub regions: Vec<RegionData>,
+    pub faces: Vec<FaceData>,
+    pub shells: Vec<ShellData>,
+
+    pub root: RootData,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SurfaceData {
+    /// The surface that represents 2D space, which has no geometry
+    Space2d,
+
+    /// The pre-defined basis planes, whose geometry is defined by `Geometry`
+    XyPlane,
+    XzPlane,
+    YzPlane,
+
+    Swept {
+        u: PathData,
+        v: Vec<f64>,
+    },
+    Revolved {
+        axis_origin: Vec<f64>,
+        axis_direction: Vec<f64>,
+        radial: Vec<f64>,
+        profile: PathData,
+    },
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CurveData {
+    pub definitions: Vec<CurveDefinitionData>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CurveDefinitionData {
+    pub surface: usize,
+    pub path: PathData,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct HalfEdgeData {
+    pub curve: usize,
+    pub start_vertex: usize,
+    pub path: PathData,
+    pub boundary: [f64; 2],
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CycleData {
+    pub half_edges: Vec<usize>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct RegionData {
+    pub exterior: usize,
+    pub interiors: Vec<usize>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<[u8; 4]>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct FaceData {
+    pub surface: usize,
+    pub region: usize,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ShellData {
+    pub faces: Vec<usize>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RootData {
+    Solid { shells: Vec<usize> },
+    Sketch { surface: usize, regions: Vec<usize> },
+}
+
+/// A path in either 2D or 3D space
+///
+/// Which one it is, is implied by where the path is used. Points and vectors
+/// are stored as lists of coordinates.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum PathData {
+    Circle {
+        center: Vec<f64>,
+        a: Vec<f64>,
+        b: Vec<f64>,
+    },
+    Line {
+        origin: Vec<f64>,
+        direction: Vec<f64>,
+    },
+    Spline {
+        degree: usize,
+        control_points: Vec<Vec<f64>>,
+        weights: Vec<f64>,
+        knots: Vec<f64>,
+    },
+}
+
+impl PathData {
+    pub fn from_circle<const D: usize>(circle: &Circle<D>) -> Self {
+        Self::Circle {
+            center: point_data(circle.center()),
+            a: vector_data(circle.a()),
+            b: vector_data(circle.b()),
+        }
+    }
+
+    pub fn from_line<const D: usize>(line: &Line<D>) -> Self {
+        Self::Line {
+            origin: point_data(line.origin()),
+            direction: vector_data(line.direction()),
+        }
+    }
+
+    pub fn from_spline<const D: usize>(spline: &Spline<D>) -> Self {
+        Self::Spline {
+            degree: spline.degree(),
+            control_points: spline
+                .control_points()
+                .iter()
+                .copied()
+                .map(point_data)
+                .collect(),
+            weights: spline.weights().iter().map(|w| w.into_f64()).collect(),
+            knots: spline.knots().iter().map(|k| k.into_f64()).collect(),
+        }
+    }
+
+    pub fn to_surface_path(&self) -> Result<SurfacePath, PersistError> {
+        let path = match self {
+            Self::Circle { center, a, b } => {
+                SurfacePath::Circle(circle(center, a, b)?)
+            }
+            Self::Line { origin, direction } => {
+                SurfacePath::Line(line(origin, direction)?)
+            }
+            Self::Spline {
+                degree,
+                control_points,
+                weights,
+                knots,
+            } => SurfacePath::Spline(spline(
+                *degree,
+                control_points,
+                weights,
+                knots,
+            )?),
+        };
+
+        Ok(path)
+    }
+
+    pub fn to_global_path(&self) -> Result<GlobalPath, PersistError> {
+        let path = match self {
+            Self::Circle { center, a, b } => {
+                GlobalPath::Circle(circle(center, a, b)?)
+            }
+            Self::Line { origin, direction } => {
+                GlobalPath::Line(line(origin, direction)?)
+            }
+            Self::Spline {
+                degree,
+                control_points,
+                weights,
+                knots,
+            } => GlobalPath::Spline(spline(
+                *degree,
+                control_points,
+                weights,
+                knots,
+            )?),
+        };
+
+        Ok(path)
+    }
+}
+
+impl From<&SurfacePath> for PathData {
+    fn from(path: &SurfacePath) -> Self {
+        match path {
+            SurfacePath::Circle(circle) => Self::from_circle(circle),
+            SurfacePath::Line(line) => Self::from_line(line),
+            SurfacePath::Spline(spline) => Self::from_spline(spline),
+        }
+    }
+}
+
+impl From<&GlobalPath> for PathData {
+    fn from(path: &GlobalPath) -> Self {
+        match path {
+            GlobalPath::Circle(circle) => Self::from_circle(circle),
+            GlobalPath::Line(line) => Self::from_line(line),
+            GlobalPath::Spline(spline) => Self::from_spline(spline),
+        }
+    }
+}
+
+pub fn point_data<const D: usize>(point: Point<D>) -> Vec<f64> {
+    vector_data(point.coords)
+}
+
+pub fn vector_data<const D: usize>(vector: Vector<D>) -> Vec<f64> {
+    vector.components.iter().map(|c| c.into_f64()).collect()
+}
+
+fn point<const D: usize>(coords: &[f64]) -> Result<Point<D>, PersistError> {
+    Ok(Point {
+        coords: vector(coords)?,
+    })
+}
+
+pub fn vector<const D: usize>(
+    components: &[f64],
+) -> Result<Vector<D>, PersistError> {
+    let components: [f64; D] = components.try_into().map_err(|_| {
+        PersistError::InvalidData(format!(
+            "Expected {D} coordinates, found {}",
+            components.len()
+        ))
+    })?;
+
+    Ok(Vector::from(components))
+}
+
+fn circle<const D: usize>(
+    center: &[f64],
+    a: &[f64],
+    b: &[f64],
+) -> Result<Circle<D>, PersistError> {
+    let a: Vector<D> = vector(a)?;
+    let b: Vector<D> = vector(b)?;
+
+    // These are the conditions that `Circle::new` asserts.
+    check(a.magnitude() != Scalar::ZERO, "Radius of circle is zero")?;
+    check(
+        (a.magnitude() - b.magnitude()).abs()
+            <= a.magnitude() * Scalar::from(f64::EPSILON) * 4.,
+        "Axes of circle are not of equal length",
+    )?;
+    check(
+        a.dot(&b) < Scalar::from(f64::EPSILON),
+        "Axes of circle are not perpendicular",
+    )?;
+
+    Ok(Circle::new(point(center)?, a, b))
+}
+
+pub fn line<const D: usize>(
+    origin: &[f64],
+    direction: &[f64],
+) -> Result<Line<D>, PersistError> {
+    let direction: Vector<D> = vector(direction)?;
+    if direction.magnitude() == Scalar::ZERO {
+        return Err(PersistError::InvalidData(String::from(
+            "Direction of line is zero",
+        )));
+    }
+
+    Ok(Line::from_origin_and_direction(point(origin)?, direction))
+}
+
+fn spline<const D: usize>(
+    degree: usize,
+    control_points: &[Vec<f64>],
+    weights: &[f64],
+    knots: &[f64],
+) -> Result<Spline<D>, PersistError> {
+    let control_points = control_points
+        .iter()
+        .map(|point| self::point::<D>(point))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // These are the conditions that `Spline::new` asserts.
+    check(degree >= 1, "Degree of spline is zero")?;
+    check(
+        control_points.len() > degree,
+        "Spline has too few control points for its degree",
+    )?;
+    check(
+        weights.len() == control_points.len(),
+        "Number of spline weights doesn't match number of control points",
+    )?;
+    check(
+        weights.iter().all(|&weight| weight > 0.),
+        "Spline weights are not positive",
+    )?;
+    check(
+        knots.len() == control_points.len() + degree + 1,
+        "Number of spline knots doesn't match number of control points and \
+        degree",
+    )?;
+    check(
+        knots.windows(2).all(|knots| knots[0] <= knots[1]),
+        "Spline knots are decreasing",
+    )?;
+    check(
+        knots[degree] < knots[control_points.len()],
+        "Domain of spline is empty",
+    )?;
+
+    Ok(Spline::new(
+        degree,
+        control_points,
+        weights.iter().copied(),
+        knots.iter().copied(),
+    ))
+}
+
+fn check(condition: bool, message: &str) -> Result<(), PersistError> {
+    if condition {
+        Ok(())
+    } else {
+        Err(PersistError::InvalidData(String::from(message)))
+    }
+}
@@ -0,0 +1,323 @@
+//! # Native file format for the object graph
+//!
+//! Writes objects, together with everything they reference, to a file, and
+//! reads them back into a [`Core`]. This can be used to cache models that are
+//! expensive to compute, or to exchange them between applications that use
+//! `fj-core`.
+//!
+//! See [`Persist`].
+//!
+//! ## Format
+//!
+//! Files are JSON documents that contain the topological objects, the geometry
+//! that the geometry layer defines for them, and the colors that the
+//! presentation layer assigns to them. Each file has a format version, and
+//! files with a version that doesn't match [`VERSION`] are rejected.
+//!
+//! Every object is written only once, and all references to it point to that
+//! single definition. This means that objects that are shared within the
+//! original object graph, are shared within the restored one too.
+//!
+//! The same object graph always results in the same file, which makes files
+//! suitable for comparing against a known-good version in tests.
+//!
+//! ## Implementation Note
+//!
+//! Files are assumed to come from a trusted source. Malformed files result in
+//! an error, but they are not otherwise checked for consistency. A file that
+//! describes an invalid object graph results in validation errors.
+
+mod format;
+mod read;
+mod write;
+
+use std::io::{self, Read, Write};
+
+use crate::{
+    topology::{Sketch, Solid},
+    Core,
+};
+
+pub use self::format::VERSION;
+
+use self::format::{File, FORMAT};
+
+/// Write an object to the native file format, and read it back
+///
+/// Implemented for [`Solid`] and [`Sketch`].
+pub trait Persist: Sized {
+    /// Write the object, and all objects it references, to `write`
+    fn persist(
+        &self,
+        core: &Core,
+        write: impl Write,
+    ) -> Result<(), PersistError>;
+
+    /// Read an object that was written by [`Persist::persist`]
+    ///
+    /// All objects that the object references are inserted into `core`, and
+    /// their geometry and presentation data is defined there. The object itself
+    /// is returned without being inserted, just like it was passed to
+    /// [`Persist::persist`].
+    fn restore(read: impl Read, core: &mut Core) -> Result<Self, PersistError>;
+}
+
+impl Persist for Solid {
+    fn persist(
+        &self,
+        core: &Core,
+        write: impl Write,
+    ) -> Result<(), PersistError> {
+        write_file(&write::solid(self, core), write)
+    }
+
+    fn restore(read: impl Read, core: &mut Core) -> Result<Self, PersistError> {
+        read::solid(read_file(read)?, core)
+    }
+}
+
+impl Persist for Sketch {
+    fn persist(
+        &self,
+        core: &Core,
+        write: impl Write,
+    ) -> Result<(), PersistError> {
+        write_file(&write::sketch(self, core), write)
+    }
+
+    fn restore(read: impl Read, core: &mut Core) -> Result<Self, PersistError> {
+        read::sketch(read_file(read)?, core)
+    }
+}
+
+fn write_file(file: &File, mut write: impl Write) -> Result<(), PersistError> {
+    serde_json::to_writer_pretty(&mut write, file)?;
+    writeln!(write)?;
+    Ok(())
+}
+
+fn read_file(read: impl Read) -> Result<File, PersistError> {
+    // Check format and version first. If the data model has changed since the
+    // file was written, we wouldn't get a useful error from deserializing it.
+    let value: serde_json::Value = serde_json::from_reader(read)?;
+
+    if value.get("format").and_then(|format| format.as_str()) != Some(FORMAT) {
+        return Err(PersistError::UnknownFormat);
+    }
+
+    let version = value.get("version").and_then(|version| version.as_u64());
+    if version != Some(VERSION) {
+        return Err(PersistError::UnsupportedVersion { version });
+    }
+
+    let file = serde_json::from_value(value)?;
+    Ok(file)
+}
+
+/// An error that can occur while writing or reading the native file format
+#[derive(Debug, thiserror::Error)]
+pub enum PersistError {
+    /// I/O error while writing or reading
+    #[error("I/O error while writing or reading file")]
+    Io(#[from] io::Error),
+
+    /// File is not valid JSON, or doesn't match the data model
+    #[error("Error encoding or decoding file")]
+    Json(#[from] serde_json::Error),
+
+    /// File is not in the native file format
+    #[error("File is not in the native file format")]
+    UnknownFormat,
+
+    /// File has been written with an unsupported version of the format
+    #[error("Unsupported format version `{version:?}` (expected `{VERSION}`)")]
+    UnsupportedVersion {
+        /// The version of the file, if it has one
+        version: Option<u64>,
+    },
+
+    /// File contains a different kind of object than expected
+    #[error("Expected file to contain a {expected}")]
+    UnexpectedRoot {
+        /// The kind of object that was expected
+        expected: &'static str,
+    },
+
+    /// File contains invalid data
+    #[error("Invalid data in file: {0}")]
+    InvalidData(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_interop::Color;
+    use fj_math::Spline;
+
+    use crate::{
+        algorithms::mass_properties::ComputeMassProperties,
+        operations::{
+            build::{BuildCycle, BuildHalfEdge, BuildRegion, BuildSketch},
+            insert::Insert,
+            reverse::Reverse,
+            sweep::SweepSketch,
+            update::{UpdateCycle, UpdateRegion, UpdateSketch},
+        },
+        topology::{Cycle, HalfEdge, Region, Sketch, Solid},
+        Core,
+    };
+
+    use super::{Persist, PersistError};
+
+    fn persist(object: &impl Persist, core: &Core) -> String {
+        let mut buffer = Vec::new();
+        object.persist(core, &mut buffer).unwrap();
+        String::from_utf8(buffer).unwrap()
+    }
+
+    #[test]
+    fn solid() {
+        let mut core = Core::new();
+
+        let surface = core.layers.topology.surfaces.space_2d();
+        let region = Region::circle([0., 0.], 2., surface.clone(), &mut core)
+            .add_interiors(
+                [Cycle::circle([0., 0.], 1., surface, &mut core)
+                    .reverse(&mut core)],
+                &mut core,
+            );
+        let spacer = Sketch::empty(&core.layers.topology)
+            .add_regions([region], &mut core)
+            .sweep_sketch(
+                core.layers.topology.surfaces.xy_plane(),
+                [0., 0., 1.],
+                &mut core,
+            );
+
+        let face = spacer.shells().first().faces().first().clone();
+        let color = Color([255, 0, 0, 255]);
+        core.layers
+            .presentation
+            .set_color(face.region().clone(), color);
+
+        let file = persist(&spacer, &core);
+
+        let mut restored_core = Core::new();
+        let restored =
+            Solid::restore(file.as_bytes(), &mut restored_core).unwrap();
+        assert!(restored_core.layers.validation.take_errors().is_ok());
+
+        // Writing the restored solid results in the same file, which means the
+        // structure of the object graph and all data have been preserved.
+        assert_eq!(persist(&restored, &restored_core), file);
+
+        let restored_face = restored.shells().first().faces().first();
+        assert_eq!(
+            restored_core
+                .layers
+                .presentation
+                .color
+                .get(restored_face.region()),
+            Some(&color)
+        );
+
+        let volume = |solid: &Solid, core: &Core| {
+            solid.mass_properties(0.01, &core.layers.geometry).volume
+        };
+        assert_eq!(volume(&restored, &restored_core), volume(&spacer, &core));
+    }
+
+    #[test]
+    fn sketch() {
+        let mut core = Core::new();
+
+        let surface = core.layers.topology.surfaces.space_2d();
+        let line = HalfEdge::line_segment(
+            [[-1., 0.], [1., 0.]],
+            None,
+            surface.clone(),
+            &mut core,
+        );
+        let arc = HalfEdge::arc([1., 0.], [-1., 0.], 3., surface, &mut core);
+        let cycle = Cycle::empty()
+            .add_half_edges([line, arc], &mut core)
+            .insert(&mut core);
+        let sketch = Sketch::empty(&core.layers.topology)
+            .add_regions([Region::new(cycle, [])], &mut core);
+
+        let file = persist(&sketch, &core);
+
+        let mut restored_core = Core::new();
+        let restored =
+            Sketch::restore(file.as_bytes(), &mut restored_core).unwrap();
+
+        assert_eq!(
+            restored.surface(),
+            &restored_core.layers.topology.surfaces.space_2d()
+        );
+        assert_eq!(persist(&restored, &restored_core), file);
+
+        let result = Solid::restore(file.as_bytes(), &mut Core::new());
+        assert!(matches!(
+            result,
+            Err(PersistError::UnexpectedRoot { expected: "solid" })
+        ));
+    }
+
+    #[test]
+    fn unsupported_version() {
+        let core = Core::new();
+        let sketch = Sketch::empty(&core.layers.topology);
+
+        let file = persist(&sketch, &core)
+            .replace("\"version\": 1", "\"version\": 1000");
+
+        let result = Sketch::restore(file.as_bytes(), &mut Core::new());
+        assert!(matches!(
+            result,
+            Err(PersistError::UnsupportedVersion {
+                version: Some(1000)
+            })
+        ));
+    }
+
+    #[test]
+    fn invalid_spline() {
+        let mut core = Core::new();
+
+        let surface = core.layers.topology.surfaces.space_2d();
+        let cycle = Cycle::new([
+            HalfEdge::line_segment(
+                [[0., 0.], [2., 0.]],
+                None,
+                surface.clone(),
+                &mut core,
+            ),
+            HalfEdge::spline(
+                Spline::bezier([[2., 0.], [2., 2.], [0., 2.], [0., 0.]]),
+                surface,
+                &mut core,
+            ),
+        ])
+        .insert(&mut core);
+        let sketch = Sketch::empty(&core.layers.topology)
+            .add_regions([Region::new(cycle, [])], &mut core);
+
+        let mut file: serde_json::Value =
+            serde_json::from_str(&persist(&sketch, &core)).unwrap();
+        let knots = file["curves"]
+            .as_array_mut()
+            .unwrap()
+            .iter_mut()
+            .find_map(|curve| curve.pointer_mut("/definitions/0/path/knots"))
+            .unwrap();
+        knots.as_array_mut().unwrap().reverse();
+
+        let result =
+            Sketch::restore(file.to_string().as_bytes(), &mut Core::new());
+        assert!(matches!(
+            result,
+            Err(PersistError::InvalidData(message))
+                if message == "Spline knots are decreasing"
+        ));
+    }
+}
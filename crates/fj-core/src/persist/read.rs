@@ -0,0 +1,206 @@
+use fj_interop::Color;
+use fj_math::Point;
+
+use crate::{
+    geometry::{
+        HalfEdgeGeom, LocalCurveGeom, RevolvedCurve, SurfaceGeom, SweptCurve,
+    },
+    operations::insert::Insert,
+    storage::Handle,
+    topology::{
+        Curve, Cycle, Face, HalfEdge, Region, Shell, Sketch, Solid, Surface,
+        Vertex,
+    },
+    Core,
+};
+
+use super::{
+    format::{line, vector, File, RootData, SurfaceData},
+    PersistError,
+};
+
+pub fn solid(file: File, core: &mut Core) -> Result<Solid, PersistError> {
+    let RootData::Solid { shells } = &file.root else {
+        return Err(PersistError::UnexpectedRoot { expected: "solid" });
+    };
+
+    let objects = Objects::read(&file, core)?;
+    let shells = get_all(&objects.shells, shells)?;
+
+    Ok(Solid::new(shells))
+}
+
+pub fn sketch(file: File, core: &mut Core) -> Result<Sketch, PersistError> {
+    let RootData::Sketch { surface, regions } = &file.root else {
+        return Err(PersistError::UnexpectedRoot { expected: "sketch" });
+    };
+
+    let objects = Objects::read(&file, core)?;
+    let surface = get(&objects.surfaces, *surface)?;
+    let regions = get_all(&objects.regions, regions)?;
+
+    Ok(Sketch::new(surface, regions))
+}
+
+/// The objects that have been read from a file, in the order they are listed
+struct Objects {
+    surfaces: Vec<Handle<Surface>>,
+    regions: Vec<Handle<Region>>,
+    shells: Vec<Handle<Shell>>,
+}
+
+impl Objects {
+    fn read(file: &File, core: &mut Core) -> Result<Self, PersistError> {
+        let mut surfaces = Vec::new();
+        for surface in &file.surfaces {
+            let stored = &core.layers.topology.surfaces;
+            let handle = match surface {
+                SurfaceData::Space2d => stored.space_2d(),
+                SurfaceData::XyPlane => stored.xy_plane(),
+                SurfaceData::XzPlane => stored.xz_plane(),
+                SurfaceData::YzPlane => stored.yz_plane(),
+                SurfaceData::Swept { u, v } => {
+                    let geometry = SurfaceGeom::Swept(SweptCurve {
+                        u: u.to_global_path()?,
+                        v: vector(v)?,
+                    });
+
+                    let surface = Surface::new().insert(core);
+                    core.layers
+                        .geometry
+                        .define_surface(surface.clone(), geometry);
+                    surface
+                }
+                SurfaceData::Revolved {
+                    axis_origin,
+                    axis_direction,
+                    radial,
+                    profile,
+                } => {
+                    let geometry = SurfaceGeom::Revolved(RevolvedCurve {
+                        axis: line(axis_origin, axis_direction)?,
+                        radial: vector(radial)?,
+                        profile: profile.to_surface_path()?,
+                    });
+
+                    let surface = Surface::new().insert(core);
+                    core.layers
+                        .geometry
+                        .define_surface(surface.clone(), geometry);
+                    surface
+                }
+            };
+
+            surfaces.push(handle);
+        }
+
+        let mut curves = Vec::new();
+        for curve in &file.curves {
+            let handle = Curve::new().insert(core);
+
+            for definition in &curve.definitions {
+                core.layers.geometry.define_curve(
+                    handle.clone(),
+                    get(&surfaces, definition.surface)?,
+                    LocalCurveGeom {
+                        path: definition.path.to_surface_path()?,
+                    },
+                );
+            }
+
+            curves.push(handle);
+        }
+
+        let vertices = (0..file.vertices)
+            .map(|_| Vertex::new().insert(core))
+            .collect::<Vec<_>>();
+
+        let mut half_edges = Vec::new();
+        for half_edge in &file.half_edges {
+            let handle = HalfEdge::new(
+                get(&curves, half_edge.curve)?,
+                get(&vertices, half_edge.start_vertex)?,
+            )
+            .insert(core);
+
+            core.layers.geometry.define_half_edge(
+                handle.clone(),
+                HalfEdgeGeom {
+                    path: half_edge.path.to_surface_path()?,
+                    boundary: half_edge.boundary.map(point_1d).into(),
+                },
+            );
+
+            half_edges.push(handle);
+        }
+
+        let mut cycles = Vec::new();
+        for cycle in &file.cycles {
+            let half_edges = get_all(&half_edges, &cycle.half_edges)?;
+            cycles.push(Cycle::new(half_edges).insert(core));
+        }
+
+        let mut regions = Vec::new();
+        for region in &file.regions {
+            let handle = Region::new(
+                get(&cycles, region.exterior)?,
+                get_all(&cycles, &region.interiors)?,
+            )
+            .insert(core);
+
+            if let Some(color) = region.color {
+                core.layers
+                    .presentation
+                    .set_color(handle.clone(), Color(color));
+            }
+
+            regions.push(handle);
+        }
+
+        let mut faces = Vec::new();
+        for face in &file.faces {
+            let handle = Face::new(
+                get(&surfaces, face.surface)?,
+                get(&regions, face.region)?,
+            )
+            .insert(core);
+
+            faces.push(handle);
+        }
+
+        let mut shells = Vec::new();
+        for shell in &file.shells {
+            let faces = get_all(&faces, &shell.faces)?;
+            shells.push(Shell::new(faces).insert(core));
+        }
+
+        Ok(Self {
+            surfaces,
+            regions,
+            shells,
+        })
+    }
+}
+
+fn get<T>(
+    handles: &[Handle<T>],
+    index: usize,
+) -> Result<Handle<T>, PersistError> {
+    handles.get(index).cloned().ok_or_else(|| {
+        PersistError::InvalidData(format!(
+            "Reference to object {index}, but only {} are defined",
+            handles.len()
+        ))
+    })
+}
+
+fn get_all<T>(
+    handles: &[Handle<T>],
+    indices: &[usize],
+) -> Result<Vec<Handle<T>>, PersistError> {
+    indices.iter().map(|&index| get(handles, index)).collect()
+}
+
+fn point_1d(t: f64) -> Point<1> {
+    Point::from([t])
+}
@@ -0,0 +1,262 @@
+use std::collections::BTreeMap;
+
+use crate::{
+    geometry::{SurfaceGeom, SweptCurve},
+    storage::Handle,
+    topology::{
+        Curve, Cycle, Face, HalfEdge, Region, Shell, Sketch, Solid, Surface,
+        Vertex,
+    },
+    Core,
+};
+
+use super::format::{
+    point_data, vector_data, CurveData, CurveDefinitionData, CycleData,
+    FaceData, File, HalfEdgeData, RegionData, RootData, ShellData, SurfaceData,
+    FORMAT, VERSION,
+};
+
+pub fn solid(solid: &Solid, core: &Core) -> File {
+    let mut writer = Writer::new(core);
+    let shells = solid
+        .shells()
+        .iter()
+        .map(|shell| writer.shell(shell))
+        .collect();
+
+    writer.finish(RootData::Solid { shells })
+}
+
+pub fn sketch(sketch: &Sketch, core: &Core) -> File {
+    let mut writer = Writer::new(core);
+    let surface = writer.surface(sketch.surface());
+    let regions = sketch
+        .regions()
+        .iter()
+        .map(|region| writer.region(region))
+        .collect();
+
+    writer.finish(RootData::Sketch { surface, regions })
+}
+
+/// Assigns indices to objects, as it encounters them
+///
+/// Objects that are referenced multiple times are only written once, which
+/// preserves the structure of the object graph.
+struct Writer<'r> {
+    core: &'r Core,
+
+    surfaces: Indexed<Surface, SurfaceData>,
+    curves: Indexed<Curve, CurveData>,
+    vertices: Indexed<Vertex, ()>,
+    half_edges: Indexed<HalfEdge, HalfEdgeData>,
+    cycles: Indexed<Cycle, CycleData>,
+    regions: Indexed<Region, RegionData>,
+    faces: Indexed<Face, FaceData>,
+    shells: Indexed<Shell, ShellData>,
+}
+
+impl<'r> Writer<'r> {
+    fn new(core: &'r Core) -> Self {
+        Self {
+            core,
+            surfaces: Indexed::default(),
+            curves: Indexed::default(),
+            vertices: Indexed::default(),
+            half_edges: Indexed::default(),
+            cycles: Indexed::default(),
+            regions: Indexed::default(),
+            faces: Indexed::default(),
+            shells: Indexed::default(),
+        }
+    }
+
+    fn finish(self, root: RootData) -> File {
+        File {
+            format: FORMAT.to_string(),
+            version: VERSION,
+            surfaces: self.surfaces.data,
+            curves: self.curves.data,
+            vertices: self.vertices.data.len(),
+            half_edges: self.half_edges.data,
+            cycles: self.cycles.data,
+            regions: self.regions.data,
+            faces: self.faces.data,
+            shells: self.shells.data,
+            root,
+        }
+    }
+
+    fn surface(&mut self, surface: &Handle<Surface>) -> usize {
+        if let Some(index) = self.surfaces.get(surface) {
+            return index;
+        }
+
+        let surfaces = &self.core.layers.topology.surfaces;
+        let data = if *surface == surfaces.space_2d() {
+            SurfaceData::Space2d
+        } else if *surface == surfaces.xy_plane() {
+            SurfaceData::XyPlane
+        } else if *surface == surfaces.xz_plane() {
+            SurfaceData::XzPlane
+        } else if *surface == surfaces.yz_plane() {
+            SurfaceData::YzPlane
+        } else {
+            match self.core.layers.geometry.of_surface(surface) {
+                SurfaceGeom::Swept(SweptCurve { u, v }) => SurfaceData::Swept {
+                    u: u.into(),
+                    v: vector_data(*v),
+                },
+                SurfaceGeom::Revolved(revolved) => SurfaceData::Revolved {
+                    axis_origin: point_data(revolved.axis.origin()),
+                    axis_direction: vector_data(revolved.axis.direction()),
+                    radial: vector_data(revolved.radial),
+                    profile: (&revolved.profile).into(),
+                },
+            }
+        };
+
+        self.surfaces.push(surface, data)
+    }
+
+    fn curve(&mut self, curve: &Handle<Curve>) -> usize {
+        if let Some(index) = self.curves.get(curve) {
+            return index;
+        }
+
+        let mut definitions = Vec::new();
+        if let Some(geometry) = self.core.layers.geometry.of_curve(curve) {
+            for (surface, local) in &geometry.definitions {
+                definitions.push(CurveDefinitionData {
+                    surface: self.surface(surface),
+                    path: (&local.path).into(),
+                });
+            }
+        }
+
+        // The definitions are stored in the order of the surfaces' addresses
+        // in memory. Sort them, so the same object graph always results in the
+        // same file.
+        definitions.sort_by_key(|definition| definition.surface);
+
+        self.curves.push(curve, CurveData { definitions })
+    }
+
+    fn vertex(&mut self, vertex: &Handle<Vertex>) -> usize {
+        if let Some(index) = self.vertices.get(vertex) {
+            return index;
+        }
+
+        self.vertices.push(vertex, ())
+    }
+
+    fn half_edge(&mut self, half_edge: &Handle<HalfEdge>) -> usize {
+        if let Some(index) = self.half_edges.get(half_edge) {
+            return index;
+        }
+
+        let geometry = self.core.layers.geometry.of_half_edge(half_edge);
+        let data = HalfEdgeData {
+            curve: self.curve(half_edge.curve()),
+            start_vertex: self.vertex(half_edge.start_vertex()),
+            path: (&geometry.path).into(),
+            boundary: geometry.boundary.inner.map(|point| point.t.into_f64()),
+        };
+
+        self.half_edges.push(half_edge, data)
+    }
+
+    fn cycle(&mut self, cycle: &Handle<Cycle>) -> usize {
+        if let Some(index) = self.cycles.get(cycle) {
+            return index;
+        }
+
+        let data = CycleData {
+            half_edges: cycle
+                .half_edges()
+                .iter()
+                .map(|half_edge| self.half_edge(half_edge))
+                .collect(),
+        };
+
+        self.cycles.push(cycle, data)
+    }
+
+    fn region(&mut self, region: &Handle<Region>) -> usize {
+        if let Some(index) = self.regions.get(region) {
+            return index;
+        }
+
+        let data = RegionData {
+            exterior: self.cycle(region.exterior()),
+            interiors: region
+                .interiors()
+                .iter()
+                .map(|cycle| self.cycle(cycle))
+                .collect(),
+            color: self
+                .core
+                .layers
+                .presentation
+                .color
+                .get(region)
+                .map(|color| color.0),
+        };
+
+        self.regions.push(region, data)
+    }
+
+    fn face(&mut self, face: &Handle<Face>) -> usize {
+        if let Some(index) = self.faces.get(face) {
+            return index;
+        }
+
+        let data = FaceData {
+            surface: self.surface(face.surface()),
+            region: self.region(face.region()),
+        };
+
+        self.faces.push(face, data)
+    }
+
+    fn shell(&mut self, shell: &Handle<Shell>) -> usize {
+        if let Some(index) = self.shells.get(shell) {
+            return index;
+        }
+
+        let data = ShellData {
+            faces: shell.faces().iter().map(|face| self.face(face)).collect(),
+        };
+
+        self.shells.push(shell, data)
+    }
+}
+
+struct Indexed<T, D> {
+    indices: BTreeMap<Handle<T>, usize>,
+    data: Vec<D>,
+}
+
+impl<T, D> Indexed<T, D> {
+    fn get(&self, handle: &Handle<T>) -> Option<usize> {
+        self.indices.get(handle).copied()
+    }
+
+    fn push(&mut self, handle: &Handle<T>, data: D) -> usize {
+        let index = self.data.len();
+
+        self.indices.insert(handle.clone(), index);
+        self.data.push(data);
+
+        index
+    }
+}
+
+impl<T, D> Default for Indexed<T, D> {
+    fn default() -> Self {
+        Self {
+            indices: BTreeMap::new(),
+            data: Vec::new(),
+        }
+    }
+}
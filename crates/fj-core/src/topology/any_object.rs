@@ -25,6 +25,17 @@ macro_rules! any_object {
             )*
         }
 
+        impl<F: Form> AnyObject<F> {
+            /// Access the name of the object's kind, for example `half-edge`
+            pub fn kind(&self) -> &'static str {
+                match self {
+                    $(
+                        Self::$ty(_) => $name,
+                    )*
+                }
+            }
+        }
+
         impl AnyObject<Stored> {
             /// Access the ID of the object
             pub fn id(&self) -> ObjectId {
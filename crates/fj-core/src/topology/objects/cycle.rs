@@ -1,4 +1,5 @@
 use fj_math::{Scalar, Winding};
+use itertools::Itertools;
 
 use crate::{
     geometry::{Geometry, SurfacePath},
@@ -30,10 +31,23 @@ impl Cycle {
     /// two possible windings, depending on the direction you look at the
     /// surface that the cycle is defined on from.
     pub fn winding(&self, geometry: &Geometry) -> Winding {
-        // The cycle could be made up of one or two circles. If that is the
-        // case, the winding of the cycle is determined by the winding of the
-        // first circle.
-        if self.half_edges.len() < 3 {
+        // Splines can bulge out in any direction, so they are represented by a
+        // few samples in addition to their start position, when treating the
+        // cycle as a polygon below. That results in enough points, even for
+        // cycles with less than 3 edges.
+        const SPLINE_SAMPLES: u64 = 8;
+
+        let has_splines = self.half_edges().iter().any(|half_edge| {
+            matches!(
+                geometry.of_half_edge(half_edge).path,
+                SurfacePath::Spline(_)
+            )
+        });
+
+        // Otherwise, the cycle could be made up of one or two circles. If that
+        // is the case, the winding of the cycle is determined by the winding of
+        // the first circle.
+        if self.half_edges.len() < 3 && !has_splines {
             let first = self
                 .half_edges()
                 .iter()
@@ -47,7 +61,7 @@ impl Cycle {
 
             let circle = match geometry.path {
                 SurfacePath::Circle(circle) => circle,
-                SurfacePath::Line(_) => unreachable!(
+                SurfacePath::Line(_) | SurfacePath::Spline(_) => unreachable!(
                     "Invalid cycle: less than 3 edges, but not all are circles"
                 ),
             };
@@ -64,13 +78,30 @@ impl Cycle {
         // cycle as a polygon:
         // https://stackoverflow.com/a/1165943
 
-        let mut sum = Scalar::ZERO;
+        let points = self
+            .half_edges()
+            .iter()
+            .flat_map(|half_edge| {
+                let geometry = geometry.of_half_edge(half_edge);
+                let [start, end] = geometry.boundary.inner;
 
-        for (a, b) in self.half_edges().pairs() {
-            let [a, b] = [a, b].map(|half_edge| {
-                geometry.of_half_edge(half_edge).start_position()
-            });
+                let num_samples = match geometry.path {
+                    SurfacePath::Spline(_) => SPLINE_SAMPLES,
+                    SurfacePath::Circle(_) | SurfacePath::Line(_) => 1,
+                };
+
+                (0..num_samples).map(move |i| {
+                    let t = Scalar::from_u64(i) / Scalar::from_u64(num_samples);
+                    geometry
+                        .path
+                        .point_from_path_coords(start + (end - start) * t)
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let mut sum = Scalar::ZERO;
 
+        for (a, b) in points.into_iter().circular_tuple_windows() {
             sum += (b.u - a.u) * (b.v + a.v);
         }
 
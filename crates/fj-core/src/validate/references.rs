@@ -1,7 +1,9 @@
 use std::collections::HashMap;
 
 use crate::storage::Handle;
-use crate::topology::{Cycle, Face, HalfEdge, Region, Shell};
+use crate::topology::{
+    AnyObject, Cycle, Face, HalfEdge, Region, Shell, Stored,
+};
 
 #[derive(Default)]
 pub struct ReferenceCounter<T, U>(HashMap<Handle<T>, Vec<Handle<U>>>);
@@ -79,11 +81,36 @@ pub enum ReferenceCountError {
     },
 }
 
+impl ReferenceCountError {
+    /// Access the referenced object, followed by the objects referencing it
+    pub(crate) fn objects(&self) -> Vec<AnyObject<Stored>> {
+        match self {
+            Self::Region { references } => references.objects(),
+            Self::Face { references } => references.objects(),
+            Self::HalfEdge { references } => references.objects(),
+            Self::Cycle { references } => references.objects(),
+        }
+    }
+}
+
 pub struct MultipleReferences<T, U> {
     referenced: Handle<T>,
     references: Vec<Handle<U>>,
 }
 
+impl<T, U> MultipleReferences<T, U>
+where
+    Handle<T>: Into<AnyObject<Stored>>,
+    Handle<U>: Into<AnyObject<Stored>>,
+{
+    fn objects(&self) -> Vec<AnyObject<Stored>> {
+        let referenced = self.referenced.clone().into();
+        let references = self.references.iter().map(|r| r.clone().into());
+
+        [referenced].into_iter().chain(references).collect()
+    }
+}
+
 use std::fmt::Debug;
 
 impl<T: Debug, U: Debug> Debug for MultipleReferences<T, U> {
@@ -453,10 +453,11 @@ mod tests {
                                 cycle.update_half_edge(
                                     cycle.half_edges().nth_circular(0),
                                     |half_edge, core| {
-                                        let mut geometry = *core
+                                        let mut geometry = core
                                             .layers
                                             .geometry
-                                            .of_half_edge(half_edge);
+                                            .of_half_edge(half_edge)
+                                            .clone();
                                         geometry.path = geometry.path.reverse();
                                         geometry.boundary =
                                             geometry.boundary.reverse();
@@ -550,10 +551,10 @@ mod tests {
                                             .update_curve(|_, _| curve, core)
                                             .insert(core)
                                             .set_geometry(
-                                                *core
-                                                    .layers
+                                                core.layers
                                                     .geometry
-                                                    .of_half_edge(half_edge),
+                                                    .of_half_edge(half_edge)
+                                                    .clone(),
                                                 &mut core.layers.geometry,
                                             )]
                                     },
@@ -1,5 +1,7 @@
 use fj_math::Scalar;
 
+use super::ValidationErrorKind;
+
 /// Configuration required for the validation process
 #[derive(Debug, Clone, Copy)]
 pub struct ValidationConfig {
@@ -16,6 +18,30 @@ pub struct ValidationConfig {
     /// that distance is less than the one defined in this field, can not be
     /// considered identical.
     pub identical_max_distance: Scalar,
+
+    /// The severity of each kind of validation error
+    ///
+    /// Indexed by [`ValidationErrorKind`]. By default, all kinds of validation
+    /// errors have a severity of [`Severity::Error`]. See
+    /// [`ValidationConfig::with_severity`] and [`ValidationConfig::severity`].
+    pub severities: [Severity; ValidationErrorKind::COUNT],
+}
+
+impl ValidationConfig {
+    /// Set the severity of a kind of validation error
+    pub fn with_severity(
+        mut self,
+        kind: ValidationErrorKind,
+        severity: Severity,
+    ) -> Self {
+        self.severities[kind as usize] = severity;
+        self
+    }
+
+    /// Access the severity of a kind of validation error
+    pub fn severity(&self, kind: ValidationErrorKind) -> Severity {
+        self.severities[kind as usize]
+    }
 }
 
 impl Default for ValidationConfig {
@@ -28,6 +54,109 @@ impl Default for ValidationConfig {
             // false positives due to floating-point accuracy issues), we can
             // adjust it.
             identical_max_distance: Scalar::from_f64(5e-14),
+
+            severities: [Severity::default(); ValidationErrorKind::COUNT],
         }
     }
 }
+
+/// The severity of a kind of validation error
+///
+/// Used by [`ValidationConfig`] to configure how validation errors of a given
+/// kind are handled.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub enum Severity {
+    /// Validation errors of this kind are fatal
+    ///
+    /// This is the default.
+    #[default]
+    Error,
+
+    /// Validation errors of this kind are collected as warnings
+    ///
+    /// Warnings are collected separately from errors, and don't cause a panic,
+    /// if they are left unhandled.
+    Warning,
+
+    /// Validation errors of this kind are discarded
+    Off,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        layers::validation::ValidateObject,
+        operations::{
+            build::{BuildCycle, BuildFace},
+            insert::Insert,
+            update::{UpdateFace, UpdateRegion},
+        },
+        storage::Handle,
+        topology::{Cycle, Face},
+        validation::ValidationErrorKind,
+        Core,
+    };
+
+    use super::{Severity, ValidationConfig};
+
+    fn insert_face_without_boundary(core: &mut Core) -> Handle<Face> {
+        Face::circle(
+            core.layers.topology.surfaces.xy_plane(),
+            [0., 0.],
+            1.,
+            core,
+        )
+        .update_region(
+            |region, core| region.update_exterior(|_, _| Cycle::empty(), core),
+            core,
+        )
+        .insert(core)
+    }
+
+    #[test]
+    fn severity() {
+        let kind = ValidationErrorKind::FaceHasNoBoundary;
+
+        let mut core = Core::with_validation_config(
+            ValidationConfig::default().with_severity(kind, Severity::Warning),
+        );
+        insert_face_without_boundary(&mut core);
+        assert!(core.layers.validation.take_errors().is_ok());
+        let warnings = core.layers.validation.take_warnings();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].err.kind(), kind);
+
+        let mut core = Core::with_validation_config(
+            ValidationConfig::default().with_severity(kind, Severity::Off),
+        );
+        insert_face_without_boundary(&mut core);
+        assert!(core.layers.validation.take_errors().is_ok());
+        assert!(core.layers.validation.take_warnings().is_empty());
+
+        let mut core = Core::new();
+        insert_face_without_boundary(&mut core);
+        assert!(core.layers.validation.take_errors().is_err());
+    }
+
+    #[test]
+    fn multiple_warnings_for_same_object() {
+        let kind = ValidationErrorKind::FaceHasNoBoundary;
+
+        let mut core = Core::with_validation_config(
+            ValidationConfig::default().with_severity(kind, Severity::Warning),
+        );
+        let face = insert_face_without_boundary(&mut core);
+
+        // Validating the same object again results in a second warning for it,
+        // which must not replace the first one.
+        core.layers.validation.process(
+            ValidateObject {
+                object: face.into(),
+                geometry: &core.layers.geometry,
+            },
+            &mut Vec::new(),
+        );
+
+        assert_eq!(core.layers.validation.take_warnings().len(), 2);
+    }
+}
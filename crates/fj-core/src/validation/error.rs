@@ -1,12 +1,18 @@
 use std::{convert::Infallible, fmt};
 
-use crate::validate::{
-    ShellValidationError, SketchValidationError, SolidValidationError,
+use crate::{
+    topology::{AnyObject, Stored},
+    validate::{
+        ShellValidationError, SketchValidationError, SolidValidationError,
+    },
 };
 
-use super::checks::{
-    AdjacentHalfEdgesNotConnected, FaceHasNoBoundary,
-    InteriorCycleHasInvalidWinding,
+use super::{
+    checks::{
+        AdjacentHalfEdgesNotConnected, FaceHasNoBoundary,
+        InteriorCycleHasInvalidWinding,
+    },
+    ValidationErrorReport, ValidationReport,
 };
 
 /// An error that can occur during a validation
@@ -37,15 +43,186 @@ pub enum ValidationError {
     Sketch(#[from] SketchValidationError),
 }
 
+impl ValidationError {
+    /// Access the kind of this validation error
+    pub fn kind(&self) -> ValidationErrorKind {
+        match self {
+            Self::AdjacentHalfEdgesNotConnected(_) => {
+                ValidationErrorKind::AdjacentHalfEdgesNotConnected
+            }
+            Self::FaceHasNoBoundary(_) => {
+                ValidationErrorKind::FaceHasNoBoundary
+            }
+            Self::InteriorCycleHasInvalidWinding(_) => {
+                ValidationErrorKind::InteriorCycleHasInvalidWinding
+            }
+            Self::Shell(err) => match err {
+                ShellValidationError::CurveCoordinateSystemMismatch(_) => {
+                    ValidationErrorKind::ShellCurveCoordinateSystemMismatch
+                }
+                ShellValidationError::HalfEdgeHasNoSibling { .. } => {
+                    ValidationErrorKind::ShellHalfEdgeHasNoSibling
+                }
+                ShellValidationError::CoincidentHalfEdgesAreNotSiblings {
+                    ..
+                } => {
+                    ValidationErrorKind::ShellCoincidentHalfEdgesAreNotSiblings
+                }
+            },
+            Self::Solid(err) => match err {
+                SolidValidationError::DistinctVerticesCoincide { .. } => {
+                    ValidationErrorKind::SolidDistinctVerticesCoincide
+                }
+                SolidValidationError::IdenticalVerticesNotCoincident {
+                    ..
+                } => ValidationErrorKind::SolidIdenticalVerticesNotCoincident,
+                SolidValidationError::MultipleReferences(_) => {
+                    ValidationErrorKind::SolidMultipleReferences
+                }
+            },
+            Self::Sketch(err) => match err {
+                SketchValidationError::MultipleReferences(_) => {
+                    ValidationErrorKind::SketchMultipleReferences
+                }
+                SketchValidationError::ClockwiseExteriorCycle { .. } => {
+                    ValidationErrorKind::SketchClockwiseExteriorCycle
+                }
+                SketchValidationError::CounterClockwiseInteriorCycle {
+                    ..
+                } => ValidationErrorKind::SketchCounterClockwiseInteriorCycle,
+            },
+        }
+    }
+}
+
 impl From<Infallible> for ValidationError {
     fn from(infallible: Infallible) -> Self {
         match infallible {}
     }
 }
 
+/// The kind of a [`ValidationError`]
+///
+/// There is one kind for each validation check in [`checks`], and one for each
+/// variant of the `*ValidationError` enums that are nested in
+/// [`ValidationError`]. Used to configure the [`Severity`] of validation errors
+/// in [`ValidationConfig`].
+///
+/// [`checks`]: super::checks
+/// [`Severity`]: super::Severity
+/// [`ValidationConfig`]: super::ValidationConfig
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub enum ValidationErrorKind {
+    /// See [`AdjacentHalfEdgesNotConnected`]
+    AdjacentHalfEdgesNotConnected,
+
+    /// See [`FaceHasNoBoundary`]
+    FaceHasNoBoundary,
+
+    /// See [`InteriorCycleHasInvalidWinding`]
+    InteriorCycleHasInvalidWinding,
+
+    /// See [`ShellValidationError::CurveCoordinateSystemMismatch`]
+    ShellCurveCoordinateSystemMismatch,
+
+    /// See [`ShellValidationError::HalfEdgeHasNoSibling`]
+    ShellHalfEdgeHasNoSibling,
+
+    /// See [`ShellValidationError::CoincidentHalfEdgesAreNotSiblings`]
+    ShellCoincidentHalfEdgesAreNotSiblings,
+
+    /// See [`SolidValidationError::DistinctVerticesCoincide`]
+    SolidDistinctVerticesCoincide,
+
+    /// See [`SolidValidationError::IdenticalVerticesNotCoincident`]
+    SolidIdenticalVerticesNotCoincident,
+
+    /// See [`SolidValidationError::MultipleReferences`]
+    SolidMultipleReferences,
+
+    /// See [`SketchValidationError::MultipleReferences`]
+    SketchMultipleReferences,
+
+    /// See [`SketchValidationError::ClockwiseExteriorCycle`]
+    SketchClockwiseExteriorCycle,
+
+    /// See [`SketchValidationError::CounterClockwiseInteriorCycle`]
+    SketchCounterClockwiseInteriorCycle,
+}
+
+impl ValidationErrorKind {
+    /// The number of kinds of validation errors
+    ///
+    /// This relies on the last variant being the one with the highest
+    /// discriminant, and needs to be kept up to date, if variants are added.
+    pub const COUNT: usize =
+        Self::SketchCounterClockwiseInteriorCycle as usize + 1;
+
+    /// Access the name of this kind of validation error
+    ///
+    /// This is the name of the variant of [`ValidationError`]. For errors that
+    /// are specific to a kind of object, the name of the nested variant is
+    /// appended, for example `Shell::HalfEdgeHasNoSibling`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::AdjacentHalfEdgesNotConnected => {
+                "AdjacentHalfEdgesNotConnected"
+            }
+            Self::FaceHasNoBoundary => "FaceHasNoBoundary",
+            Self::InteriorCycleHasInvalidWinding => {
+                "InteriorCycleHasInvalidWinding"
+            }
+            Self::ShellCurveCoordinateSystemMismatch => {
+                "Shell::CurveCoordinateSystemMismatch"
+            }
+            Self::ShellHalfEdgeHasNoSibling => "Shell::HalfEdgeHasNoSibling",
+            Self::ShellCoincidentHalfEdgesAreNotSiblings => {
+                "Shell::CoincidentHalfEdgesAreNotSiblings"
+            }
+            Self::SolidDistinctVerticesCoincide => {
+                "Solid::DistinctVerticesCoincide"
+            }
+            Self::SolidIdenticalVerticesNotCoincident => {
+                "Solid::IdenticalVerticesNotCoincident"
+            }
+            Self::SolidMultipleReferences => "Solid::MultipleReferences",
+            Self::SketchMultipleReferences => "Sketch::MultipleReferences",
+            Self::SketchClockwiseExteriorCycle => {
+                "Sketch::ClockwiseExteriorCycle"
+            }
+            Self::SketchCounterClockwiseInteriorCycle => {
+                "Sketch::CounterClockwiseInteriorCycle"
+            }
+        }
+    }
+}
+
+/// A validation error, together with the object it occurred for
+#[derive(Clone, Debug, thiserror::Error)]
+#[error("Invalid {} ({:?}): {err}", object.kind(), object.id())]
+pub struct ObjectValidationError {
+    /// The object for which validation failed
+    pub object: AnyObject<Stored>,
+
+    /// The validation error
+    pub err: ValidationError,
+}
+
 /// A collection of validation errors
 #[derive(Debug, thiserror::Error)]
-pub struct ValidationErrors(pub Vec<ValidationError>);
+pub struct ValidationErrors(pub Vec<ObjectValidationError>);
+
+impl ValidationErrors {
+    /// Create a structured report of the validation errors
+    ///
+    /// See [`ValidationReport`].
+    pub fn report(&self) -> ValidationReport {
+        ValidationReport {
+            errors: self.0.iter().map(ValidationErrorReport::new).collect(),
+            warnings: Vec::new(),
+        }
+    }
+}
 
 impl fmt::Display for ValidationErrors {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
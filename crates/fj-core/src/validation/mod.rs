@@ -22,14 +22,19 @@
 
 mod config;
 mod error;
+mod report;
 mod validation;
 mod validation_check;
 
 pub mod checks;
 
 pub use self::{
-    config::ValidationConfig,
-    error::{ValidationError, ValidationErrors},
+    config::{Severity, ValidationConfig},
+    error::{
+        ObjectValidationError, ValidationError, ValidationErrorKind,
+        ValidationErrors,
+    },
+    report::{ObjectReport, ValidationErrorReport, ValidationReport},
     validation::Validation,
     validation_check::ValidationCheck,
 };
@@ -0,0 +1,300 @@
+use fj_math::Point;
+use serde::Serialize;
+
+use crate::{
+    storage::Handle,
+    topology::{AnyObject, Stored, Vertex},
+    validate::{
+        ShellValidationError, SketchValidationError, SolidValidationError,
+    },
+};
+
+use super::{ObjectValidationError, ValidationError};
+
+/// A structured report of validation errors
+///
+/// Unlike the [`Display`] implementation of [`ValidationErrors`], which is
+/// meant to be read by humans, this report is meant to be processed by tools.
+/// It can be serialized, for example to JSON.
+///
+/// Created by [`ValidationErrors::report`].
+///
+/// [`Display`]: std::fmt::Display
+/// [`ValidationErrors`]: super::ValidationErrors
+/// [`ValidationErrors::report`]: super::ValidationErrors::report
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct ValidationReport {
+    /// The reports of all validation errors
+    pub errors: Vec<ValidationErrorReport>,
+
+    /// The reports of all validation errors with [`Severity::Warning`]
+    ///
+    /// [`Severity::Warning`]: super::Severity::Warning
+    pub warnings: Vec<ValidationErrorReport>,
+}
+
+/// A structured report of a single validation error
+#[derive(Clone, Debug, Serialize)]
+pub struct ValidationErrorReport {
+    /// The object for which validation failed
+    pub object: ObjectReport,
+
+    /// The kind of validation error
+    ///
+    /// See [`ValidationErrorKind::name`].
+    ///
+    /// [`ValidationErrorKind::name`]: super::ValidationErrorKind::name
+    pub error: String,
+
+    /// The human-readable error message
+    pub message: String,
+
+    /// Positions that are relevant to the error
+    ///
+    /// Depending on the error, these are either 2D positions in surface
+    /// coordinates, or 3D positions in global coordinates.
+    pub positions: Vec<Vec<f64>>,
+
+    /// Distances that are relevant to the error
+    pub distances: Vec<f64>,
+
+    /// Other objects that are involved in the error
+    pub related_objects: Vec<ObjectReport>,
+}
+
+impl ValidationErrorReport {
+    /// Create a report of the provided validation error
+    pub fn new(err: &ObjectValidationError) -> Self {
+        let mut report = Self {
+            object: ObjectReport::new(&err.object),
+            error: err.err.kind().name().to_string(),
+            message: err.err.to_string(),
+            positions: Vec::new(),
+            distances: Vec::new(),
+            related_objects: Vec::new(),
+        };
+
+        match &err.err {
+            ValidationError::AdjacentHalfEdgesNotConnected(err) => {
+                report.position(err.end_pos_of_first_half_edge);
+                report.position(err.start_pos_of_second_half_edge);
+                report
+                    .distances
+                    .push(err.distance_between_positions.into_f64());
+                report.related(&err.unconnected_half_edges);
+            }
+            ValidationError::FaceHasNoBoundary(_) => {}
+            ValidationError::InteriorCycleHasInvalidWinding(err) => {
+                report.related([&err.interior_cycle]);
+            }
+            ValidationError::Shell(err) => match err {
+                ShellValidationError::CurveCoordinateSystemMismatch(
+                    mismatches,
+                ) => {
+                    for mismatch in mismatches {
+                        report.position(mismatch.point_a);
+                        report.position(mismatch.point_b);
+                        report.distances.push(mismatch.distance.into_f64());
+                        report.related([
+                            &mismatch.half_edge_a,
+                            &mismatch.half_edge_b,
+                        ]);
+                    }
+                }
+                ShellValidationError::HalfEdgeHasNoSibling { half_edge } => {
+                    report.related([half_edge]);
+                }
+                ShellValidationError::CoincidentHalfEdgesAreNotSiblings {
+                    curves,
+                    half_edge_a,
+                    half_edge_b,
+                    ..
+                } => {
+                    report.related([half_edge_a, half_edge_b]);
+                    report.related(&curves.curves);
+                }
+            },
+            ValidationError::Solid(err) => match err {
+                SolidValidationError::DistinctVerticesCoincide {
+                    vertex_a,
+                    vertex_b,
+                    position_a,
+                    position_b,
+                } => {
+                    report.vertices(
+                        [vertex_a, vertex_b],
+                        [*position_a, *position_b],
+                    );
+                }
+                SolidValidationError::IdenticalVerticesNotCoincident {
+                    vertex_a,
+                    vertex_b,
+                    position_a,
+                    position_b,
+                } => {
+                    report.vertices(
+                        [vertex_a, vertex_b],
+                        [*position_a, *position_b],
+                    );
+                }
+                SolidValidationError::MultipleReferences(err) => {
+                    report
+                        .related_objects
+                        .extend(err.objects().iter().map(ObjectReport::new));
+                }
+            },
+            ValidationError::Sketch(err) => match err {
+                SketchValidationError::MultipleReferences(err) => {
+                    report
+                        .related_objects
+                        .extend(err.objects().iter().map(ObjectReport::new));
+                }
+                SketchValidationError::ClockwiseExteriorCycle { cycle } => {
+                    report.related([cycle]);
+                }
+                SketchValidationError::CounterClockwiseInteriorCycle {
+                    cycle,
+                } => {
+                    report.related([cycle]);
+                }
+            },
+        }
+
+        report
+    }
+
+    fn position<const D: usize>(&mut self, point: Point<D>) {
+        self.positions.push(
+            point
+                .coords
+                .components
+                .iter()
+                .map(|s| s.into_f64())
+                .collect(),
+        );
+    }
+
+    fn vertices(
+        &mut self,
+        vertices: [&Handle<Vertex>; 2],
+        [a, b]: [Point<3>; 2],
+    ) {
+        self.position(a);
+        self.position(b);
+        self.distances.push(a.distance_to(&b).into_f64());
+        self.related(vertices);
+    }
+
+    fn related<'r, T: 'r>(
+        &mut self,
+        objects: impl IntoIterator<Item = &'r Handle<T>>,
+    ) where
+        Handle<T>: Into<AnyObject<Stored>>,
+    {
+        self.related_objects.extend(
+            objects
+                .into_iter()
+                .map(|object| ObjectReport::new(&object.clone().into())),
+        );
+    }
+}
+
+/// Identifies an object within a [`ValidationReport`]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct ObjectReport {
+    /// The kind of object, for example `half-edge`
+    pub kind: &'static str,
+
+    /// The ID of the object
+    ///
+    /// Object IDs are unique within a single run of a model, but not stable
+    /// across runs.
+    pub id: u64,
+}
+
+impl ObjectReport {
+    /// Create an instance of `ObjectReport` for the provided object
+    pub fn new(object: &AnyObject<Stored>) -> Self {
+        Self {
+            kind: object.kind(),
+            id: object.id().0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        layers::validation::ValidateObject,
+        operations::{
+            build::{BuildCycle, BuildFace},
+            insert::Insert,
+            update::{UpdateFace, UpdateRegion},
+        },
+        topology::{AnyObject, Cycle, Face},
+        Core,
+    };
+
+    use super::ObjectReport;
+
+    #[test]
+    fn report_invalid_face() {
+        let mut core = Core::new();
+
+        let face = Face::circle(
+            core.layers.topology.surfaces.xy_plane(),
+            [0., 0.],
+            1.,
+            &mut core,
+        )
+        .update_region(
+            |region, core| region.update_exterior(|_, _| Cycle::empty(), core),
+            &mut core,
+        )
+        .insert(&mut core);
+
+        let errors = core.layers.validation.take_errors().unwrap_err();
+        let report = errors.report();
+
+        let [err] = report.errors.as_slice() else {
+            panic!("Expected exactly one error: {report:#?}");
+        };
+        assert_eq!(err.object, ObjectReport::new(&AnyObject::from(face)));
+        assert_eq!(err.object.kind, "face");
+        assert_eq!(err.error, "FaceHasNoBoundary");
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["errors"][0]["object"]["kind"], "face");
+        assert_eq!(json["errors"][0]["error"], "FaceHasNoBoundary");
+    }
+
+    #[test]
+    fn report_all_errors_of_object() {
+        let mut core = Core::new();
+
+        let face = Face::circle(
+            core.layers.topology.surfaces.xy_plane(),
+            [0., 0.],
+            1.,
+            &mut core,
+        )
+        .update_region(
+            |region, core| region.update_exterior(|_, _| Cycle::empty(), core),
+            &mut core,
+        )
+        .insert(&mut core);
+
+        // Validating the same object again results in a second error for it,
+        // which must not replace the first one.
+        core.layers.validation.process(
+            ValidateObject {
+                object: face.into(),
+                geometry: &core.layers.geometry,
+            },
+            &mut Vec::new(),
+        );
+
+        let errors = core.layers.validation.take_errors().unwrap_err();
+        assert_eq!(errors.report().errors.len(), 2);
+    }
+}
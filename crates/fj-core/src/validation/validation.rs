@@ -2,13 +2,21 @@ use std::{collections::HashMap, error::Error, thread};
 
 use crate::storage::ObjectId;
 
-use super::{ValidationConfig, ValidationError};
+use super::{ObjectValidationError, ValidationConfig};
 
 /// Errors that occurred while validating the objects inserted into the stores
 #[derive(Default)]
 pub struct Validation {
     /// All unhandled validation errors
-    pub errors: HashMap<ObjectId, ValidationError>,
+    pub errors: HashMap<ObjectId, Vec<ObjectValidationError>>,
+
+    /// All unhandled validation errors with [`Severity::Warning`]
+    ///
+    /// Unlike errors, warnings don't cause a panic when they are left
+    /// unhandled.
+    ///
+    /// [`Severity::Warning`]: super::Severity::Warning
+    pub warnings: HashMap<ObjectId, Vec<ObjectValidationError>>,
 
     /// Validation configuration for the validation service
     pub config: ValidationConfig,
@@ -17,26 +25,29 @@ pub struct Validation {
 impl Validation {
     /// Construct an instance of `Validation`, using the provided configuration
     pub fn with_validation_config(config: ValidationConfig) -> Self {
-        let errors = HashMap::new();
-        Self { errors, config }
+        Self {
+            errors: HashMap::new(),
+            warnings: HashMap::new(),
+            config,
+        }
     }
 }
 
 impl Drop for Validation {
     fn drop(&mut self) {
-        let num_errors = self.errors.len();
+        let num_errors = self.errors.values().map(Vec::len).sum::<usize>();
         if num_errors > 0 {
             println!(
                 "Dropping `Validation` with {num_errors} unhandled validation \
                 errors:"
             );
 
-            for err in self.errors.values() {
+            for err in self.errors.values().flatten() {
                 println!("{}", err);
 
                 // Once `Report` is stable, we can replace this:
                 // https://doc.rust-lang.org/std/error/struct.Report.html
-                let mut source = err.source();
+                let mut source = err.err.source();
                 while let Some(err) = source {
                     println!("\nCaused by:\n\t{err}");
                     source = err.source();
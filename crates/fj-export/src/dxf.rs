@@ -0,0 +1,239 @@
+//! # DXF export
+//!
+//! Exports the outlines of 2D shapes to an ASCII [DXF] file, as used for laser
+//! cutting, waterjet cutting, and similar processes.
+//!
+//! The file only uses `LINE`, `ARC` and `CIRCLE` entities from the R12 version
+//! of the format, which pretty much any application can read. The outlines are
+//! written in surface coordinates, which are millimeters. R12 files can't
+//! declare their units, so the application that reads the file needs to be
+//! configured to interpret them as millimeters.
+//!
+//! [DXF]: https://en.wikipedia.org/wiki/AutoCAD_DXF
+
+use std::{fmt::Display, io::Write};
+
+use fj_core::{
+    geometry::Geometry,
+    topology::{Region, Sketch},
+};
+use fj_math::{Point, Scalar};
+
+use crate::{
+    outline::{angle, outlines, Segment},
+    Error,
+};
+
+/// Export the outline of a 2D shape in the DXF format
+///
+/// Implemented for [`Sketch`] and [`Region`]. Lines and circles are exported
+/// exactly, splines are approximated by lines.
+pub trait ExportDxf {
+    /// Export the object to the provided writer in the DXF format
+    fn export_dxf(
+        self,
+        geometry: &Geometry,
+        write: impl Write,
+    ) -> Result<(), Error>;
+}
+
+impl ExportDxf for &Sketch {
+    fn export_dxf(
+        self,
+        geometry: &Geometry,
+        write: impl Write,
+    ) -> Result<(), Error> {
+        export_regions(
+            self.regions().iter().map(|region| &**region),
+            geometry,
+            write,
+        )
+    }
+}
+
+impl ExportDxf for &Region {
+    fn export_dxf(
+        self,
+        geometry: &Geometry,
+        write: impl Write,
+    ) -> Result<(), Error> {
+        export_regions([self], geometry, write)
+    }
+}
+
+fn export_regions<'r>(
+    regions: impl IntoIterator<Item = &'r Region>,
+    geometry: &Geometry,
+    mut write: impl Write,
+) -> Result<(), Error> {
+    let mut dxf = DxfWriter { write: &mut write };
+
+    dxf.pair(0, "SECTION")?;
+    dxf.pair(2, "HEADER")?;
+    dxf.pair(9, "$ACADVER")?;
+    dxf.pair(1, "AC1009")?;
+    dxf.pair(0, "ENDSEC")?;
+
+    dxf.pair(0, "SECTION")?;
+    dxf.pair(2, "ENTITIES")?;
+    for region in regions {
+        for segment in outlines(region, geometry).iter().flatten() {
+            dxf.segment(segment)?;
+        }
+    }
+    dxf.pair(0, "ENDSEC")?;
+
+    dxf.pair(0, "EOF")?;
+
+    Ok(())
+}
+
+struct DxfWriter<W> {
+    write: W,
+}
+
+impl<W: Write> DxfWriter<W> {
+    /// Write a group code and its value
+    fn pair(&mut self, code: u16, value: impl Display) -> Result<(), Error> {
+        writeln!(self.write, "{code:>3}")?;
+        writeln!(self.write, "{value}")?;
+        Ok(())
+    }
+
+    /// Write a point, starting at the provided group code for its x-coordinate
+    fn point(&mut self, code: u16, point: Point<2>) -> Result<(), Error> {
+        self.pair(code, point.u)?;
+        self.pair(code + 10, point.v)?;
+        self.pair(code + 20, 0.)?;
+        Ok(())
+    }
+
+    fn entity(&mut self, entity: &str) -> Result<(), Error> {
+        self.pair(0, entity)?;
+        self.pair(8, "0")?;
+        Ok(())
+    }
+
+    fn segment(&mut self, segment: &Segment) -> Result<(), Error> {
+        match *segment {
+            Segment::Line { start, end } => {
+                self.entity("LINE")?;
+                self.point(10, start)?;
+                self.point(11, end)?;
+            }
+            Segment::Arc { center, radius, .. } if segment.is_full_circle() => {
+                self.entity("CIRCLE")?;
+                self.point(10, center)?;
+                self.pair(40, radius)?;
+            }
+            Segment::Arc {
+                center,
+                radius,
+                start,
+                end,
+                sweep,
+            } => {
+                // DXF arcs always go counter-clockwise, from the start angle to
+                // the end angle.
+                let [start, end] = if sweep > Scalar::ZERO {
+                    [start, end]
+                } else {
+                    [end, start]
+                };
+
+                self.entity("ARC")?;
+                self.point(10, center)?;
+                self.pair(40, radius)?;
+                self.pair(50, degrees(angle(center, start)))?;
+                self.pair(51, degrees(angle(center, end)))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn degrees(angle: Scalar) -> f64 {
+    angle.into_f64().to_degrees()
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_core::{
+        operations::{
+            build::{BuildCycle, BuildHalfEdge, BuildRegion},
+            insert::Insert,
+            update::UpdateCycle,
+        },
+        topology::{Cycle, HalfEdge, Region},
+        Core,
+    };
+    use fj_math::Scalar;
+
+    use super::ExportDxf;
+
+    fn export(region: &Region, core: &Core) -> String {
+        let mut buffer = Vec::new();
+        region
+            .export_dxf(&core.layers.geometry, &mut buffer)
+            .unwrap();
+        String::from_utf8(buffer).unwrap()
+    }
+
+    #[test]
+    fn export_region_with_hole() {
+        let mut core = Core::new();
+
+        let exterior = Region::polygon(
+            [[0., 0.], [10., 0.], [10., 10.], [0., 10.]],
+            core.layers.topology.surfaces.space_2d(),
+            &mut core,
+        );
+        let hole = Region::circle(
+            [5., 5.],
+            2.,
+            core.layers.topology.surfaces.space_2d(),
+            &mut core,
+        );
+        let region =
+            Region::new(exterior.exterior().clone(), [hole.exterior().clone()]);
+
+        let dxf = export(&region, &core);
+
+        // The header only declares the R12 version of the format. Header
+        // variables from later versions, like `$INSUNITS`, are not allowed.
+        assert!(dxf.starts_with(
+            "  0\nSECTION\n  2\nHEADER\n  9\n$ACADVER\n  1\nAC1009\n  0\nENDSEC\n"
+        ));
+        assert!(dxf.trim_end().ends_with("EOF"));
+        assert_eq!(dxf.matches("\nLINE\n").count(), 4);
+        assert_eq!(dxf.matches("\nCIRCLE\n").count(), 1);
+        assert_eq!(dxf.matches("\nARC\n").count(), 0);
+    }
+
+    #[test]
+    fn export_arc() {
+        let mut core = Core::new();
+
+        let surface = core.layers.topology.surfaces.space_2d();
+        let line = HalfEdge::line_segment(
+            [[-1., 0.], [1., 0.]],
+            None,
+            surface.clone(),
+            &mut core,
+        );
+        let arc =
+            HalfEdge::arc([1., 0.], [-1., 0.], Scalar::PI, surface, &mut core);
+        let cycle = Cycle::empty()
+            .add_half_edges([line, arc], &mut core)
+            .insert(&mut core);
+        let region = Region::new(cycle, []);
+
+        let dxf = export(&region, &core);
+
+        assert_eq!(dxf.matches("\nLINE\n").count(), 1);
+        assert!(dxf.contains(
+            "\nARC\n  8\n0\n 10\n0\n 20\n0\n 30\n0\n 40\n1\n 50\n0\n 51\n180\n"
+        ));
+    }
+}
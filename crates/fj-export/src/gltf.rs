@@ -0,0 +1,403 @@
+//! # glTF export
+//!
+//! Exports a mesh to [glTF 2.0], either as a self-contained `.gltf` file that
+//! embeds its binary data, or as a binary `.glb` file.
+//!
+//! glTF uses meters and a coordinate system where y points up, while Fornjot
+//! models are defined in millimeters, with z pointing up. The vertex data is
+//! written as-is, and the node that refers to the mesh converts it.
+//!
+//! [glTF 2.0]: https://registry.khronos.org/glTF/specs/2.0/glTF-2.0.html
+
+use std::{collections::BTreeMap, io::Write};
+
+use base64::Engine;
+use fj_interop::{Color, Mesh};
+use fj_math::{Point, Vector};
+use serde_json::json;
+
+use crate::{material_name, Colors, Error};
+
+/// Export the provided mesh to the provided writer in the glTF format
+///
+/// The binary data is embedded into the file, as a base64-encoded data URI.
+/// Each color of the mesh becomes a material.
+///
+/// glTF doesn't allow empty buffers, so an empty mesh results in a file that
+/// contains an empty scene, without any buffers.
+pub fn export_gltf(
+    mesh: &Mesh<Point<3>>,
+    mut write: impl Write,
+) -> Result<(), Error> {
+    let (mut json, buffer) = gltf(mesh);
+
+    if !buffer.is_empty() {
+        json["buffers"] = json!([{
+            "byteLength": buffer.len(),
+            "uri": format!(
+                "data:application/octet-stream;base64,{}",
+                base64::engine::general_purpose::STANDARD.encode(&buffer)
+            ),
+        }]);
+    }
+
+    serde_json::to_writer(&mut write, &json).map_err(std::io::Error::from)?;
+
+    Ok(())
+}
+
+/// Export the provided mesh to the provided writer in the binary glTF format
+///
+/// Each color of the mesh becomes a material.
+///
+/// glTF doesn't allow empty buffers, so an empty mesh results in a file that
+/// contains an empty scene, without a binary chunk.
+pub fn export_glb(
+    mesh: &Mesh<Point<3>>,
+    mut write: impl Write,
+) -> Result<(), Error> {
+    const MAGIC: &[u8] = b"glTF";
+    const VERSION: u32 = 2;
+    const CHUNK_TYPE_JSON: &[u8] = b"JSON";
+    const CHUNK_TYPE_BIN: &[u8] = b"BIN\0";
+
+    let (mut json, mut buffer) = gltf(mesh);
+
+    if !buffer.is_empty() {
+        json["buffers"] = json!([{ "byteLength": buffer.len() }]);
+    }
+
+    // Chunks need to be aligned to 4 bytes. The JSON chunk is padded with
+    // spaces, the binary chunk with zeros.
+    let mut json = serde_json::to_vec(&json).map_err(std::io::Error::from)?;
+    pad(&mut json, b' ');
+    pad(&mut buffer, 0);
+
+    let mut chunks = vec![(CHUNK_TYPE_JSON, json)];
+    if !buffer.is_empty() {
+        chunks.push((CHUNK_TYPE_BIN, buffer));
+    }
+
+    let length =
+        12 + chunks.iter().map(|(_, data)| 8 + data.len()).sum::<usize>();
+    let length =
+        u32::try_from(length).map_err(|_| Error::InvalidTriangleCount)?;
+
+    write.write_all(MAGIC)?;
+    write.write_all(&VERSION.to_le_bytes())?;
+    write.write_all(&length.to_le_bytes())?;
+
+    for (chunk_type, data) in chunks {
+        // Both chunks are smaller than the whole file, whose size we already
+        // checked.
+        let chunk_length = data.len() as u32;
+
+        write.write_all(&chunk_length.to_le_bytes())?;
+        write.write_all(chunk_type)?;
+        write.write_all(&data)?;
+    }
+
+    Ok(())
+}
+
+/// Build the glTF JSON (minus `buffers`) and the binary buffer it refers to
+fn gltf(mesh: &Mesh<Point<3>>) -> (serde_json::Value, Vec<u8>) {
+    const FLOAT: u32 = 5126;
+    const UNSIGNED_INT: u32 = 5125;
+    const ARRAY_BUFFER: u32 = 34962;
+    const ELEMENT_ARRAY_BUFFER: u32 = 34963;
+
+    let colors = Colors::new(mesh);
+
+    // glTF vertices have a single index for all their attributes, so a point
+    // that has different normals in different triangles becomes multiple
+    // vertices.
+    let mut vertices = Vec::new();
+    let mut indices_by_vertex = BTreeMap::new();
+    let mut indices_by_color = vec![Vec::new(); colors.iter().count()];
+
+    for triangle in mesh.triangles() {
+        let indices = &mut indices_by_color[colors.index_of(triangle.color)];
+
+        for (point, normal) in
+            triangle.inner.points().into_iter().zip(triangle.normals)
+        {
+            let index = *indices_by_vertex
+                .entry((point, normal))
+                .or_insert_with(|| {
+                    vertices.push((point, normal));
+                    vertices.len() as u32 - 1
+                });
+
+            indices.push(index);
+        }
+    }
+
+    let mut json = json!({
+        "asset": {
+            "version": "2.0",
+            "generator": "Fornjot",
+        },
+        "scene": 0,
+        "scenes": [{ "nodes": [0] }],
+        "nodes": [{
+            // Convert from millimeters and z-up into meters and y-up.
+            "rotation": [-std::f64::consts::FRAC_1_SQRT_2, 0., 0., std::f64::consts::FRAC_1_SQRT_2],
+            "scale": [0.001, 0.001, 0.001],
+        }],
+    });
+
+    if vertices.is_empty() {
+        // Accessors and buffers can't be empty, so there's nothing we could
+        // refer to.
+        return (json, Vec::new());
+    }
+
+    let mut buffer = Vec::new();
+
+    let positions_offset = buffer.len();
+    let mut min = [f32::INFINITY; 3];
+    let mut max = [f32::NEG_INFINITY; 3];
+    for (point, _) in &vertices {
+        let point = point.coords.components.map(|s| s.into_f32());
+        for i in 0..3 {
+            min[i] = min[i].min(point[i]);
+            max[i] = max[i].max(point[i]);
+        }
+        push_vector(&mut buffer, point);
+    }
+
+    let normals_offset = buffer.len();
+    for (_, normal) in &vertices {
+        push_vector(&mut buffer, normalized(normal));
+    }
+
+    let indices_offset = buffer.len();
+    let mut accessors = vec![
+        json!({
+            "bufferView": 0,
+            "componentType": FLOAT,
+            "count": vertices.len(),
+            "type": "VEC3",
+            "min": min,
+            "max": max,
+        }),
+        json!({
+            "bufferView": 1,
+            "componentType": FLOAT,
+            "count": vertices.len(),
+            "type": "VEC3",
+        }),
+    ];
+    let mut primitives = Vec::new();
+    for (material, indices) in indices_by_color.iter().enumerate() {
+        accessors.push(json!({
+            "bufferView": 2,
+            "byteOffset": buffer.len() - indices_offset,
+            "componentType": UNSIGNED_INT,
+            "count": indices.len(),
+            "type": "SCALAR",
+        }));
+        primitives.push(json!({
+            "attributes": { "POSITION": 0, "NORMAL": 1 },
+            "indices": accessors.len() - 1,
+            "material": material,
+        }));
+
+        for index in indices {
+            buffer.extend(index.to_le_bytes());
+        }
+    }
+
+    let materials = colors
+        .iter()
+        .map(|&color| {
+            let Color([r, g, b, a]) = color;
+            let base_color = [
+                srgb_to_linear(r),
+                srgb_to_linear(g),
+                srgb_to_linear(b),
+                f64::from(a) / 255.,
+            ];
+
+            json!({
+                "name": material_name(color),
+                "pbrMetallicRoughness": {
+                    "baseColorFactor": base_color,
+                    "metallicFactor": 0.,
+                    "roughnessFactor": 0.5,
+                },
+                "alphaMode": if a == 255 { "OPAQUE" } else { "BLEND" },
+            })
+        })
+        .collect::<Vec<_>>();
+
+    json["nodes"][0]["mesh"] = json!(0);
+    json["meshes"] = json!([{ "primitives": primitives }]);
+    json["materials"] = json!(materials);
+    json["accessors"] = json!(accessors);
+    json["bufferViews"] = json!([
+        {
+            "buffer": 0,
+            "byteOffset": positions_offset,
+            "byteLength": normals_offset - positions_offset,
+            "target": ARRAY_BUFFER,
+        },
+        {
+            "buffer": 0,
+            "byteOffset": normals_offset,
+            "byteLength": indices_offset - normals_offset,
+            "target": ARRAY_BUFFER,
+        },
+        {
+            "buffer": 0,
+            "byteOffset": indices_offset,
+            "byteLength": buffer.len() - indices_offset,
+            "target": ELEMENT_ARRAY_BUFFER,
+        },
+    ]);
+
+    (json, buffer)
+}
+
+fn push_vector(buffer: &mut Vec<u8>, vector: [f32; 3]) {
+    for component in vector {
+        buffer.extend(component.to_le_bytes());
+    }
+}
+
+/// Normalize a vector after conversion to `f32`
+///
+/// glTF requires normals to be unit length, which they might no longer be
+/// exactly after conversion.
+fn normalized(vector: &Vector<3>) -> [f32; 3] {
+    let [x, y, z] = vector.components.map(|s| s.into_f32());
+    let length = (x * x + y * y + z * z).sqrt();
+    [x / length, y / length, z / length]
+}
+
+/// Convert a color component from sRGB into linear space, as glTF expects
+fn srgb_to_linear(component: u8) -> f64 {
+    let c = f64::from(component) / 255.;
+
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn pad(data: &mut Vec<u8>, padding: u8) {
+    while data.len() % 4 != 0 {
+        data.push(padding);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use base64::Engine;
+    use fj_interop::{Color, Mesh};
+    use fj_math::Point;
+
+    fn two_color_mesh() -> Mesh<Point<3>> {
+        let mut mesh = Mesh::new();
+        mesh.push_triangle(
+            [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.]],
+            Color([255, 0, 0, 255]),
+        );
+        mesh.push_triangle(
+            [[1., 0., 0.], [1., 1., 0.], [0., 1., 0.]],
+            Color([255, 0, 0, 255]),
+        );
+        mesh.push_triangle(
+            [[0., 0., 0.], [0., 1., 0.], [0., 0., 1.]],
+            Color([0, 0, 255, 128]),
+        );
+        mesh
+    }
+
+    #[test]
+    fn export_glb() {
+        let mut glb = Vec::new();
+        super::export_glb(&two_color_mesh(), &mut glb).unwrap();
+
+        let u32_at =
+            |i: usize| u32::from_le_bytes(glb[i..i + 4].try_into().unwrap());
+
+        assert_eq!(&glb[..4], b"glTF");
+        assert_eq!(u32_at(4), 2);
+        assert_eq!(u32_at(8) as usize, glb.len());
+
+        let json_length = u32_at(12) as usize;
+        assert_eq!(&glb[16..20], b"JSON");
+        let json: serde_json::Value =
+            serde_json::from_slice(&glb[20..20 + json_length]).unwrap();
+
+        let bin_start = 20 + json_length;
+        assert_eq!(&glb[bin_start + 4..bin_start + 8], b"BIN\0");
+        let bin_length = u32_at(bin_start) as usize;
+        assert!(
+            json["buffers"][0]["byteLength"].as_u64().unwrap() as usize
+                <= bin_length
+        );
+
+        // The two red triangles share two of their points, and have the same
+        // normal there. The blue triangle has a different normal.
+        assert_eq!(json["accessors"][0]["count"], 4 + 3);
+        assert_eq!(json["materials"].as_array().unwrap().len(), 2);
+        let primitives = json["meshes"][0]["primitives"].as_array().unwrap();
+        assert_eq!(primitives.len(), 2);
+        assert_eq!(json["accessors"][2]["count"], 6);
+        assert_eq!(json["accessors"][3]["count"], 3);
+        assert_eq!(json["materials"][1]["alphaMode"], "BLEND");
+    }
+
+    #[test]
+    fn export_empty_mesh() {
+        let mesh = Mesh::new();
+
+        let mut gltf = Vec::new();
+        super::export_gltf(&mesh, &mut gltf).unwrap();
+
+        let json: serde_json::Value = serde_json::from_slice(&gltf).unwrap();
+        for key in ["buffers", "bufferViews", "accessors", "meshes"] {
+            assert!(json.get(key).is_none());
+        }
+        assert!(json["nodes"][0].get("mesh").is_none());
+
+        let mut glb = Vec::new();
+        super::export_glb(&mesh, &mut glb).unwrap();
+
+        // The file consists of the header and the JSON chunk only.
+        let json_length =
+            u32::from_le_bytes(glb[12..16].try_into().unwrap()) as usize;
+        assert_eq!(glb.len(), 12 + 8 + json_length);
+        assert_eq!(&glb[16..20], b"JSON");
+
+        let json: serde_json::Value =
+            serde_json::from_slice(&glb[20..]).unwrap();
+        assert!(json.get("buffers").is_none());
+    }
+
+    #[test]
+    fn export_gltf_with_embedded_buffer() {
+        let mut gltf = Vec::new();
+        super::export_gltf(&two_color_mesh(), &mut gltf).unwrap();
+
+        let json: serde_json::Value = serde_json::from_slice(&gltf).unwrap();
+
+        let uri = json["buffers"][0]["uri"].as_str().unwrap();
+        let data = uri
+            .strip_prefix("data:application/octet-stream;base64,")
+            .unwrap();
+        let buffer = base64::engine::general_purpose::STANDARD
+            .decode(data)
+            .unwrap();
+        assert_eq!(json["buffers"][0]["byteLength"], buffer.len());
+
+        let view = &json["bufferViews"][2];
+        let end = view["byteOffset"].as_u64().unwrap()
+            + view["byteLength"].as_u64().unwrap();
+        assert_eq!(end as usize, buffer.len());
+    }
+}
@@ -8,36 +8,94 @@
 //!
 //! [Fornjot]: https://www.fornjot.app/
 
+mod dxf;
+mod gltf;
+mod outline;
+mod step;
+mod svg;
+
+pub use self::{
+    dxf::ExportDxf,
+    gltf::{export_glb, export_gltf},
+    step::ExportStep,
+    svg::ExportSvg,
+};
+
 use std::{
+    collections::{BTreeMap, HashMap},
     fs::File,
     io::{Seek, Write},
     path::Path,
+    str::FromStr,
 };
 
 use thiserror::Error;
 
-use fj_interop::Mesh;
-use fj_math::{Point, Triangle};
+use fj_interop::{ext::ArrayExt, Color, Mesh};
+use fj_math::Point;
 
 /// Export the provided mesh to the file at the given path.
 ///
-/// This function will create a file if it does not exist, and will truncate it if it does.
+/// This function will create a file if it does not exist, and will truncate it
+/// if it does.
+///
+/// Currently 3MF, STL, OBJ & glTF (`.gltf` and `.glb`) file types are
+/// supported. OBJ files are accompanied by an MTL file with the same name,
+/// which holds the colors. The case insensitive file extension of the provided
+/// path is used to switch between supported types.
 ///
-/// Currently 3MF & STL file types are supported. The case insensitive file extension of
-/// the provided path is used to switch between supported types.
-pub fn export(mesh: &Mesh<Point<3>>, path: &Path) -> Result<(), Error> {
+/// The provided options configure the export to formats that support that. If
+/// no model name is set for STL, the file name is used instead.
+///
+/// To export the exact b-rep instead of a mesh, use [`ExportStep`].
+pub fn export(
+    mesh: &Mesh<Point<3>>,
+    path: &Path,
+    options: &ExportOptions,
+) -> Result<(), Error> {
     match path.extension() {
         Some(extension) if extension.to_ascii_uppercase() == "3MF" => {
             let mut file = File::create(path)?;
             export_3mf(mesh, &mut file)
         }
         Some(extension) if extension.to_ascii_uppercase() == "STL" => {
+            let mut options = options.stl.clone();
+            if options.name.is_none() {
+                options.name = path
+                    .file_stem()
+                    .map(|stem| stem.to_string_lossy().into_owned());
+            }
+
             let mut file = File::create(path)?;
-            export_stl(mesh, &mut file)
+            export_stl_with_options(mesh, &mut file, &options)
         }
         Some(extension) if extension.to_ascii_uppercase() == "OBJ" => {
+            // The materials go into an MTL file next to the OBJ file, which
+            // references it by name.
+            let mtl_path = path.with_extension("mtl");
+            let mtl_name = mtl_path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default();
+
+            let options = ObjOptions {
+                mtllib: Some(mtl_name),
+                ..options.obj.clone()
+            };
+
+            let mut file = File::create(path)?;
+            export_obj_with_options(mesh, &mut file, &options)?;
+
+            let mut file = File::create(mtl_path)?;
+            export_mtl(mesh, &mut file)
+        }
+        Some(extension) if extension.to_ascii_uppercase() == "GLTF" => {
             let mut file = File::create(path)?;
-            export_obj(mesh, &mut file)
+            export_gltf(mesh, &mut file)
+        }
+        Some(extension) if extension.to_ascii_uppercase() == "GLB" => {
+            let mut file = File::create(path)?;
+            export_glb(mesh, &mut file)
         }
         Some(extension) => Err(Error::InvalidExtension(
             extension.to_string_lossy().into_owned(),
@@ -47,139 +105,546 @@ pub fn export(mesh: &Mesh<Point<3>>, path: &Path) -> Result<(), Error> {
 }
 
 /// Export the provided mesh to the provided writer in the 3MF format.
+///
+/// The colors of the mesh's triangles are exported as base materials. If the
+/// mesh has no triangles, and therefore no colors, no base materials are
+/// written, as the 3MF core specification requires at least one.
 pub fn export_3mf(
     mesh: &Mesh<Point<3>>,
     write: impl Write + Seek,
 ) -> Result<(), Error> {
-    let vertices = mesh
-        .vertices()
-        .map(|point| threemf::model::Vertex {
-            x: point.x.into_f64(),
-            y: point.y.into_f64(),
-            z: point.z.into_f64(),
-        })
-        .collect();
+    let colors = Colors::new(mesh);
 
+    let mut model = String::new();
+    model.push_str(
+        "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n\
+        <model unit=\"millimeter\" xml:lang=\"en-US\" \
+        xmlns=\"http://schemas.microsoft.com/3dmanufacturing/core/2015/02\">\n\
+        <resources>\n",
+    );
+    if !colors.is_empty() {
+        model.push_str("<basematerials id=\"1\">\n");
+        for (i, Color([r, g, b, a])) in colors.iter().enumerate() {
+            model.push_str(&format!(
+                "<base name=\"color {i}\" \
+                displaycolor=\"#{r:02X}{g:02X}{b:02X}{a:02X}\" />\n"
+            ));
+        }
+        model.push_str("</basematerials>\n");
+    }
+    model.push_str(
+        "<object id=\"2\" type=\"model\">\n\
+        <mesh>\n\
+        <vertices>\n",
+    );
+    for point in mesh.vertices() {
+        model.push_str(&format!(
+            "<vertex x=\"{}\" y=\"{}\" z=\"{}\" />\n",
+            point.x.into_f64(),
+            point.y.into_f64(),
+            point.z.into_f64(),
+        ));
+    }
+    model.push_str("</vertices>\n<triangles>\n");
     let indices: Vec<_> = mesh.indices().collect();
-    let triangles = indices
-        .chunks(3)
-        .map(|triangle| threemf::model::Triangle {
-            v1: triangle[0] as usize,
-            v2: triangle[1] as usize,
-            v3: triangle[2] as usize,
-        })
-        .collect();
+    for (triangle, color) in indices.chunks(3).zip(mesh.triangles()) {
+        model.push_str(&format!(
+            "<triangle v1=\"{}\" v2=\"{}\" v3=\"{}\" pid=\"1\" p1=\"{}\" />\n",
+            triangle[0],
+            triangle[1],
+            triangle[2],
+            colors.index_of(color.color),
+        ));
+    }
+    model.push_str(
+        "</triangles>\n\
+        </mesh>\n\
+        </object>\n\
+        </resources>\n\
+        <build>\n\
+        <item objectid=\"2\" />\n\
+        </build>\n\
+        </model>\n",
+    );
 
-    let mesh = threemf::Mesh {
-        vertices: threemf::model::Vertices { vertex: vertices },
-        triangles: threemf::model::Triangles {
-            triangle: triangles,
-        },
-    };
+    let mut archive = zip::ZipWriter::new(write);
+    let options = zip::write::FileOptions::default();
+
+    archive.start_file("[Content_Types].xml", options)?;
+    archive.write_all(
+        b"<?xml version=\"1.0\" encoding=\"utf-8\"?>\n\
+        <Types xmlns=\"http://schemas.openxmlformats.org/package/2006/content-types\">\n\
+        <Default Extension=\"model\" \
+        ContentType=\"application/vnd.ms-package.3dmanufacturing-3dmodel+xml\" />\n\
+        <Default Extension=\"rels\" \
+        ContentType=\"application/vnd.openxmlformats-package.relationships+xml\" />\n\
+        </Types>\n",
+    )?;
+
+    archive.start_file("_rels/.rels", options)?;
+    archive.write_all(
+        b"<?xml version=\"1.0\" encoding=\"utf-8\"?>\n\
+        <Relationships xmlns=\"http://schemas.openxmlformats.org/package/2006/relationships\">\n\
+        <Relationship \
+        Type=\"http://schemas.microsoft.com/3dmanufacturing/2013/01/3dmodel\" \
+        Target=\"/3D/model.model\" Id=\"rel0\" />\n\
+        </Relationships>\n",
+    )?;
 
-    threemf::write(write, mesh)?;
+    archive.start_file("3D/model.model", options)?;
+    archive.write_all(model.as_bytes())?;
+
+    archive.finish()?;
 
     Ok(())
 }
 
 /// Export the provided mesh to the provided writer in the STL format.
+///
+/// Writes binary STL with default options. Use [`export_stl_with_options`] to
+/// write ASCII STL, or to configure units and names.
 pub fn export_stl(
+    mesh: &Mesh<Point<3>>,
+    write: impl Write,
+) -> Result<(), Error> {
+    export_stl_with_options(mesh, write, &StlOptions::default())
+}
+
+/// Export the provided mesh to the provided writer in the STL format
+///
+/// Like [`export_stl`], but uses the provided options.
+pub fn export_stl_with_options(
     mesh: &Mesh<Point<3>>,
     mut write: impl Write,
+    options: &StlOptions,
 ) -> Result<(), Error> {
-    let points = mesh
+    let scale = options.units.per_millimeter();
+
+    let triangles = mesh
         .triangles()
-        .map(|triangle| triangle.inner.points())
-        .collect::<Vec<_>>();
+        .map(|triangle| {
+            let normal =
+                triangle.inner.normal().components.map(|s| s.into_f64());
+            let points = triangle.inner.points().map(|point| {
+                point.coords.components.map(|s| s.into_f64() * scale)
+            });
 
-    let vertices = points.iter().map(|points| {
-        points.map(|point| point.coords.components.map(|s| s.into_f32()))
-    });
-
-    let normals = points
-        .iter()
-        .map(|&points| points.into())
-        .map(|triangle: Triangle<3>| triangle.normal())
-        .map(|vector| vector.components.map(|s| s.into_f32()));
-
-    let triangles = vertices
-        .zip(normals)
-        .map(|([v1, v2, v3], normal)| stl::Triangle {
-            normal,
-            v1,
-            v2,
-            v3,
-            attr_byte_count: 0,
+            (normal, points)
         })
         .collect::<Vec<_>>();
 
-    let binary_stl_file = stl::BinaryStlFile {
-        header: stl::BinaryStlHeader {
-            header: [0u8; 80],
-            num_triangles: triangles
-                .len()
-                .try_into()
-                .map_err(|_| Error::InvalidTriangleCount)?,
-        },
-        triangles,
-    };
+    match options.format {
+        StlFormat::Binary => {
+            let triangles = triangles
+                .into_iter()
+                .map(|(normal, [v1, v2, v3])| stl::Triangle {
+                    normal: normal.map(|c| c as f32),
+                    v1: v1.map(|c| c as f32),
+                    v2: v2.map(|c| c as f32),
+                    v3: v3.map(|c| c as f32),
+                    attr_byte_count: 0,
+                })
+                .collect::<Vec<_>>();
 
-    stl::write_stl(&mut write, &binary_stl_file)?;
+            let mut header = [0u8; 80];
+            if let Some(text) = options.header_text() {
+                // The header is fixed-size, so longer text is truncated.
+                let text = text.as_bytes();
+                let len = text.len().min(header.len());
+                header[..len].copy_from_slice(&text[..len]);
+            }
+
+            let binary_stl_file = stl::BinaryStlFile {
+                header: stl::BinaryStlHeader {
+                    header,
+                    num_triangles: triangles
+                        .len()
+                        .try_into()
+                        .map_err(|_| Error::InvalidTriangleCount)?,
+                },
+                triangles,
+            };
+
+            stl::write_stl(&mut write, &binary_stl_file)?;
+        }
+        StlFormat::Ascii => {
+            let name = options.name.as_deref().unwrap_or_default();
+
+            writeln!(write, "solid {name}")?;
+            for (normal, points) in triangles {
+                let [x, y, z] = normal;
+                writeln!(write, "  facet normal {x:e} {y:e} {z:e}")?;
+                writeln!(write, "    outer loop")?;
+                for [x, y, z] in points {
+                    writeln!(write, "      vertex {x:e} {y:e} {z:e}")?;
+                }
+                writeln!(write, "    endloop")?;
+                writeln!(write, "  endfacet")?;
+            }
+            writeln!(write, "endsolid {name}")?;
+        }
+    }
 
     Ok(())
 }
 
 /// Export the provided mesh to the provided writer in the OBJ format.
+///
+/// Writes smooth normals and doesn't reference an MTL file. Use
+/// [`export_obj_with_options`] to change that.
 pub fn export_obj(
+    mesh: &Mesh<Point<3>>,
+    write: impl Write,
+) -> Result<(), Error> {
+    export_obj_with_options(mesh, write, &ObjOptions::default())
+}
+
+/// Export the provided mesh to the provided writer in the OBJ format
+///
+/// Like [`export_obj`], but uses the provided options.
+///
+/// Vertices that are shared between triangles are written only once. Each
+/// triangle uses the material of its color, as written by [`export_mtl`].
+pub fn export_obj_with_options(
     mesh: &Mesh<Point<3>>,
     mut write: impl Write,
+    options: &ObjOptions,
 ) -> Result<(), Error> {
-    for (cnt, t) in mesh.triangles().enumerate() {
-        // write each point of the triangle
-        for v in t.inner.points() {
-            wavefront_rs::obj::writer::Writer { auto_newline: true }
-                .write(
-                    &mut write,
-                    &wavefront_rs::obj::entity::Entity::Vertex {
-                        x: v.x.into_f64(),
-                        y: v.y.into_f64(),
-                        z: v.z.into_f64(),
-                        w: None,
+    let writer = wavefront_rs::obj::writer::Writer { auto_newline: true };
+    let mut write_entity = |entity: wavefront_rs::obj::entity::Entity| {
+        writer.write(&mut write, &entity).or(Err(Error::OBJ))
+    };
+
+    if let Some(name) = &options.mtllib {
+        write_entity(wavefront_rs::obj::entity::Entity::MtlLib {
+            name: name.clone(),
+        })?;
+    }
+
+    for v in mesh.vertices() {
+        write_entity(wavefront_rs::obj::entity::Entity::Vertex {
+            x: v.x.into_f64(),
+            y: v.y.into_f64(),
+            z: v.z.into_f64(),
+            w: None,
+        })?;
+    }
+
+    // Many triangles share their normals, so we only write each distinct
+    // normal once.
+    let mut normal_indices = BTreeMap::new();
+    let normals = mesh
+        .triangles()
+        .map(|triangle| {
+            let normals = match options.normals {
+                ObjNormals::None => return Ok(None),
+                ObjNormals::Face => [triangle.inner.normal(); 3],
+                ObjNormals::Smooth => triangle.normals,
+            };
+
+            let indices = normals.try_map_ext(|normal| {
+                if let Some(&index) = normal_indices.get(&normal) {
+                    return Ok(index);
+                }
+
+                write_entity(
+                    wavefront_rs::obj::entity::Entity::VertexNormal {
+                        x: normal.x.into_f64(),
+                        y: normal.y.into_f64(),
+                        z: normal.z.into_f64(),
                     },
-                )
-                .or(Err(Error::OBJ))?;
-        }
-
-        // write the triangle
-        wavefront_rs::obj::writer::Writer { auto_newline: true }
-            .write(
-                &mut write,
-                &wavefront_rs::obj::entity::Entity::Face {
-                    vertices: vec![
-                        wavefront_rs::obj::entity::FaceVertex {
-                            vertex: (cnt * 3 + 1) as i64,
-                            texture: None,
-                            normal: None,
-                        },
-                        wavefront_rs::obj::entity::FaceVertex {
-                            vertex: (cnt * 3 + 2) as i64,
-                            texture: None,
-                            normal: None,
-                        },
-                        wavefront_rs::obj::entity::FaceVertex {
-                            vertex: (cnt * 3 + 3) as i64,
-                            texture: None,
-                            normal: None,
-                        },
-                    ],
-                },
-            )
-            .or(Err(Error::OBJ))?;
+                )?;
+
+                let index = normal_indices.len() as i64 + 1;
+                normal_indices.insert(normal, index);
+
+                Ok::<_, Error>(index)
+            })?;
+
+            Ok(Some(indices))
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    let indices = mesh.indices().collect::<Vec<_>>();
+    let mut current_color = None;
+
+    for ((triangle, indices), normals) in
+        mesh.triangles().zip(indices.chunks(3)).zip(normals)
+    {
+        // switch materials, if the color of this triangle is a new one
+        if current_color != Some(triangle.color) {
+            write_entity(wavefront_rs::obj::entity::Entity::UseMtl {
+                name: material_name(triangle.color),
+            })?;
+            current_color = Some(triangle.color);
+        }
+
+        // write the triangle; OBJ indices start at 1
+        let vertices = (0..3)
+            .map(|i| wavefront_rs::obj::entity::FaceVertex {
+                vertex: i64::from(indices[i]) + 1,
+                texture: None,
+                normal: normals.map(|normals| normals[i]),
+            })
+            .collect();
+        write_entity(wavefront_rs::obj::entity::Entity::Face { vertices })?;
     }
 
     Ok(())
 }
 
+/// Export the colors of the provided mesh as materials in the MTL format
+///
+/// The materials are named after their color, and referenced by the OBJ files
+/// written by [`export_obj`].
+pub fn export_mtl(
+    mesh: &Mesh<Point<3>>,
+    mut write: impl Write,
+) -> Result<(), Error> {
+    let colors = Colors::new(mesh);
+
+    // `wavefront_rs` writes the MTL keywords in lowercase, which not all
+    // readers accept. So we write the few statements we need ourselves.
+    for &color in colors.iter() {
+        let Color([r, g, b, a]) = color;
+
+        writeln!(write, "newmtl {}", material_name(color))?;
+        writeln!(
+            write,
+            "Kd {} {} {}",
+            f64::from(r) / 255.,
+            f64::from(g) / 255.,
+            f64::from(b) / 255.,
+        )?;
+        writeln!(write, "d {}", f64::from(a) / 255.)?;
+    }
+
+    Ok(())
+}
+
+fn material_name(Color([r, g, b, a]): Color) -> String {
+    format!("color_{r:02x}{g:02x}{b:02x}{a:02x}")
+}
+
+/// The distinct colors of a mesh, in order of first appearance
+struct Colors {
+    colors: Vec<Color>,
+    indices: HashMap<Color, usize>,
+}
+
+impl Colors {
+    fn new(mesh: &Mesh<Point<3>>) -> Self {
+        let mut colors = Vec::new();
+        let mut indices = HashMap::new();
+
+        for triangle in mesh.triangles() {
+            indices.entry(triangle.color).or_insert_with(|| {
+                colors.push(triangle.color);
+                colors.len() - 1
+            });
+        }
+
+        Self { colors, indices }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.colors.is_empty()
+    }
+
+    fn iter(&self) -> impl Iterator<Item = &Color> {
+        self.colors.iter()
+    }
+
+    fn index_of(&self, color: Color) -> usize {
+        *self
+            .indices
+            .get(&color)
+            .expect("Colors contain all colors of the mesh")
+    }
+}
+
+/// Options for [`export`]
+#[derive(Clone, Debug, Default)]
+pub struct ExportOptions {
+    /// Options that apply when exporting to STL
+    pub stl: StlOptions,
+
+    /// Options that apply when exporting to OBJ
+    ///
+    /// [`export`] always writes an MTL file next to the OBJ file, and
+    /// references that, regardless of [`ObjOptions::mtllib`].
+    pub obj: ObjOptions,
+}
+
+/// Options for exporting to OBJ
+#[derive(Clone, Debug, Default)]
+pub struct ObjOptions {
+    /// Which normals to write
+    pub normals: ObjNormals,
+
+    /// The name of the MTL file to reference, if any
+    pub mtllib: Option<String>,
+}
+
+/// The normals written into an OBJ file
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ObjNormals {
+    /// Don't write normals
+    ///
+    /// Results in the smallest files. Applications that display the file have
+    /// to compute normals themselves.
+    None,
+
+    /// Write the normal of each triangle
+    ///
+    /// Curved surfaces appear faceted.
+    Face,
+
+    /// Write the normals of the surfaces that the triangles approximate
+    ///
+    /// Curved surfaces appear smooth, while edges between faces stay sharp.
+    #[default]
+    Smooth,
+}
+
+impl FromStr for ObjNormals {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "none" => Ok(Self::None),
+            "face" => Ok(Self::Face),
+            "smooth" => Ok(Self::Smooth),
+            _ => Err(Error::InvalidObjNormals(s.to_string())),
+        }
+    }
+}
+
+/// Options for exporting to STL
+#[derive(Clone, Debug, Default)]
+pub struct StlOptions {
+    /// Whether to write binary or ASCII STL
+    pub format: StlFormat,
+
+    /// The unit that coordinates are written in
+    ///
+    /// STL files don't specify their unit, so the consuming application needs
+    /// to be told about it. The unit is mentioned in the header of binary
+    /// files, as a hint.
+    pub units: Units,
+
+    /// The name of the model
+    ///
+    /// Used as the solid name in ASCII files, and mentioned in the header of
+    /// binary files.
+    pub name: Option<String>,
+
+    /// Custom text for the 80-byte header of binary files
+    ///
+    /// Replaces the header that is generated from name and units. Text that
+    /// doesn't fit into the header is truncated.
+    pub header: Option<String>,
+}
+
+impl StlOptions {
+    fn header_text(&self) -> Option<String> {
+        if let Some(header) = &self.header {
+            return Some(header.clone());
+        }
+
+        // Don't start the generated header with "solid", as some readers take
+        // that as a sign of an ASCII file.
+        let units = format!("UNITS={}", self.units.abbreviation());
+        let text = match &self.name {
+            Some(name) => format!("Fornjot model {name} {units}"),
+            None => format!("Fornjot model {units}"),
+        };
+
+        Some(text)
+    }
+}
+
+/// The format of an STL file
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum StlFormat {
+    /// Binary STL
+    ///
+    /// This is the most compact format, and the one supported most widely.
+    #[default]
+    Binary,
+
+    /// ASCII STL
+    ///
+    /// Human-readable, which makes it easier to inspect and diff files.
+    Ascii,
+}
+
+/// The unit of length that coordinates are exported in
+///
+/// Fornjot models are defined in millimeters. Coordinates are converted, if a
+/// different unit is selected.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Units {
+    /// Micrometers
+    Micrometers,
+
+    /// Millimeters
+    #[default]
+    Millimeters,
+
+    /// Centimeters
+    Centimeters,
+
+    /// Meters
+    Meters,
+
+    /// Inches
+    Inches,
+}
+
+impl Units {
+    /// The number of this unit that make up a millimeter
+    pub fn per_millimeter(&self) -> f64 {
+        match self {
+            Self::Micrometers => 1000.,
+            Self::Millimeters => 1.,
+            Self::Centimeters => 0.1,
+            Self::Meters => 0.001,
+            Self::Inches => 1. / 25.4,
+        }
+    }
+
+    /// The common abbreviation of this unit
+    pub fn abbreviation(&self) -> &'static str {
+        match self {
+            Self::Micrometers => "um",
+            Self::Millimeters => "mm",
+            Self::Centimeters => "cm",
+            Self::Meters => "m",
+            Self::Inches => "in",
+        }
+    }
+}
+
+impl FromStr for Units {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let units = match s.to_ascii_lowercase().as_str() {
+            "um" | "micrometer" | "micrometers" | "micron" | "microns" => {
+                Self::Micrometers
+            }
+            "mm" | "millimeter" | "millimeters" => Self::Millimeters,
+            "cm" | "centimeter" | "centimeters" => Self::Centimeters,
+            "m" | "meter" | "meters" => Self::Meters,
+            "in" | "inch" | "inches" => Self::Inches,
+            _ => return Err(Error::InvalidUnits(s.to_string())),
+        };
+
+        Ok(units)
+    }
+}
+
 /// An error that can occur while exporting
 #[derive(Debug, Error)]
 pub enum Error {
@@ -195,15 +660,248 @@ pub enum Error {
     #[error("I/O error whilst exporting to file")]
     Io(#[from] std::io::Error),
 
+    /// Unrecognized units
+    #[error("unrecognized units `{0}`")]
+    InvalidUnits(String),
+
+    /// Unrecognized kind of OBJ normals
+    #[error("unrecognized kind of OBJ normals `{0}`")]
+    InvalidObjNormals(String),
+
     /// Maximum triangle count exceeded
     #[error("maximum triangle count exceeded")]
     InvalidTriangleCount,
 
-    /// Threemf error whilst exporting to 3MF file
-    #[error("threemf error whilst exporting to 3MF file")]
-    ThreeMF(#[from] threemf::Error),
+    /// Zip error whilst exporting to 3MF file
+    #[error("zip error whilst exporting to 3MF file")]
+    Zip(#[from] zip::result::ZipError),
 
     /// OBJ exporter error whilst exporting to OBJ file
     #[error("obj error whilst exporting to OBJ file")]
     OBJ,
+
+    /// Geometry that can't be represented in a STEP file
+    #[error("geometry not supported by STEP export: {0}")]
+    UnsupportedGeometry(&'static str),
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, Read};
+
+    use fj_interop::{Color, Mesh};
+    use fj_math::Point;
+
+    use super::{ObjNormals, ObjOptions, StlFormat, StlOptions, Units};
+
+    fn two_color_mesh() -> Mesh<Point<3>> {
+        let red = Color([255, 0, 0, 255]);
+        let blue = Color([0, 0, 255, 128]);
+
+        let mut mesh = Mesh::new();
+        mesh.push_triangle([[0., 0., 0.], [1., 0., 0.], [0., 1., 0.]], red);
+        mesh.push_triangle([[1., 0., 0.], [1., 1., 0.], [0., 1., 0.]], red);
+        mesh.push_triangle([[0., 0., 0.], [0., 1., 0.], [0., 0., 1.]], blue);
+
+        mesh
+    }
+
+    #[test]
+    fn export_3mf_with_colors() {
+        let mut buffer = Cursor::new(Vec::new());
+        super::export_3mf(&two_color_mesh(), &mut buffer).unwrap();
+
+        let mut archive = zip::ZipArchive::new(buffer).unwrap();
+        let mut model = String::new();
+        archive
+            .by_name("3D/model.model")
+            .unwrap()
+            .read_to_string(&mut model)
+            .unwrap();
+
+        assert!(model.contains("displaycolor=\"#FF0000FF\""));
+        assert!(model.contains("displaycolor=\"#0000FF80\""));
+        assert_eq!(model.matches("pid=\"1\" p1=\"0\"").count(), 2);
+        assert_eq!(model.matches("pid=\"1\" p1=\"1\"").count(), 1);
+    }
+
+    #[test]
+    fn export_3mf_conforms_to_core_specification() {
+        for (mesh, num_colors) in [(two_color_mesh(), 2), (Mesh::new(), 0)] {
+            let mut buffer = Cursor::new(Vec::new());
+            super::export_3mf(&mesh, &mut buffer).unwrap();
+
+            let mut archive = zip::ZipArchive::new(buffer).unwrap();
+            let mut model = String::new();
+            archive
+                .by_name("3D/model.model")
+                .unwrap()
+                .read_to_string(&mut model)
+                .unwrap();
+
+            check_3mf_model(&model, mesh.vertices().count(), num_colors);
+        }
+    }
+
+    /// Check the structural requirements of the 3MF core specification that
+    /// the exported model is subject to
+    fn check_3mf_model(model: &str, num_vertices: usize, num_colors: usize) {
+        const CORE: &str =
+            "http://schemas.microsoft.com/3dmanufacturing/core/2015/02";
+
+        let document = roxmltree::Document::parse(model).unwrap();
+        let root = document.root_element();
+        assert!(root.has_tag_name((CORE, "model")));
+        assert_eq!(root.attribute("unit"), Some("millimeter"));
+
+        fn children<'a, 'i>(
+            node: roxmltree::Node<'a, 'i>,
+            name: &str,
+        ) -> Vec<roxmltree::Node<'a, 'i>> {
+            node.children()
+                .filter(|child| child.has_tag_name((CORE, name)))
+                .collect()
+        }
+        fn child<'a, 'i>(
+            node: roxmltree::Node<'a, 'i>,
+            name: &str,
+        ) -> roxmltree::Node<'a, 'i> {
+            let [child] = children(node, name)[..] else {
+                panic!("Expected exactly one `{name}` element");
+            };
+            child
+        }
+
+        let resources = child(root, "resources");
+
+        // Base materials must contain at least one base, so they must be
+        // omitted, if there are no colors.
+        let basematerials = children(resources, "basematerials");
+        if num_colors == 0 {
+            assert!(basematerials.is_empty());
+        } else {
+            let [basematerials] = basematerials[..] else {
+                panic!("Expected exactly one `basematerials` element");
+            };
+            assert_eq!(children(basematerials, "base").len(), num_colors);
+        }
+
+        let object = child(resources, "object");
+        let mesh = child(object, "mesh");
+        assert_eq!(
+            children(child(mesh, "vertices"), "vertex").len(),
+            num_vertices
+        );
+        for triangle in children(child(mesh, "triangles"), "triangle") {
+            let attribute = |name| {
+                triangle.attribute(name).unwrap().parse::<usize>().unwrap()
+            };
+
+            for name in ["v1", "v2", "v3"] {
+                assert!(attribute(name) < num_vertices);
+            }
+            assert!(attribute("p1") < num_colors);
+        }
+
+        let item = child(child(root, "build"), "item");
+        assert_eq!(item.attribute("objectid"), object.attribute("id"));
+    }
+
+    #[test]
+    fn export_ascii_stl() {
+        let options = StlOptions {
+            format: StlFormat::Ascii,
+            name: Some("triangles".into()),
+            ..StlOptions::default()
+        };
+
+        let mut stl = Vec::new();
+        super::export_stl_with_options(&two_color_mesh(), &mut stl, &options)
+            .unwrap();
+        let stl = String::from_utf8(stl).unwrap();
+
+        assert!(stl.starts_with("solid triangles\n"));
+        assert!(stl.ends_with("endsolid triangles\n"));
+        assert_eq!(stl.matches("facet normal").count(), 3);
+        assert!(stl.contains(
+            "  facet normal 0e0 0e0 1e0\n\
+            \x20   outer loop\n\
+            \x20     vertex 0e0 0e0 0e0\n\
+            \x20     vertex 1e0 0e0 0e0\n\
+            \x20     vertex 0e0 1e0 0e0\n\
+            \x20   endloop\n\
+            \x20 endfacet\n"
+        ));
+    }
+
+    #[test]
+    fn export_binary_stl_with_units_and_name() {
+        let options = StlOptions {
+            units: Units::Centimeters,
+            name: Some("triangles".into()),
+            ..StlOptions::default()
+        };
+
+        let mut stl = Vec::new();
+        super::export_stl_with_options(&two_color_mesh(), &mut stl, &options)
+            .unwrap();
+
+        let header = String::from_utf8_lossy(&stl[..80]);
+        assert!(header.starts_with("Fornjot model triangles UNITS=cm\0"));
+
+        let file = stl::read_stl(&mut &stl[..]).unwrap();
+        assert_eq!(file.triangles[0].v2, [0.1, 0., 0.]);
+    }
+
+    #[test]
+    fn export_obj_and_mtl_with_colors() {
+        let mesh = two_color_mesh();
+
+        let options = ObjOptions {
+            mtllib: Some("model.mtl".into()),
+            ..ObjOptions::default()
+        };
+
+        let mut obj = Vec::new();
+        super::export_obj_with_options(&mesh, &mut obj, &options).unwrap();
+        let obj = String::from_utf8(obj).unwrap();
+
+        let mut mtl = Vec::new();
+        super::export_mtl(&mesh, &mut mtl).unwrap();
+        let mtl = String::from_utf8(mtl).unwrap();
+
+        assert!(obj.starts_with("mtllib model.mtl"));
+        assert_eq!(obj.matches("usemtl color_ff0000ff").count(), 1);
+        assert_eq!(obj.matches("usemtl color_0000ff80").count(), 1);
+
+        assert!(mtl.contains("newmtl color_ff0000ff\nKd 1 0 0\nd 1\n"));
+        assert!(mtl.contains("newmtl color_0000ff80\nKd 0 0 1\n"));
+    }
+
+    #[test]
+    fn export_indexed_obj() {
+        let mesh = two_color_mesh();
+
+        let export = |normals| {
+            let options = ObjOptions {
+                normals,
+                ..ObjOptions::default()
+            };
+
+            let mut obj = Vec::new();
+            super::export_obj_with_options(&mesh, &mut obj, &options).unwrap();
+            String::from_utf8(obj).unwrap()
+        };
+
+        let obj = export(ObjNormals::Smooth);
+        assert_eq!(obj.lines().filter(|l| l.starts_with("v ")).count(), 5);
+        assert_eq!(obj.lines().filter(|l| l.starts_with("vn ")).count(), 2);
+        assert!(obj.contains("f 1//1 2//1 3//1\n"));
+        assert!(obj.contains("f 2//1 4//1 3//1\n"));
+        assert!(obj.contains("f 1//2 3//2 5//2\n"));
+
+        let obj = export(ObjNormals::None);
+        assert!(!obj.contains("vn "));
+        assert!(obj.contains("f 2 4 3\n"));
+    }
 }
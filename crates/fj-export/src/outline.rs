@@ -0,0 +1,160 @@
+//! # 2D outlines
+//!
+//! Converts the cycles of regions into the segments that 2D formats (SVG, DXF)
+//! are made of. Lines and circles are converted exactly, into lines and arcs.
+//! Splines have no equivalent in all of the formats, and are approximated by
+//! lines.
+
+use std::f64::consts::{FRAC_PI_2, TAU};
+
+use fj_core::{
+    geometry::{Geometry, SurfacePath},
+    topology::{Cycle, Region},
+};
+use fj_math::{Aabb, Point, Scalar};
+
+/// The number of lines that approximate a spline
+const SPLINE_SEGMENTS: usize = 64;
+
+/// A segment of an outline, in surface coordinates
+pub(crate) enum Segment {
+    Line {
+        start: Point<2>,
+        end: Point<2>,
+    },
+    Arc {
+        center: Point<2>,
+        radius: Scalar,
+        start: Point<2>,
+        end: Point<2>,
+
+        /// The angle that the arc sweeps; positive, if counter-clockwise
+        sweep: Scalar,
+    },
+}
+
+impl Segment {
+    pub(crate) fn start(&self) -> Point<2> {
+        match self {
+            Self::Line { start, .. } | Self::Arc { start, .. } => *start,
+        }
+    }
+
+    /// Indicate whether this segment is an arc that forms a full circle
+    pub(crate) fn is_full_circle(&self) -> bool {
+        match self {
+            Self::Line { .. } => false,
+            Self::Arc { sweep, .. } => {
+                // Full circles are built with a boundary of exactly `TAU`. A
+                // small tolerance makes up for any transformation since.
+                sweep.abs().into_f64() >= TAU - 1e-9
+            }
+        }
+    }
+
+    /// Compute the axis-aligned bounding box of the segment
+    pub(crate) fn aabb(&self) -> Aabb<2> {
+        match self {
+            Self::Line { start, end } => Aabb::<2>::from_points([*start, *end]),
+            Self::Arc {
+                center,
+                radius,
+                start,
+                end,
+                sweep,
+            } => {
+                // Besides its end points, the arc reaches as far as each point
+                // where it crosses one of the axes through its center.
+                let start_angle = angle(*center, *start).into_f64();
+                let sweep = sweep.into_f64();
+                let extremes = (0..4).filter_map(|i| {
+                    let axis_angle = FRAC_PI_2 * f64::from(i);
+
+                    let offset = (axis_angle - start_angle) * sweep.signum();
+                    (offset.rem_euclid(TAU) <= sweep.abs()).then(|| {
+                        let (sin, cos) = axis_angle.sin_cos();
+                        *center + [*radius * cos, *radius * sin]
+                    })
+                });
+
+                Aabb::<2>::from_points(
+                    [*start, *end].into_iter().chain(extremes),
+                )
+            }
+        }
+    }
+}
+
+/// Convert the cycles of the provided region into outlines
+///
+/// Returns one outline per cycle, each a closed sequence of segments. The
+/// outline of the exterior cycle comes first.
+pub(crate) fn outlines(
+    region: &Region,
+    geometry: &Geometry,
+) -> Vec<Vec<Segment>> {
+    region
+        .all_cycles()
+        .map(|cycle| outline(cycle, geometry))
+        .collect()
+}
+
+fn outline(cycle: &Cycle, geometry: &Geometry) -> Vec<Segment> {
+    let mut segments = Vec::new();
+
+    for half_edge in cycle.half_edges() {
+        let half_edge_geom = geometry.of_half_edge(half_edge);
+        let [a, b] = half_edge_geom.boundary.inner;
+
+        let path = &half_edge_geom.path;
+        let start = path.point_from_path_coords(a);
+        let end = path.point_from_path_coords(b);
+
+        match path {
+            SurfacePath::Line(_) => {
+                segments.push(Segment::Line { start, end });
+            }
+            SurfacePath::Circle(circle) => {
+                // The circle's coordinates are angles. Whether increasing
+                // angles go counter-clockwise depends on its `a` and `b`.
+                let [au, av] = circle.a().components;
+                let [bu, bv] = circle.b().components;
+                let is_ccw = au * bv - av * bu > Scalar::ZERO;
+
+                let sweep = b.t - a.t;
+                let sweep = if is_ccw { sweep } else { -sweep };
+
+                segments.push(Segment::Arc {
+                    center: circle.center(),
+                    radius: circle.radius(),
+                    start,
+                    end,
+                    sweep,
+                });
+            }
+            SurfacePath::Spline(_) => {
+                let mut previous = start;
+
+                for i in 1..=SPLINE_SEGMENTS {
+                    let t =
+                        a.t + (b.t - a.t) * (i as f64 / SPLINE_SEGMENTS as f64);
+                    let point = path.point_from_path_coords([t]);
+
+                    segments.push(Segment::Line {
+                        start: previous,
+                        end: point,
+                    });
+                    previous = point;
+                }
+            }
+        }
+    }
+
+    segments
+}
+
+/// The angle of the vector from `center` to `point`, in radians
+pub(crate) fn angle(center: Point<2>, point: Point<2>) -> Scalar {
+    let [u, v] = (point - center).components;
+    v.atan2(u)
+}
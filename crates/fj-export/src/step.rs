@@ -0,0 +1,794 @@
+//! # STEP export
+//!
+//! Exports the b-rep of a model to a STEP file ([ISO 10303-21]), using the
+//! AP214 application protocol ("automotive design"). In contrast to the mesh
+//! formats, this preserves the exact geometry of the model.
+//!
+//! Fornjot models don't have units. The exported file declares millimeters.
+//!
+//! [ISO 10303-21]: https://en.wikipedia.org/wiki/ISO_10303-21
+
+use std::{collections::BTreeMap, io::Write};
+
+use fj_core::{
+    geometry::{
+        CurveBoundary, Geometry, GlobalPath, RevolvedCurve, SurfaceGeom,
+        SurfacePath, SweptCurve,
+    },
+    storage::{Handle, ObjectId},
+    topology::{Cycle, Face, HalfEdge, Shell, Solid},
+};
+use fj_math::{Circle, Line, Point, Scalar, Spline, Transform, Vector};
+
+use crate::Error;
+
+/// Export an object's b-rep in the STEP format
+///
+/// Implemented for [`Solid`] and [`Shell`]. Each shell is exported as a
+/// separate manifold solid.
+pub trait ExportStep {
+    /// Export the object to the provided writer in the STEP format
+    fn export_step(
+        self,
+        geometry: &Geometry,
+        write: impl Write,
+    ) -> Result<(), Error>;
+}
+
+impl ExportStep for &Solid {
+    fn export_step(
+        self,
+        geometry: &Geometry,
+        write: impl Write,
+    ) -> Result<(), Error> {
+        export_shells(
+            self.shells().iter().map(|shell| &**shell),
+            geometry,
+            write,
+        )
+    }
+}
+
+impl ExportStep for &Shell {
+    fn export_step(
+        self,
+        geometry: &Geometry,
+        write: impl Write,
+    ) -> Result<(), Error> {
+        export_shells([self], geometry, write)
+    }
+}
+
+fn export_shells<'r>(
+    shells: impl IntoIterator<Item = &'r Shell>,
+    geometry: &Geometry,
+    mut write: impl Write,
+) -> Result<(), Error> {
+    let mut step = StepWriter::new(geometry);
+
+    let context = step.context();
+    let breps = shells
+        .into_iter()
+        .map(|shell| {
+            let shell = step.shell(shell)?;
+            Ok(step.add(format!("MANIFOLD_SOLID_BREP('',#{shell})")))
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+    step.product(&breps, context);
+
+    step.write(&mut write)
+}
+
+/// Accumulates the entities of a STEP file
+struct StepWriter<'r> {
+    geometry: &'r Geometry,
+    entities: Vec<String>,
+    vertices: BTreeMap<ObjectId, usize>,
+    edges: BTreeMap<EdgeKey, usize>,
+}
+
+/// Identifies an edge, as seen from one of its half-edges
+///
+/// Sibling half-edges refer to the same curve and boundary, but in opposite
+/// directions. This mirrors how `fj-core` identifies siblings.
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd)]
+struct EdgeKey {
+    curve: ObjectId,
+    vertices: [ObjectId; 2],
+    boundary: CurveBoundary<Point<1>>,
+}
+
+impl EdgeKey {
+    fn reverse(self) -> Self {
+        let [a, b] = self.vertices;
+
+        Self {
+            curve: self.curve,
+            vertices: [b, a],
+            boundary: self.boundary.reverse(),
+        }
+    }
+}
+
+impl<'r> StepWriter<'r> {
+    fn new(geometry: &'r Geometry) -> Self {
+        Self {
+            geometry,
+            entities: Vec::new(),
+            vertices: BTreeMap::new(),
+            edges: BTreeMap::new(),
+        }
+    }
+
+    /// Add an entity and return its ID
+    fn add(&mut self, entity: String) -> usize {
+        self.entities.push(entity);
+        self.entities.len()
+    }
+
+    fn write(&self, mut write: impl Write) -> Result<(), Error> {
+        writeln!(write, "ISO-10303-21;")?;
+        writeln!(write, "HEADER;")?;
+        writeln!(write, "FILE_DESCRIPTION(('Fornjot model'),'2;1');")?;
+        writeln!(
+            write,
+            "FILE_NAME('','',(''),(''),'fj-export','Fornjot','');"
+        )?;
+        writeln!(
+            write,
+            "FILE_SCHEMA(('AUTOMOTIVE_DESIGN {{ 1 0 10303 214 1 1 1 1 }}'));"
+        )?;
+        writeln!(write, "ENDSEC;")?;
+
+        writeln!(write, "DATA;")?;
+        for (i, entity) in self.entities.iter().enumerate() {
+            writeln!(write, "#{}={entity};", i + 1)?;
+        }
+        writeln!(write, "ENDSEC;")?;
+        writeln!(write, "END-ISO-10303-21;")?;
+
+        Ok(())
+    }
+
+    /// Add the representation context, including units
+    fn context(&mut self) -> usize {
+        let length = self.add(
+            "(LENGTH_UNIT()NAMED_UNIT(*)SI_UNIT(.MILLI.,.METRE.))".to_string(),
+        );
+        let angle = self.add(
+            "(NAMED_UNIT(*)PLANE_ANGLE_UNIT()SI_UNIT($,.RADIAN.))".to_string(),
+        );
+        let solid_angle = self.add(
+            "(NAMED_UNIT(*)SI_UNIT($,.STERADIAN.)SOLID_ANGLE_UNIT())"
+                .to_string(),
+        );
+        let uncertainty = self.add(format!(
+            "UNCERTAINTY_MEASURE_WITH_UNIT(LENGTH_MEASURE(1.E-07),#{length},\
+            'distance_accuracy_value','confusion accuracy')"
+        ));
+
+        self.add(format!(
+            "(GEOMETRIC_REPRESENTATION_CONTEXT(3)\
+            GLOBAL_UNCERTAINTY_ASSIGNED_CONTEXT((#{uncertainty}))\
+            GLOBAL_UNIT_ASSIGNED_CONTEXT((#{length},#{angle},#{solid_angle}))\
+            REPRESENTATION_CONTEXT('',''))"
+        ))
+    }
+
+    /// Add the product structure that refers to the shape
+    ///
+    /// This is not strictly required to describe the shape, but CAD
+    /// applications expect it.
+    fn product(&mut self, breps: &[usize], context: usize) {
+        let application = self.add(
+            "APPLICATION_CONTEXT('core data for automotive mechanical design \
+            processes')"
+                .to_string(),
+        );
+        self.add(format!(
+            "APPLICATION_PROTOCOL_DEFINITION('international standard',\
+            'automotive_design',2000,#{application})"
+        ));
+        let product_context = self
+            .add(format!("PRODUCT_CONTEXT('',#{application},'mechanical')"));
+        let product = self.add(format!(
+            "PRODUCT('fornjot','fornjot','',(#{product_context}))"
+        ));
+        self.add(format!(
+            "PRODUCT_RELATED_PRODUCT_CATEGORY('part',$,(#{product}))"
+        ));
+        let definition_context = self.add(format!(
+            "PRODUCT_DEFINITION_CONTEXT('part definition',#{application},\
+            'design')"
+        ));
+        let formation =
+            self.add(format!("PRODUCT_DEFINITION_FORMATION('','',#{product})"));
+        let definition = self.add(format!(
+            "PRODUCT_DEFINITION('design','',#{formation},\
+            #{definition_context})"
+        ));
+        let shape =
+            self.add(format!("PRODUCT_DEFINITION_SHAPE('','',#{definition})"));
+
+        let origin =
+            self.placement(Point::origin(), Vector::unit_z(), Vector::unit_x());
+        let items = breps
+            .iter()
+            .chain([&origin])
+            .map(|id| format!("#{id}"))
+            .collect::<Vec<_>>()
+            .join(",");
+        let representation = self.add(format!(
+            "ADVANCED_BREP_SHAPE_REPRESENTATION('',({items}),#{context})"
+        ));
+        self.add(format!(
+            "SHAPE_DEFINITION_REPRESENTATION(#{shape},#{representation})"
+        ));
+    }
+
+    fn shell(&mut self, shell: &Shell) -> Result<usize, Error> {
+        let faces = shell
+            .faces()
+            .iter()
+            .map(|face| self.face(face))
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        Ok(self.add(format!("CLOSED_SHELL('',({}))", list(&faces))))
+    }
+
+    fn face(&mut self, face: &Face) -> Result<usize, Error> {
+        let surface_geom = self.geometry.of_surface(face.surface());
+        let (surface, is_flipped) = self.surface(surface_geom)?;
+
+        let mut bounds = Vec::new();
+        for (i, cycle) in face.region().all_cycles().enumerate() {
+            let edge_loop = self.edge_loop(cycle, surface_geom)?;
+
+            let bound = if i == 0 {
+                "FACE_OUTER_BOUND"
+            } else {
+                "FACE_BOUND"
+            };
+            bounds.push(self.add(format!("{bound}('',#{edge_loop},.T.)")));
+        }
+
+        // The front side of a face is where its exterior cycle is wound
+        // counter-clockwise. For a right-handed surface coordinate system, that
+        // is the side the surface normal points to.
+        let is_front_along_normal =
+            face.region().exterior().winding(self.geometry).is_ccw();
+        let same_sense = is_front_along_normal != is_flipped;
+
+        Ok(self.add(format!(
+            "ADVANCED_FACE('',({}),#{surface},{})",
+            list(&bounds),
+            logical(same_sense),
+        )))
+    }
+
+    fn edge_loop(
+        &mut self,
+        cycle: &Cycle,
+        surface: &SurfaceGeom,
+    ) -> Result<usize, Error> {
+        let edges = cycle
+            .half_edges()
+            .pairs()
+            .map(|(half_edge, next)| self.edge(half_edge, next, surface))
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        Ok(self.add(format!("EDGE_LOOP('',({}))", list(&edges))))
+    }
+
+    /// Add an oriented edge for the provided half-edge
+    ///
+    /// The edge itself is shared with the sibling of the half-edge.
+    fn edge(
+        &mut self,
+        half_edge: &Handle<HalfEdge>,
+        next: &Handle<HalfEdge>,
+        surface: &SurfaceGeom,
+    ) -> Result<usize, Error> {
+        let half_edge_geom = self.geometry.of_half_edge(half_edge);
+
+        let key = EdgeKey {
+            curve: half_edge.curve().id(),
+            vertices: [half_edge.start_vertex().id(), next.start_vertex().id()],
+            boundary: half_edge_geom.boundary,
+        };
+
+        let (edge, orientation) = if let Some(&edge) = self.edges.get(&key) {
+            (edge, true)
+        } else if let Some(&edge) = self.edges.get(&key.reverse()) {
+            (edge, false)
+        } else {
+            let (path, boundary) = global_path(
+                &half_edge_geom.path,
+                half_edge_geom.boundary,
+                surface,
+            )?;
+            let [start, end] = [0, 1].map(|i| {
+                let position = path.point_from_path_coords(boundary.inner[i]);
+                self.vertex(key.vertices[i], position)
+            });
+
+            let curve = self.curve(&path);
+            let [a, b] = boundary.inner;
+            let edge = self.add(format!(
+                "EDGE_CURVE('',#{start},#{end},#{curve},{})",
+                logical(a < b)
+            ));
+
+            self.edges.insert(key, edge);
+            (edge, true)
+        };
+
+        Ok(self.add(format!(
+            "ORIENTED_EDGE('',*,*,#{edge},{})",
+            logical(orientation)
+        )))
+    }
+
+    fn vertex(&mut self, vertex: ObjectId, position: Point<3>) -> usize {
+        if let Some(&id) = self.vertices.get(&vertex) {
+            return id;
+        }
+
+        let point = self.point(position);
+        let id = self.add(format!("VERTEX_POINT('',#{point})"));
+        self.vertices.insert(vertex, id);
+        id
+    }
+
+    /// Add a surface
+    ///
+    /// Also returns whether the normal of the STEP surface points in the
+    /// opposite direction of the normal of the Fornjot surface.
+    fn surface(
+        &mut self,
+        surface: &SurfaceGeom,
+    ) -> Result<(usize, bool), Error> {
+        match surface {
+            SurfaceGeom::Swept(SweptCurve {
+                u: GlobalPath::Line(line),
+                v,
+            }) => {
+                let normal = line.direction().cross(v);
+                let placement =
+                    self.placement(line.origin(), normal, line.direction());
+
+                Ok((self.add(format!("PLANE('',#{placement})")), false))
+            }
+            SurfaceGeom::Swept(SweptCurve {
+                u: GlobalPath::Circle(circle),
+                v,
+            }) if is_cylinder(circle, v) => {
+                let placement = self.placement(circle.center(), *v, circle.a());
+                let surface = self.add(format!(
+                    "CYLINDRICAL_SURFACE('',#{placement},{})",
+                    real(circle.radius())
+                ));
+
+                // The normal of a cylindrical surface points away from its
+                // axis. Fornjot's surface normal does that too, unless the
+                // circle is wound clockwise around the v-axis.
+                let axis = circle.a().cross(&circle.b());
+                Ok((surface, axis.dot(v) < Scalar::ZERO))
+            }
+            SurfaceGeom::Swept(SweptCurve { u, v }) => {
+                // All other swept surfaces are the result of a linear
+                // extrusion, and can be represented exactly like that. The
+                // normal is defined the same way as Fornjot's.
+                let curve = self.curve(u);
+                let vector = self.vector(*v);
+
+                Ok((
+                    self.add(format!(
+                        "SURFACE_OF_LINEAR_EXTRUSION('',#{curve},#{vector})"
+                    )),
+                    false,
+                ))
+            }
+            SurfaceGeom::Revolved(surface) => {
+                // The parameterization of a surface of revolution in STEP
+                // matches Fornjot's, which means so does the normal.
+                let profile = map_path(&surface.profile, frame(surface, 0.));
+                let curve = self.curve(&profile);
+
+                let origin = self.point(surface.axis.origin());
+                let direction = self.direction(surface.axis.direction());
+                let axis = self
+                    .add(format!("AXIS1_PLACEMENT('',#{origin},#{direction})"));
+
+                Ok((
+                    self.add(format!(
+                        "SURFACE_OF_REVOLUTION('',#{curve},#{axis})"
+                    )),
+                    false,
+                ))
+            }
+        }
+    }
+
+    fn curve(&mut self, path: &GlobalPath) -> usize {
+        match path {
+            GlobalPath::Line(line) => {
+                let origin = self.point(line.origin());
+                let vector = self.vector(line.direction());
+
+                self.add(format!("LINE('',#{origin},#{vector})"))
+            }
+            GlobalPath::Circle(circle) => {
+                let axis = circle.a().cross(&circle.b());
+                let placement =
+                    self.placement(circle.center(), axis, circle.a());
+
+                self.add(format!(
+                    "CIRCLE('',#{placement},{})",
+                    real(circle.radius())
+                ))
+            }
+            GlobalPath::Spline(spline) => self.spline(spline),
+        }
+    }
+
+    fn spline(&mut self, spline: &Spline<3>) -> usize {
+        let points = spline
+            .control_points()
+            .iter()
+            .map(|&point| self.point(point))
+            .collect::<Vec<_>>();
+
+        // STEP stores knots without repetition, with separate multiplicities.
+        let mut knots = Vec::<Scalar>::new();
+        let mut multiplicities = Vec::<usize>::new();
+        for &knot in spline.knots() {
+            if knots.last() == Some(&knot) {
+                if let Some(multiplicity) = multiplicities.last_mut() {
+                    *multiplicity += 1;
+                }
+            } else {
+                knots.push(knot);
+                multiplicities.push(1);
+            }
+        }
+
+        let degree = spline.degree();
+        let points = list(&points);
+        let multiplicities = multiplicities
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(",");
+        let knots = reals(knots);
+
+        if spline.weights().iter().all(|&weight| weight == Scalar::ONE) {
+            self.add(format!(
+                "B_SPLINE_CURVE_WITH_KNOTS('',{degree},({points}),\
+                .UNSPECIFIED.,.F.,.F.,({multiplicities}),({knots}),\
+                .UNSPECIFIED.)"
+            ))
+        } else {
+            let weights = reals(spline.weights().iter().copied());
+
+            self.add(format!(
+                "(BOUNDED_CURVE()B_SPLINE_CURVE({degree},({points}),\
+                .UNSPECIFIED.,.F.,.F.)B_SPLINE_CURVE_WITH_KNOTS(\
+                ({multiplicities}),({knots}),.UNSPECIFIED.)CURVE()\
+                GEOMETRIC_REPRESENTATION_ITEM()\
+                RATIONAL_B_SPLINE_CURVE(({weights}))\
+                REPRESENTATION_ITEM(''))"
+            ))
+        }
+    }
+
+    fn placement(
+        &mut self,
+        origin: Point<3>,
+        axis: Vector<3>,
+        reference: Vector<3>,
+    ) -> usize {
+        let origin = self.point(origin);
+        let axis = self.direction(axis);
+        let reference = self.direction(reference);
+
+        self.add(format!(
+            "AXIS2_PLACEMENT_3D('',#{origin},#{axis},#{reference})"
+        ))
+    }
+
+    fn point(&mut self, point: Point<3>) -> usize {
+        self.add(format!(
+            "CARTESIAN_POINT('',({}))",
+            reals(point.coords.components)
+        ))
+    }
+
+    fn direction(&mut self, direction: Vector<3>) -> usize {
+        self.add(format!(
+            "DIRECTION('',({}))",
+            reals(direction.normalize().components)
+        ))
+    }
+
+    fn vector(&mut self, vector: Vector<3>) -> usize {
+        let direction = self.direction(vector);
+        self.add(format!(
+            "VECTOR('',#{direction},{})",
+            real(vector.magnitude())
+        ))
+    }
+}
+
+/// Convert the path of a half-edge into global coordinates
+///
+/// Returns the global path, as well as the boundary of the half-edge on that
+/// path. This is only possible for paths that are not curved with respect to
+/// the surface, which are all paths on a plane, and lines along the u- or
+/// v-axis of a curved surface.
+fn global_path(
+    path: &SurfacePath,
+    boundary: CurveBoundary<Point<1>>,
+    surface: &SurfaceGeom,
+) -> Result<(GlobalPath, CurveBoundary<Point<1>>), Error> {
+    if let SurfaceGeom::Swept(SweptCurve {
+        u: GlobalPath::Line(line),
+        v,
+    }) = surface
+    {
+        let frame = (line.origin(), [line.direction(), *v]);
+        return Ok((map_path(path, frame), boundary));
+    }
+
+    let SurfacePath::Line(line) = path else {
+        return Err(Error::UnsupportedGeometry(
+            "curved edge on a curved surface",
+        ));
+    };
+    let map_boundary = |f: fn(Point<2>) -> Scalar| CurveBoundary {
+        inner: boundary
+            .inner
+            .map(|point| Point::from([f(line.point_from_line_coords(point))])),
+    };
+
+    let direction = line.direction();
+    let origin = line.origin();
+
+    match surface {
+        SurfaceGeom::Swept(SweptCurve { u, v }) => {
+            if direction.u == Scalar::ZERO {
+                // A straight line along the v-axis.
+                let global = Line::from_origin_and_direction(
+                    surface.point_from_surface_coords(origin),
+                    *v * direction.v,
+                );
+                Ok((GlobalPath::Line(global), boundary))
+            } else if direction.v == Scalar::ZERO {
+                // The u-axis, moved along the v-axis.
+                let global =
+                    u.clone().transform(&Transform::translation(*v * origin.v));
+                Ok((global, map_boundary(|point| point.u)))
+            } else {
+                Err(Error::UnsupportedGeometry(
+                    "edge that is not aligned with the axes of a curved \
+                    surface",
+                ))
+            }
+        }
+        SurfaceGeom::Revolved(revolved) => {
+            if direction.u == Scalar::ZERO {
+                // The profile, rotated into place.
+                let global =
+                    map_path(&revolved.profile, frame(revolved, origin.u));
+                Ok((global, map_boundary(|point| point.v)))
+            } else if direction.v == Scalar::ZERO {
+                // A circle around the axis.
+                let profile_point =
+                    revolved.profile.point_from_path_coords([origin.v]);
+                let [a, b] = {
+                    let [a, b] = radial_and_tangential(revolved);
+                    [a * profile_point.u, b * profile_point.u]
+                };
+
+                if a.magnitude() == Scalar::ZERO {
+                    return Err(Error::UnsupportedGeometry(
+                        "degenerate edge on the axis of a surface of \
+                        revolution",
+                    ));
+                }
+
+                let center =
+                    revolved.axis.point_from_line_coords([profile_point.v]);
+                let global = GlobalPath::Circle(Circle::new(center, a, b));
+                Ok((global, map_boundary(|point| point.u)))
+            } else {
+                Err(Error::UnsupportedGeometry(
+                    "edge that is not aligned with the axes of a curved \
+                    surface",
+                ))
+            }
+        }
+    }
+}
+
+/// Map a path from a plane into global coordinates
+///
+/// The plane is defined by its origin and the vectors that correspond to its
+/// u- and v-axes. The mapping is affine, which means the parameterization of
+/// the path is preserved.
+fn map_path(
+    path: &SurfacePath,
+    (origin, [u, v]): (Point<3>, [Vector<3>; 2]),
+) -> GlobalPath {
+    let map_point = |point: Point<2>| origin + u * point.u + v * point.v;
+    let map_vector = |vector: Vector<2>| u * vector.u + v * vector.v;
+
+    match path {
+        SurfacePath::Circle(circle) => GlobalPath::Circle(Circle::new(
+            map_point(circle.center()),
+            map_vector(circle.a()),
+            map_vector(circle.b()),
+        )),
+        SurfacePath::Line(line) => {
+            GlobalPath::Line(Line::from_origin_and_direction(
+                map_point(line.origin()),
+                map_vector(line.direction()),
+            ))
+        }
+        SurfacePath::Spline(spline) => {
+            GlobalPath::Spline(spline.map_control_points(map_point))
+        }
+    }
+}
+
+/// The plane that contains the profile of a surface of revolution
+///
+/// Returns the plane at the provided angle, in the form expected by
+/// [`map_path`].
+fn frame(
+    surface: &RevolvedCurve,
+    angle: impl Into<Scalar>,
+) -> (Point<3>, [Vector<3>; 2]) {
+    let (sin, cos) = angle.into().sin_cos();
+    let [radial, tangential] = radial_and_tangential(surface);
+
+    (
+        surface.axis.origin(),
+        [radial * cos + tangential * sin, surface.axis.direction()],
+    )
+}
+
+/// The radial vector of a surface of revolution, and its rotation by 90°
+fn radial_and_tangential(surface: &RevolvedCurve) -> [Vector<3>; 2] {
+    let axis = surface.axis.direction().normalize();
+    [surface.radial, axis.cross(&surface.radial)]
+}
+
+/// Indicate whether the surface swept from the circle is a right cylinder
+fn is_cylinder(circle: &Circle<3>, v: &Vector<3>) -> bool {
+    let axis = circle.a().cross(&circle.b()).normalize();
+    let v = v.normalize();
+
+    (v.dot(&axis).abs() - Scalar::ONE).abs() < Scalar::from(1e-12)
+}
+
+fn list(ids: &[usize]) -> String {
+    ids.iter()
+        .map(|id| format!("#{id}"))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn reals(values: impl IntoIterator<Item = Scalar>) -> String {
+    values.into_iter().map(real).collect::<Vec<_>>().join(",")
+}
+
+/// Format a real number
+///
+/// STEP requires real numbers to contain a decimal point, and exponents to use
+/// an upper-case `E`.
+fn real(value: Scalar) -> String {
+    let value = format!("{:?}", value.into_f64());
+
+    match value.split_once('e') {
+        Some((mantissa, exponent)) => {
+            let mantissa = if mantissa.contains('.') {
+                mantissa.to_string()
+            } else {
+                format!("{mantissa}.")
+            };
+            format!("{mantissa}E{exponent}")
+        }
+        None => value,
+    }
+}
+
+fn logical(value: bool) -> &'static str {
+    if value {
+        ".T."
+    } else {
+        ".F."
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_core::{
+        operations::{
+            build::{BuildRegion, BuildSketch},
+            insert::Insert,
+            sweep::SweepSketch,
+            update::UpdateSketch,
+        },
+        topology::{Region, Sketch, Solid},
+        Core,
+    };
+
+    use super::ExportStep;
+
+    fn sweep(region: Region, core: &mut Core) -> Solid {
+        Sketch::empty(&core.layers.topology)
+            .add_regions([region], core)
+            .sweep_sketch(
+                core.layers.topology.surfaces.xy_plane(),
+                [0., 0., 1.],
+                core,
+            )
+    }
+
+    fn export(solid: &Solid, core: &Core) -> String {
+        let mut buffer = Vec::new();
+        solid
+            .export_step(&core.layers.geometry, &mut buffer)
+            .unwrap();
+        String::from_utf8(buffer).unwrap()
+    }
+
+    fn count(step: &str, entity: &str) -> usize {
+        step.matches(&format!("={entity}(")).count()
+    }
+
+    #[test]
+    fn cuboid() {
+        let mut core = Core::new();
+
+        let region = Region::polygon(
+            [[0., 0.], [1., 0.], [1., 1.], [0., 1.]],
+            core.layers.topology.surfaces.space_2d(),
+            &mut core,
+        );
+        let solid = sweep(region, &mut core).insert(&mut core);
+
+        let step = export(&solid, &core);
+
+        assert!(step.starts_with("ISO-10303-21;"));
+        assert!(step.trim_end().ends_with("END-ISO-10303-21;"));
+
+        assert_eq!(count(&step, "ADVANCED_FACE"), 6);
+        assert_eq!(count(&step, "PLANE"), 6);
+        assert_eq!(count(&step, "ORIENTED_EDGE"), 24);
+        assert_eq!(count(&step, "EDGE_CURVE"), 12);
+        assert_eq!(count(&step, "VERTEX_POINT"), 8);
+        assert_eq!(count(&step, "MANIFOLD_SOLID_BREP"), 1);
+    }
+
+    #[test]
+    fn cylinder() {
+        let mut core = Core::new();
+
+        let region = Region::circle(
+            [0., 0.],
+            1.,
+            core.layers.topology.surfaces.space_2d(),
+            &mut core,
+        );
+        let solid = sweep(region, &mut core).insert(&mut core);
+
+        let step = export(&solid, &core);
+
+        assert_eq!(count(&step, "ADVANCED_FACE"), 3);
+        assert_eq!(count(&step, "PLANE"), 2);
+        assert_eq!(count(&step, "CYLINDRICAL_SURFACE"), 1);
+        assert_eq!(count(&step, "CIRCLE"), 2);
+    }
+}
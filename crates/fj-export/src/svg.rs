@@ -0,0 +1,210 @@
+//! # SVG export
+//!
+//! Exports the outlines of 2D shapes to an [SVG] file, as used for laser
+//! cutting and similar processes.
+//!
+//! The outlines are written in surface coordinates, which are interpreted as
+//! millimeters. SVG's y-axis points down, so the v-coordinates are flipped, to
+//! keep the shape from appearing mirrored.
+//!
+//! [SVG]: https://www.w3.org/TR/SVG2/
+
+use std::io::Write;
+
+use fj_core::{
+    geometry::Geometry,
+    topology::{Region, Sketch},
+};
+use fj_math::{Aabb, Point, Scalar};
+
+use crate::{
+    outline::{outlines, Segment},
+    Error,
+};
+
+/// Export the outline of a 2D shape in the SVG format
+///
+/// Implemented for [`Sketch`] and [`Region`]. Each region becomes a path, made
+/// up of one closed subpath per cycle. Lines and circles are exported exactly,
+/// splines are approximated by lines.
+pub trait ExportSvg {
+    /// Export the object to the provided writer in the SVG format
+    fn export_svg(
+        self,
+        geometry: &Geometry,
+        write: impl Write,
+    ) -> Result<(), Error>;
+}
+
+impl ExportSvg for &Sketch {
+    fn export_svg(
+        self,
+        geometry: &Geometry,
+        write: impl Write,
+    ) -> Result<(), Error> {
+        export_regions(
+            self.regions().iter().map(|region| &**region),
+            geometry,
+            write,
+        )
+    }
+}
+
+impl ExportSvg for &Region {
+    fn export_svg(
+        self,
+        geometry: &Geometry,
+        write: impl Write,
+    ) -> Result<(), Error> {
+        export_regions([self], geometry, write)
+    }
+}
+
+fn export_regions<'r>(
+    regions: impl IntoIterator<Item = &'r Region>,
+    geometry: &Geometry,
+    mut write: impl Write,
+) -> Result<(), Error> {
+    let regions = regions
+        .into_iter()
+        .map(|region| outlines(region, geometry))
+        .collect::<Vec<_>>();
+
+    let aabb = regions
+        .iter()
+        .flatten()
+        .flatten()
+        .map(Segment::aabb)
+        .reduce(|a, b| a.merged(&b))
+        .unwrap_or(Aabb {
+            min: Point::origin(),
+            max: Point::origin(),
+        });
+    let [width, height] = (aabb.max - aabb.min).components;
+
+    writeln!(write, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(
+        write,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}mm" height="{height}mm" viewBox="{} {} {width} {height}">"#,
+        aabb.min.u, -aabb.max.v,
+    )?;
+
+    for outlines in regions {
+        let mut data = Vec::new();
+
+        for outline in outlines {
+            let Some(first) = outline.first() else {
+                continue;
+            };
+            data.push(format!("M {}", point(first.start())));
+
+            for segment in &outline {
+                match *segment {
+                    Segment::Line { end, .. } => {
+                        data.push(format!("L {}", point(end)));
+                    }
+                    Segment::Arc {
+                        center,
+                        radius,
+                        start,
+                        end,
+                        sweep,
+                    } => {
+                        // An SVG arc can't start and end at the same point. A
+                        // full circle needs to be split into two halves.
+                        let ends = if segment.is_full_circle() {
+                            vec![center + (center - start), end]
+                        } else {
+                            vec![end]
+                        };
+                        let sweep = sweep / Scalar::from(ends.len() as f64);
+
+                        for end in ends {
+                            data.push(arc(radius, sweep, end));
+                        }
+                    }
+                }
+            }
+
+            data.push("Z".to_string());
+        }
+
+        writeln!(
+            write,
+            r#"  <path d="{}" fill="none" stroke="black" stroke-width="0.1"/>"#,
+            data.join(" "),
+        )?;
+    }
+
+    writeln!(write, "</svg>")?;
+
+    Ok(())
+}
+
+/// Format an arc command to the provided end point
+fn arc(radius: Scalar, sweep: Scalar, end: Point<2>) -> String {
+    let large_arc = sweep.abs() > Scalar::PI;
+
+    // Positive angles go from the positive x-axis towards the positive
+    // y-axis in SVG. Since we flip the v-axis, that is the clockwise
+    // direction in surface coordinates.
+    let clockwise = sweep < Scalar::ZERO;
+
+    format!(
+        "A {radius} {radius} 0 {} {} {}",
+        u8::from(large_arc),
+        u8::from(clockwise),
+        point(end),
+    )
+}
+
+/// Format a point in surface coordinates as SVG coordinates
+fn point(point: Point<2>) -> String {
+    format!("{} {}", point.u, -point.v)
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_core::{
+        operations::{
+            build::{BuildRegion, BuildSketch},
+            update::UpdateSketch,
+        },
+        topology::{Region, Sketch},
+        Core,
+    };
+
+    use super::ExportSvg;
+
+    #[test]
+    fn export_sketch() {
+        let mut core = Core::new();
+
+        let square = Region::polygon(
+            [[0., 0.], [10., 0.], [10., 10.], [0., 10.]],
+            core.layers.topology.surfaces.space_2d(),
+            &mut core,
+        );
+        let circle = Region::circle(
+            [20., 5.],
+            5.,
+            core.layers.topology.surfaces.space_2d(),
+            &mut core,
+        );
+        let sketch = Sketch::empty(&core.layers.topology)
+            .add_regions([square, circle], &mut core);
+
+        let mut buffer = Vec::new();
+        sketch
+            .export_svg(&core.layers.geometry, &mut buffer)
+            .unwrap();
+        let svg = String::from_utf8(buffer).unwrap();
+
+        assert!(svg.contains(r#"viewBox="0 -10 25 10""#));
+        assert_eq!(svg.matches("<path").count(), 2);
+        assert_eq!(svg.matches(" L ").count(), 4);
+
+        // The circle is written as two counter-clockwise half circles.
+        assert_eq!(svg.matches("A 5 5 0 0 0").count(), 2);
+    }
+}
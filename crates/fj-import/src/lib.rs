@@ -0,0 +1,136 @@
+//! # Fornjot Importer
+//!
+//! [Fornjot] is an early-stage b-rep CAD kernel written in Rust. The kernel is
+//! split into multiple libraries that can be used semi-independently, and this
+//! is one of those.
+//!
+//! This library imports triangle meshes from external file formats. It is the
+//! counterpart to `fj-export`.
+//!
+//! [Fornjot]: https://www.fornjot.app/
+
+mod obj;
+mod stl;
+mod three_mf;
+
+pub use self::{
+    obj::{import_mtl, import_obj, mtl_libraries, Materials},
+    stl::import_stl,
+    three_mf::import_3mf,
+};
+
+use std::{
+    fs::{self, File},
+    io::BufReader,
+    path::Path,
+};
+
+use thiserror::Error;
+
+use fj_interop::{Color, Mesh};
+use fj_math::{Point, Triangle};
+
+/// Import a mesh from the file at the given path.
+///
+/// Currently 3MF, STL (binary and ASCII) & OBJ file types are supported. The
+/// case insensitive file extension of the provided path is used to switch
+/// between supported types.
+///
+/// MTL files referenced by an OBJ file are loaded from paths relative to the
+/// OBJ file.
+pub fn import(path: &Path) -> Result<Mesh<Point<3>>, Error> {
+    match path.extension() {
+        Some(extension) if extension.to_ascii_uppercase() == "3MF" => {
+            let file = File::open(path)?;
+            import_3mf(BufReader::new(file))
+        }
+        Some(extension) if extension.to_ascii_uppercase() == "STL" => {
+            let file = File::open(path)?;
+            import_stl(BufReader::new(file))
+        }
+        Some(extension) if extension.to_ascii_uppercase() == "OBJ" => {
+            let obj = fs::read_to_string(path)?;
+
+            let mut materials = Materials::new();
+            for name in mtl_libraries(obj.as_bytes())? {
+                let mtl_path = path.with_file_name(name);
+                let file = File::open(mtl_path)?;
+                materials.extend(import_mtl(BufReader::new(file))?);
+            }
+
+            import_obj(obj.as_bytes(), &materials)
+        }
+        Some(extension) => Err(Error::InvalidExtension(
+            extension.to_string_lossy().into_owned(),
+        )),
+        None => Err(Error::NoExtension),
+    }
+}
+
+/// Add a triangle to the mesh, unless it is degenerate
+///
+/// Meshes from external sources, scans in particular, regularly contain
+/// triangles that don't span any area. Those can't be represented in a
+/// [`Mesh`], and don't contribute anything to the shape anyway.
+fn push_triangle(
+    mesh: &mut Mesh<Point<3>>,
+    points: [Point<3>; 3],
+    color: Color,
+) {
+    if let Ok(triangle) = Triangle::from_points(points) {
+        mesh.push_triangle(triangle, color);
+    }
+}
+
+/// Create a point from coordinates that were read from a file
+///
+/// [`Point`] can't represent NaN, and infinite coordinates don't make sense for
+/// a mesh. Files that contain either are malformed.
+fn point(coords: [f64; 3]) -> Result<Point<3>, Error> {
+    if !coords.iter().all(|coord| coord.is_finite()) {
+        return Err(Error::Malformed(format!(
+            "non-finite coordinates {coords:?}"
+        )));
+    }
+
+    Ok(Point::from(coords))
+}
+
+/// Convert a color component in the range `0..=1` into a byte
+fn color_component(value: f64) -> u8 {
+    (value.clamp(0., 1.) * 255.).round() as u8
+}
+
+/// An error that can occur while importing
+#[derive(Debug, Error)]
+pub enum Error {
+    /// No extension specified
+    #[error("no extension specified")]
+    NoExtension,
+
+    /// Unrecognized extension found
+    #[error("unrecognized extension found `{0:?}`")]
+    InvalidExtension(String),
+
+    /// I/O error whilst importing from file
+    #[error("I/O error whilst importing from file")]
+    Io(#[from] std::io::Error),
+
+    /// Zip error whilst importing from 3MF file
+    #[error("zip error whilst importing from 3MF file")]
+    Zip(#[from] zip::result::ZipError),
+
+    /// XML error whilst importing from 3MF file
+    #[error("XML error whilst importing from 3MF file")]
+    Xml(#[from] quick_xml::Error),
+
+    /// The file doesn't follow the format it claims to have
+    #[error("malformed file: {0}")]
+    Malformed(String),
+}
+
+impl From<quick_xml::events::attributes::AttrError> for Error {
+    fn from(err: quick_xml::events::attributes::AttrError) -> Self {
+        Self::Xml(err.into())
+    }
+}
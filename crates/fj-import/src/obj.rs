@@ -0,0 +1,253 @@
+use std::{collections::BTreeMap, io::BufRead};
+
+use fj_interop::{Color, Mesh};
+use fj_math::Point;
+use wavefront_rs::{mtl, obj};
+
+use crate::{color_component, point, push_triangle, Error};
+
+/// Colors of materials, by material name
+///
+/// Returned by [`import_mtl`], used by [`import_obj`].
+pub type Materials = BTreeMap<String, Color>;
+
+/// Import a mesh from the provided reader in the OBJ format
+///
+/// Triangles get the color of the material that is in use for them, as long as
+/// that material is found in `materials`. All other triangles get the default
+/// color. Polygons with more than 3 vertices are triangulated as a fan, which
+/// is correct for the convex polygons that OBJ files usually contain.
+pub fn import_obj(
+    read: impl BufRead,
+    materials: &Materials,
+) -> Result<Mesh<Point<3>>, Error> {
+    let mut mesh = Mesh::new();
+
+    let mut vertices = Vec::new();
+    let mut color = Color::default();
+
+    for_each_entity(read, &["v", "f", "usemtl"], |entity| {
+        match entity {
+            Entity::Obj(obj::entity::Entity::Vertex { x, y, z, .. }) => {
+                let point = point([x, y, z]).map_err(|err| err.to_string())?;
+                vertices.push(point);
+            }
+            Entity::Obj(obj::entity::Entity::UseMtl { name }) => {
+                color = materials.get(name.trim()).copied().unwrap_or_default();
+            }
+            Entity::Obj(obj::entity::Entity::Face { vertices: face }) => {
+                let points = face
+                    .iter()
+                    .map(|face_vertex| {
+                        // Indices are 1-based. Negative indices are relative
+                        // to the end of the vertices defined so far.
+                        let index = if face_vertex.vertex < 0 {
+                            vertices.len() as i64 + face_vertex.vertex
+                        } else {
+                            face_vertex.vertex - 1
+                        };
+
+                        usize::try_from(index)
+                            .ok()
+                            .and_then(|index| vertices.get(index))
+                            .copied()
+                            .ok_or_else(|| {
+                                format!(
+                                    "invalid vertex index {}",
+                                    face_vertex.vertex
+                                )
+                            })
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                let Some((&first, rest)) = points.split_first() else {
+                    return Ok(());
+                };
+                for pair in rest.windows(2) {
+                    push_triangle(&mut mesh, [first, pair[0], pair[1]], color);
+                }
+            }
+            _ => {}
+        }
+
+        Ok(())
+    })?;
+
+    Ok(mesh)
+}
+
+/// Import the colors of materials from the provided reader in the MTL format
+///
+/// The diffuse color (`Kd`) of a material is used as its color, the dissolve
+/// value (`d`) as its alpha.
+pub fn import_mtl(read: impl BufRead) -> Result<Materials, Error> {
+    let mut materials = Materials::new();
+    let mut current = None;
+
+    for_each_entity(read, &["newmtl", "kd", "d"], |entity| {
+        match entity {
+            Entity::Mtl(mtl::entity::Entity::MaterialName { name }) => {
+                let name = name.trim().to_string();
+                materials.insert(name.clone(), Color([255, 255, 255, 255]));
+                current = Some(name);
+            }
+            Entity::Mtl(mtl::entity::Entity::DiffuseColor { r, g, b }) => {
+                let color = current
+                    .as_ref()
+                    .and_then(|name| materials.get_mut(name))
+                    .ok_or("`Kd` outside of material")?;
+                let [r, g, b] = [r, g, b].map(color_component);
+                color.0 = [r, g, b, color.0[3]];
+            }
+            Entity::Mtl(mtl::entity::Entity::Dissolve { value }) => {
+                let color = current
+                    .as_ref()
+                    .and_then(|name| materials.get_mut(name))
+                    .ok_or("`d` outside of material")?;
+                color.0[3] = color_component(value);
+            }
+            _ => {}
+        }
+
+        Ok(())
+    })?;
+
+    Ok(materials)
+}
+
+/// Find the names of the MTL files that the provided OBJ file references
+pub fn mtl_libraries(read: impl BufRead) -> Result<Vec<String>, Error> {
+    let mut names = Vec::new();
+
+    for_each_entity(read, &["mtllib"], |entity| {
+        if let Entity::Obj(obj::entity::Entity::MtlLib { name }) = entity {
+            names.extend(name.split_whitespace().map(str::to_string));
+        }
+
+        Ok(())
+    })?;
+
+    Ok(names)
+}
+
+enum Entity {
+    Obj(obj::entity::Entity),
+    Mtl(mtl::entity::Entity),
+}
+
+/// Parse the lines of an OBJ or MTL file that start with one of the `tokens`
+///
+/// Both formats have lots of statements that we don't care about, some of
+/// which `wavefront_rs` doesn't know, and would fail to parse. We only hand the
+/// lines we need to the parser, and ignore all others.
+fn for_each_entity(
+    read: impl BufRead,
+    tokens: &[&str],
+    mut f: impl FnMut(Entity) -> Result<(), String>,
+) -> Result<(), Error> {
+    for (i, line) in read.lines().enumerate() {
+        let line = line?;
+
+        let Some(token) = line.split_whitespace().next() else {
+            continue;
+        };
+        let token = token.to_ascii_lowercase();
+        if !tokens.contains(&token.as_str()) {
+            continue;
+        }
+
+        let malformed =
+            |err: String| Error::Malformed(format!("line {}: {err}", i + 1));
+
+        // `newmtl`, `kd` and `d` are MTL statements. All others we care
+        // about are OBJ statements.
+        let entity = if ["newmtl", "kd", "d"].contains(&token.as_str()) {
+            mtl::parser::Parser::parse_line(&mut line.as_bytes())
+                .map(Entity::Mtl)
+        } else {
+            obj::parser::Parser::parse_line(&mut line.as_bytes())
+                .map(Entity::Obj)
+        }
+        .map_err(|err| malformed(err.to_string()))?;
+
+        f(entity).map_err(malformed)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use fj_interop::{Color, Mesh};
+    use fj_math::Point;
+
+    use crate::Error;
+
+    use super::Materials;
+
+    #[test]
+    fn import_obj_with_polygons() {
+        let obj = "\
+# a square and a triangle, the latter using negative indices
+v 0 0 0
+v 1 0 0
+v 1 1 0
+v 0 1 0
+vn 0 0 1
+f 1//1 2//1 3//1 4//1
+v 0 0 1
+f -5 -1 -2
+";
+
+        let mesh =
+            super::import_obj(obj.as_bytes(), &Materials::new()).unwrap();
+
+        assert_eq!(mesh.triangles().count(), 3);
+        assert!(mesh.contains_triangle([
+            Point::from([0., 0., 0.]),
+            Point::from([0., 0., 1.]),
+            Point::from([0., 1., 0.]),
+        ]));
+    }
+
+    #[test]
+    fn import_obj_and_mtl_round_trip() {
+        let red = Color([255, 0, 0, 255]);
+        let blue = Color([0, 0, 255, 128]);
+
+        let mut mesh = Mesh::new();
+        mesh.push_triangle([[0., 0., 0.], [1., 0., 0.], [0., 1., 0.]], red);
+        mesh.push_triangle([[0., 0., 0.], [0., 1., 0.], [0., 0., 1.]], blue);
+
+        let options = fj_export::ObjOptions {
+            mtllib: Some("model.mtl".into()),
+            ..Default::default()
+        };
+
+        let mut obj = Vec::new();
+        fj_export::export_obj_with_options(&mesh, &mut obj, &options).unwrap();
+        let mut mtl = Vec::new();
+        fj_export::export_mtl(&mesh, &mut mtl).unwrap();
+
+        assert_eq!(super::mtl_libraries(&obj[..]).unwrap(), ["model.mtl"]);
+
+        let materials = super::import_mtl(&mtl[..]).unwrap();
+        let imported = super::import_obj(&obj[..], &materials).unwrap();
+
+        let colors: Vec<_> = imported.triangles().map(|t| t.color).collect();
+        assert_eq!(colors, [red, blue]);
+    }
+
+    #[test]
+    fn import_obj_with_non_finite_coordinates() {
+        let obj = "\
+v nan 0 0
+v 1 0 0
+v 0 1 0
+f 1 2 3
+";
+
+        let result = super::import_obj(obj.as_bytes(), &Materials::new());
+        assert!(matches!(result, Err(Error::Malformed(_))));
+    }
+}
@@ -0,0 +1,191 @@
+use std::io::Read;
+
+use fj_interop::{Color, Mesh};
+use fj_math::Point;
+
+use crate::{point, push_triangle, Error};
+
+/// Import a mesh from the provided reader in the STL format
+///
+/// Both binary and ASCII STL are supported. STL doesn't carry colors, so all
+/// triangles get the default color.
+pub fn import_stl(mut read: impl Read) -> Result<Mesh<Point<3>>, Error> {
+    let mut data = Vec::new();
+    read.read_to_end(&mut data)?;
+
+    // Binary STL files may start with `solid` too, so that is not enough to
+    // identify an ASCII file. But the size of a binary file is fully
+    // determined by its triangle count, which makes for a reliable check.
+    let is_binary = data.len() >= 84 && {
+        let num_triangles =
+            u32::from_le_bytes([data[80], data[81], data[82], data[83]]);
+        data.len() as u64 == 84 + 50 * u64::from(num_triangles)
+    };
+    let is_ascii = !is_binary && {
+        let start = data
+            .iter()
+            .position(|byte| !byte.is_ascii_whitespace())
+            .unwrap_or(data.len());
+        data[start..].starts_with(b"solid")
+    };
+
+    if is_ascii {
+        let data = std::str::from_utf8(&data).map_err(|_| {
+            Error::Malformed("ASCII STL file is not valid UTF-8".into())
+        })?;
+        import_ascii_stl(data)
+    } else {
+        import_binary_stl(&data)
+    }
+}
+
+fn import_binary_stl(mut data: &[u8]) -> Result<Mesh<Point<3>>, Error> {
+    let file = stl::read_stl(&mut data)?;
+
+    let mut mesh = Mesh::new();
+
+    for triangle in file.triangles {
+        let [a, b, c] = [triangle.v1, triangle.v2, triangle.v3]
+            .map(|coords| point(coords.map(f64::from)));
+        push_triangle(&mut mesh, [a?, b?, c?], Color::default());
+    }
+
+    Ok(mesh)
+}
+
+fn import_ascii_stl(data: &str) -> Result<Mesh<Point<3>>, Error> {
+    let mut mesh = Mesh::new();
+    let mut points = Vec::new();
+
+    for (i, line) in data.lines().enumerate() {
+        let mut tokens = line.split_whitespace();
+
+        match tokens.next() {
+            Some("vertex") => {
+                let mut coords = [0.; 3];
+                for coord in &mut coords {
+                    *coord = tokens
+                        .next()
+                        .and_then(|token| token.parse().ok())
+                        .filter(|coord: &f64| coord.is_finite())
+                        .ok_or_else(|| {
+                            Error::Malformed(format!(
+                                "invalid vertex in line {} of STL file",
+                                i + 1
+                            ))
+                        })?;
+                }
+
+                points.push(Point::from(coords));
+            }
+            Some("endloop") => {
+                let [a, b, c] = points[..] else {
+                    return Err(Error::Malformed(format!(
+                        "expected 3 vertices per facet, found {} (line {} of \
+                        STL file)",
+                        points.len(),
+                        i + 1
+                    )));
+                };
+                push_triangle(&mut mesh, [a, b, c], Color::default());
+                points.clear();
+            }
+            // All other lines just provide structure (`solid`, `facet`,
+            // `outer loop`, ...) or information we don't need (facet
+            // normals). We can ignore them.
+            _ => {}
+        }
+    }
+
+    Ok(mesh)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use fj_interop::Mesh;
+    use fj_math::Point;
+
+    use crate::Error;
+
+    #[test]
+    fn import_binary_stl() {
+        let mut mesh = Mesh::new();
+        mesh.push_triangle(
+            [[0., 0., 0.], [1., 0., 0.], [0., 1., 0.]],
+            Default::default(),
+        );
+        mesh.push_triangle(
+            [[0., 0., 0.], [0., 1., 0.], [0., 0., 1.]],
+            Default::default(),
+        );
+
+        let mut data = Cursor::new(Vec::new());
+        fj_export::export_stl(&mesh, &mut data).unwrap();
+
+        let imported = super::import_stl(&data.into_inner()[..]).unwrap();
+        assert_eq!(imported.triangles().count(), 2);
+        for triangle in mesh.triangles() {
+            assert!(imported.contains_triangle(triangle.inner));
+        }
+    }
+
+    #[test]
+    fn import_ascii_stl() {
+        let data = "\
+solid triangles
+  facet normal 0 0 1
+    outer loop
+      vertex 0 0 0
+      vertex 1 0 0
+      vertex 0 1 0
+    endloop
+  endfacet
+  facet normal 0 0 1
+    outer loop
+      vertex 1 0 0
+      vertex 1 1 0
+      vertex 0 1 0
+    endloop
+  endfacet
+endsolid triangles
+";
+
+        let mesh = super::import_stl(data.as_bytes()).unwrap();
+
+        assert_eq!(mesh.triangles().count(), 2);
+        assert!(mesh.contains_triangle([
+            Point::from([1., 0., 0.]),
+            Point::from([1., 1., 0.]),
+            Point::from([0., 1., 0.]),
+        ]));
+    }
+
+    #[test]
+    fn import_stl_with_non_finite_coordinates() {
+        let mut binary = vec![0; 80];
+        binary.extend(1u32.to_le_bytes());
+        for coord in [0., 0., 1., f32::NAN, 0., 0., 1., 0., 0., 0., 1., 0.] {
+            binary.extend(f32::to_le_bytes(coord));
+        }
+        binary.extend([0, 0]);
+
+        let ascii = "\
+solid triangle
+  facet normal 0 0 1
+    outer loop
+      vertex nan 0 0
+      vertex 1 0 0
+      vertex 0 1 0
+    endloop
+  endfacet
+endsolid triangle
+";
+
+        for data in [&binary[..], ascii.as_bytes()] {
+            let result = super::import_stl(data);
+            assert!(matches!(result, Err(Error::Malformed(_))));
+        }
+    }
+}
@@ -0,0 +1,557 @@
+use std::{
+    collections::BTreeMap,
+    io::{Read, Seek},
+};
+
+use fj_interop::{Color, Mesh};
+use fj_math::Point;
+use quick_xml::events::{BytesStart, Event};
+
+use crate::{point, push_triangle, Error};
+
+/// Import a mesh from the provided reader in the 3MF format
+///
+/// All objects that are part of the build are merged into a single mesh, with
+/// the transforms of build items and components applied. Coordinates are
+/// converted into millimeters.
+///
+/// Triangles get the color of their base material or color group, if they have
+/// one. All other triangles get the default color.
+pub fn import_3mf(read: impl Read + Seek) -> Result<Mesh<Point<3>>, Error> {
+    let mut archive = zip::ZipArchive::new(read)?;
+
+    let model_path = {
+        let mut rels = String::new();
+        match archive.by_name("_rels/.rels") {
+            Ok(mut file) => {
+                file.read_to_string(&mut rels)?;
+                root_model_path(&rels)?
+            }
+            Err(zip::result::ZipError::FileNotFound) => None,
+            Err(err) => return Err(err.into()),
+        }
+        .unwrap_or_else(|| "3D/3dmodel.model".to_string())
+    };
+
+    let mut xml = String::new();
+    archive.by_name(&model_path)?.read_to_string(&mut xml)?;
+
+    let model = Model::parse(&xml)?;
+
+    let mut mesh = Mesh::new();
+    for (object_id, transform) in &model.items {
+        model.add_object(*object_id, transform, 0, &mut mesh)?;
+    }
+
+    Ok(mesh)
+}
+
+/// Find the path of the model part in the package relationships
+fn root_model_path(rels: &str) -> Result<Option<String>, Error> {
+    let mut reader = quick_xml::Reader::from_str(rels);
+
+    loop {
+        match reader.read_event()? {
+            Event::Start(element) | Event::Empty(element)
+                if element.local_name().as_ref() == b"Relationship" =>
+            {
+                let attributes = Attributes::of(&element)?;
+                let is_model = attributes
+                    .get("Type")
+                    .is_some_and(|type_| type_.ends_with("/3dmodel"));
+
+                if is_model {
+                    let target = attributes.require("Target")?;
+                    return Ok(Some(target.trim_start_matches('/').into()));
+                }
+            }
+            Event::Eof => return Ok(None),
+            _ => {}
+        }
+    }
+}
+
+struct Model {
+    objects: BTreeMap<u32, Object>,
+    properties: BTreeMap<u32, Vec<Color>>,
+    items: Vec<(u32, Transform)>,
+}
+
+impl Model {
+    fn parse(xml: &str) -> Result<Self, Error> {
+        let mut model = Self {
+            objects: BTreeMap::new(),
+            properties: BTreeMap::new(),
+            items: Vec::new(),
+        };
+
+        let mut scale = 1.;
+        let mut object: Option<(u32, Object)> = None;
+        let mut property_group = None;
+
+        let mut reader = quick_xml::Reader::from_str(xml);
+
+        loop {
+            let element = match reader.read_event()? {
+                Event::Start(element) | Event::Empty(element) => element,
+                Event::End(element) => {
+                    match element.local_name().as_ref() {
+                        b"object" => {
+                            if let Some((id, object)) = object.take() {
+                                model.objects.insert(id, object);
+                            }
+                        }
+                        b"basematerials" | b"colorgroup" => {
+                            property_group = None;
+                        }
+                        _ => {}
+                    }
+                    continue;
+                }
+                Event::Eof => break,
+                _ => continue,
+            };
+
+            let attributes = Attributes::of(&element)?;
+
+            match element.local_name().as_ref() {
+                b"model" => {
+                    scale = match attributes.get("unit").unwrap_or("millimeter")
+                    {
+                        "micron" => 0.001,
+                        "millimeter" => 1.,
+                        "centimeter" => 10.,
+                        "inch" => 25.4,
+                        "foot" => 304.8,
+                        "meter" => 1000.,
+                        unit => {
+                            return Err(Error::Malformed(format!(
+                                "unknown unit `{unit}`"
+                            )))
+                        }
+                    };
+                }
+                b"basematerials" | b"colorgroup" => {
+                    let id = attributes.parse("id")?;
+                    model.properties.insert(id, Vec::new());
+                    property_group = Some(id);
+                }
+                b"base" | b"color" => {
+                    let Some(group) = property_group else {
+                        continue;
+                    };
+                    let name = if element.local_name().as_ref() == b"base" {
+                        "displaycolor"
+                    } else {
+                        "color"
+                    };
+                    let color = parse_color(attributes.require(name)?)?;
+                    model
+                        .properties
+                        .get_mut(&group)
+                        .expect("Inserted group when starting it")
+                        .push(color);
+                }
+                b"object" => {
+                    object = Some((
+                        attributes.parse("id")?,
+                        Object {
+                            vertices: Vec::new(),
+                            triangles: Vec::new(),
+                            components: Vec::new(),
+                            pid: attributes.parse_optional("pid")?,
+                            pindex: attributes.parse_optional("pindex")?,
+                        },
+                    ));
+                }
+                b"vertex" => {
+                    let Some((_, object)) = &mut object else {
+                        continue;
+                    };
+                    let coords = [
+                        attributes.parse::<f64>("x")?,
+                        attributes.parse::<f64>("y")?,
+                        attributes.parse::<f64>("z")?,
+                    ];
+                    object
+                        .vertices
+                        .push(point(coords.map(|coord| coord * scale))?);
+                }
+                b"triangle" => {
+                    let Some((_, object)) = &mut object else {
+                        continue;
+                    };
+                    object.triangles.push(MeshTriangle {
+                        vertices: [
+                            attributes.parse("v1")?,
+                            attributes.parse("v2")?,
+                            attributes.parse("v3")?,
+                        ],
+                        pid: attributes.parse_optional("pid")?,
+                        p1: attributes.parse_optional("p1")?,
+                    });
+                }
+                b"component" => {
+                    let Some((_, object)) = &mut object else {
+                        continue;
+                    };
+                    object.components.push((
+                        attributes.parse("objectid")?,
+                        Transform::parse(attributes.get("transform"), scale)?,
+                    ));
+                }
+                b"item" => {
+                    model.items.push((
+                        attributes.parse("objectid")?,
+                        Transform::parse(attributes.get("transform"), scale)?,
+                    ));
+                }
+                _ => {}
+            }
+        }
+
+        Ok(model)
+    }
+
+    fn add_object(
+        &self,
+        id: u32,
+        transform: &Transform,
+        depth: usize,
+        mesh: &mut Mesh<Point<3>>,
+    ) -> Result<(), Error> {
+        // Components can reference each other. In a valid file, that doesn't
+        // lead to cycles, but we shouldn't overflow the stack on an invalid
+        // one either.
+        const MAX_DEPTH: usize = 32;
+        if depth > MAX_DEPTH {
+            return Err(Error::Malformed(
+                "components nested too deeply".into(),
+            ));
+        }
+
+        let object = self.objects.get(&id).ok_or_else(|| {
+            Error::Malformed(format!("reference to unknown object {id}"))
+        })?;
+
+        for triangle in &object.triangles {
+            let points = triangle
+                .vertices
+                .map(|index| object.vertices.get(index).copied());
+            let [Some(a), Some(b), Some(c)] = points else {
+                return Err(Error::Malformed(format!(
+                    "invalid vertex index in object {id}"
+                )));
+            };
+
+            let color = triangle
+                .pid
+                .or(object.pid)
+                .zip(triangle.p1.or(object.pindex))
+                .and_then(|(pid, index)| {
+                    self.properties.get(&pid)?.get(index).copied()
+                })
+                .unwrap_or_default();
+
+            // Coordinates and transforms are finite, but applying the latter
+            // to the former can still overflow.
+            let [a, b, c] = [a, b, c].map(|p| transform.apply(p));
+            push_triangle(mesh, [a?, b?, c?], color);
+        }
+
+        for (component_id, component_transform) in &object.components {
+            self.add_object(
+                *component_id,
+                &component_transform.then(transform),
+                depth + 1,
+                mesh,
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+struct Object {
+    vertices: Vec<Point<3>>,
+    triangles: Vec<MeshTriangle>,
+    components: Vec<(u32, Transform)>,
+    pid: Option<u32>,
+    pindex: Option<usize>,
+}
+
+struct MeshTriangle {
+    vertices: [usize; 3],
+    pid: Option<u32>,
+    p1: Option<usize>,
+}
+
+/// An affine transform, as 3MF defines it
+///
+/// 3MF transforms are 4x3 matrices, which are multiplied with points as row
+/// vectors. The last row contains the translation.
+#[derive(Clone, Copy)]
+struct Transform([[f64; 3]; 4]);
+
+impl Transform {
+    fn parse(transform: Option<&str>, scale: f64) -> Result<Self, Error> {
+        let Some(transform) = transform else {
+            return Ok(Self([
+                [1., 0., 0.],
+                [0., 1., 0.],
+                [0., 0., 1.],
+                [0., 0., 0.],
+            ]));
+        };
+
+        let values = transform
+            .split_whitespace()
+            .map(|value| value.parse::<f64>())
+            .collect::<Result<Vec<_>, _>>()
+            .ok()
+            .filter(|values| {
+                values.len() == 12 && values.iter().all(|v| v.is_finite())
+            })
+            .ok_or_else(|| {
+                Error::Malformed(format!("invalid transform `{transform}`"))
+            })?;
+
+        let mut rows = [[0.; 3]; 4];
+        for (row, values) in rows.iter_mut().zip(values.chunks(3)) {
+            row.copy_from_slice(values);
+        }
+
+        // Translations are given in the model's unit, like everything else.
+        rows[3] = rows[3].map(|value| value * scale);
+
+        Ok(Self(rows))
+    }
+
+    fn apply(&self, point: Point<3>) -> Result<Point<3>, Error> {
+        let [x, y, z] = point.coords.components.map(|s| s.into_f64());
+        let m = &self.0;
+
+        self::point(
+            [0, 1, 2]
+                .map(|i| x * m[0][i] + y * m[1][i] + z * m[2][i] + m[3][i]),
+        )
+    }
+
+    /// Compose this transform with another one, that is applied afterwards
+    fn then(&self, other: &Self) -> Self {
+        let m = &self.0;
+        let n = &other.0;
+
+        let mut rows = [[0.; 3]; 4];
+        for (i, row) in rows.iter_mut().enumerate() {
+            for (j, value) in row.iter_mut().enumerate() {
+                *value = (0..3).map(|k| m[i][k] * n[k][j]).sum::<f64>();
+                if i == 3 {
+                    *value += n[3][j];
+                }
+            }
+        }
+
+        Self(rows)
+    }
+}
+
+/// The attributes of an XML element, by local name
+struct Attributes(BTreeMap<String, String>);
+
+impl Attributes {
+    fn of(element: &BytesStart) -> Result<Self, Error> {
+        let mut attributes = BTreeMap::new();
+
+        for attribute in element.attributes() {
+            let attribute = attribute?;
+            let name =
+                String::from_utf8_lossy(attribute.key.local_name().as_ref())
+                    .into_owned();
+            let value = attribute.unescape_value()?.into_owned();
+
+            attributes.insert(name, value);
+        }
+
+        Ok(Self(attributes))
+    }
+
+    fn get(&self, name: &str) -> Option<&str> {
+        self.0.get(name).map(String::as_str)
+    }
+
+    fn require(&self, name: &str) -> Result<&str, Error> {
+        self.get(name).ok_or_else(|| {
+            Error::Malformed(format!("missing attribute `{name}`"))
+        })
+    }
+
+    fn parse<T: std::str::FromStr>(&self, name: &str) -> Result<T, Error> {
+        self.parse_optional(name)?.ok_or_else(|| {
+            Error::Malformed(format!("missing attribute `{name}`"))
+        })
+    }
+
+    fn parse_optional<T: std::str::FromStr>(
+        &self,
+        name: &str,
+    ) -> Result<Option<T>, Error> {
+        self.get(name)
+            .map(|value| {
+                value.trim().parse().map_err(|_| {
+                    Error::Malformed(format!(
+                        "invalid value `{value}` for attribute `{name}`"
+                    ))
+                })
+            })
+            .transpose()
+    }
+}
+
+/// Parse a color in the `#RRGGBB` or `#RRGGBBAA` format
+fn parse_color(color: &str) -> Result<Color, Error> {
+    let invalid = || Error::Malformed(format!("invalid color `{color}`"));
+
+    let hex = color.trim().strip_prefix('#').ok_or_else(invalid)?;
+    if !(hex.len() == 6 || hex.len() == 8) || !hex.is_ascii() {
+        return Err(invalid());
+    }
+
+    let mut rgba = [255; 4];
+    for (component, i) in rgba.iter_mut().zip((0..hex.len()).step_by(2)) {
+        *component =
+            u8::from_str_radix(&hex[i..i + 2], 16).map_err(|_| invalid())?;
+    }
+
+    Ok(Color(rgba))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, Write};
+
+    use fj_interop::{Color, Mesh};
+    use fj_math::Point;
+
+    use crate::Error;
+
+    #[test]
+    fn import_3mf_round_trip() {
+        let red = Color([255, 0, 0, 255]);
+        let blue = Color([0, 0, 255, 128]);
+
+        let mut mesh = Mesh::new();
+        mesh.push_triangle([[0., 0., 0.], [1., 0., 0.], [0., 1., 0.]], red);
+        mesh.push_triangle([[0., 0., 0.], [0., 1., 0.], [0., 0., 1.]], blue);
+
+        let mut data = Cursor::new(Vec::new());
+        fj_export::export_3mf(&mesh, &mut data).unwrap();
+
+        let imported = super::import_3mf(data).unwrap();
+
+        let colors: Vec<_> = imported.triangles().map(|t| t.color).collect();
+        assert_eq!(colors, [red, blue]);
+        for triangle in mesh.triangles() {
+            assert!(imported.contains_triangle(triangle.inner));
+        }
+    }
+
+    #[test]
+    fn import_3mf_with_components_and_units() {
+        let model = r##"<?xml version="1.0" encoding="UTF-8"?>
+<model unit="centimeter" xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02"
+    xmlns:m="http://schemas.microsoft.com/3dmanufacturing/material/2015/02">
+  <resources>
+    <m:colorgroup id="1">
+      <m:color color="#00FF00" />
+    </m:colorgroup>
+    <object id="2" type="model" pid="1" pindex="0">
+      <mesh>
+        <vertices>
+          <vertex x="0" y="0" z="0" />
+          <vertex x="1" y="0" z="0" />
+          <vertex x="0" y="1" z="0" />
+        </vertices>
+        <triangles>
+          <triangle v1="0" v2="1" v3="2" />
+        </triangles>
+      </mesh>
+    </object>
+    <object id="3" type="model">
+      <components>
+        <component objectid="2" transform="1 0 0 0 1 0 0 0 1 0 0 1" />
+      </components>
+    </object>
+  </resources>
+  <build>
+    <item objectid="3" transform="1 0 0 0 1 0 0 0 1 1 0 0" />
+  </build>
+</model>
+"##;
+
+        let mesh = super::import_3mf(archive(model)).unwrap();
+
+        let triangles: Vec<_> = mesh.triangles().collect();
+        assert_eq!(triangles.len(), 1);
+        assert_eq!(triangles[0].color, Color([0, 255, 0, 255]));
+        assert!(mesh.contains_triangle([
+            Point::from([10., 0., 10.]),
+            Point::from([20., 0., 10.]),
+            Point::from([10., 10., 10.]),
+        ]));
+    }
+
+    #[test]
+    fn import_3mf_with_non_finite_values() {
+        let model = |vertex: &str, transform: &str| {
+            format!(
+                r#"<?xml version="1.0" encoding="UTF-8"?>
+<model unit="millimeter" xmlns="http://schemas.microsoft.com/3dmanufacturing/core/2015/02">
+  <resources>
+    <object id="1" type="model">
+      <mesh>
+        <vertices>
+          <vertex {vertex} />
+          <vertex x="1" y="0" z="0" />
+          <vertex x="0" y="1" z="0" />
+        </vertices>
+        <triangles>
+          <triangle v1="0" v2="1" v3="2" />
+        </triangles>
+      </mesh>
+    </object>
+  </resources>
+  <build>
+    <item objectid="1" transform="{transform}" />
+  </build>
+</model>
+"#
+            )
+        };
+
+        let vertex = r#"x="0" y="0" z="0""#;
+        let transform = "1 0 0 0 1 0 0 0 1 0 0 0";
+
+        for model in [
+            model(r#"x="NaN" y="0" z="0""#, transform),
+            model(vertex, "1 0 0 0 1 0 0 0 1 inf 0 0"),
+            model(r#"x="10" y="0" z="0""#, "1e308 0 0 0 1 0 0 0 1 0 0 0"),
+        ] {
+            let result = super::import_3mf(archive(&model));
+            assert!(matches!(result, Err(Error::Malformed(_))), "{model}");
+        }
+    }
+
+    fn archive(model: &str) -> Cursor<Vec<u8>> {
+        let mut data = Cursor::new(Vec::new());
+        {
+            let mut archive = zip::ZipWriter::new(&mut data);
+            archive
+                .start_file("3D/3dmodel.model", Default::default())
+                .unwrap();
+            archive.write_all(model.as_bytes()).unwrap();
+            archive.finish().unwrap();
+        }
+        data
+    }
+}
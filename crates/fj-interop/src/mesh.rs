@@ -1,6 +1,6 @@
 use std::{collections::HashMap, hash::Hash};
 
-use fj_math::Point;
+use fj_math::{Point, Vector};
 
 use crate::Color;
 
@@ -73,12 +73,32 @@ where
 
 impl Mesh<Point<3>> {
     /// Add a triangle to the mesh
+    ///
+    /// The normal of the triangle is used as the normal at all of its points.
+    /// Use [`Mesh::push_triangle_with_normals`], if better normals are known.
     pub fn push_triangle(
         &mut self,
         triangle: impl Into<fj_math::Triangle<3>>,
         color: Color,
     ) {
         let triangle = triangle.into();
+        let normal = triangle.normal();
+
+        self.push_triangle_with_normals(triangle, [normal; 3], color);
+    }
+
+    /// Add a triangle to the mesh, with a normal for each of its points
+    ///
+    /// This is useful for triangles that approximate a curved surface, where
+    /// the normals of that surface make for a smoother appearance than the
+    /// normal of the triangle.
+    pub fn push_triangle_with_normals(
+        &mut self,
+        triangle: impl Into<fj_math::Triangle<3>>,
+        normals: [Vector<3>; 3],
+        color: Color,
+    ) {
+        let triangle = triangle.into();
 
         for point in triangle.points() {
             self.push_vertex(point);
@@ -86,6 +106,7 @@ impl Mesh<Point<3>> {
 
         self.triangles.push(Triangle {
             inner: triangle,
+            normals,
             color,
         });
     }
@@ -109,12 +130,18 @@ pub type Index = u32;
 
 /// A triangle
 ///
-/// Extension of [`fj_math::Triangle`] that also includes a color.
+/// Extension of [`fj_math::Triangle`] that also includes normals and a color.
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd)]
 pub struct Triangle {
     /// The points of the triangle
     pub inner: fj_math::Triangle<3>,
 
+    /// The normals at the points of the triangle
+    ///
+    /// These are the normals of the surface that the triangle approximates,
+    /// if known, or the normal of the triangle itself.
+    pub normals: [Vector<3>; 3],
+
     /// The color of the triangle
     pub color: Color,
 }
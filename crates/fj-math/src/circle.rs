@@ -33,19 +33,21 @@ impl<const D: usize> Circle<D> {
         let a = a.into();
         let b = b.into();
 
-        assert_eq!(
-            a.magnitude(),
-            b.magnitude(),
-            "`a` and `b` must be of equal length"
-        );
         assert_ne!(
             a.magnitude(),
             Scalar::ZERO,
             "circle radius must not be zero"
         );
-        // Requiring the vector to be *precisely* perpendicular is not
-        // practical, because of numerical inaccuracy. This epsilon value seems
-        // seems to work for now, but maybe it needs to become configurable.
+        // Requiring the vectors to be *precisely* of equal length and
+        // perpendicular is not practical, because of numerical inaccuracy
+        // (which, for example, rotating them can introduce). These epsilon
+        // values seem to work for now, but maybe they need to become
+        // configurable.
+        assert!(
+            (a.magnitude() - b.magnitude()).abs()
+                <= a.magnitude() * Scalar::default_epsilon() * 4.,
+            "`a` and `b` must be of equal length"
+        );
         assert!(
             a.dot(&b) < Scalar::default_epsilon(),
             "`a` and `b` must be perpendicular to each other"
@@ -181,10 +183,29 @@ impl<const D: usize> approx::AbsDiffEq for Circle<D> {
 mod tests {
     use std::f64::consts::{FRAC_PI_2, PI};
 
-    use crate::{Point, Vector};
+    use crate::{Point, Scalar, Vector};
 
     use super::Circle;
 
+    #[test]
+    fn new_tolerates_numerical_inaccuracy() {
+        // Rotating the axes of a circle can change their lengths by a tiny
+        // amount.
+        let (sin, cos) = 1f64.sin_cos();
+        let a = Vector::from([cos, sin, 0.]);
+        let b = Vector::from([-sin, cos, 0.]) * (1. + f64::EPSILON);
+        assert_ne!(a.magnitude(), b.magnitude());
+
+        let circle = Circle::new([0., 0., 0.], a, b);
+        assert_eq!(circle.radius(), Scalar::ONE);
+    }
+
+    #[test]
+    #[should_panic(expected = "`a` and `b` must be of equal length")]
+    fn new_rejects_axes_of_different_length() {
+        Circle::new([0., 0.], [1., 0.], [0., 1.001]);
+    }
+
     #[test]
     fn point_to_circle_coords() {
         let circle = Circle {
@@ -41,6 +41,7 @@ mod point;
 mod poly_chain;
 mod scalar;
 mod segment;
+mod spline;
 mod transform;
 mod triangle;
 mod vector;
@@ -56,6 +57,7 @@ pub use self::{
     poly_chain::PolyChain,
     scalar::{Scalar, Sign},
     segment::Segment,
+    spline::Spline,
     transform::Transform,
     triangle::{Triangle, Winding},
     vector::Vector,
@@ -0,0 +1,514 @@
+use std::iter;
+
+use crate::{Aabb, Point, Scalar, Vector};
+
+/// An n-dimensional NURBS curve (non-uniform rational B-spline)
+///
+/// The dimensionality of the spline is defined by the const generic `D`
+/// parameter.
+///
+/// Bezier curves and non-rational B-splines are special cases of NURBS curves,
+/// and can be constructed using [`Spline::bezier`] and [`Spline::clamped`].
+///
+/// The coordinates of the spline range over its [domain](Spline::domain),
+/// which is defined by its knots. Coordinates outside of the domain are valid
+/// too, and extrapolate the spline from its first or last segment.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct Spline<const D: usize> {
+    degree: usize,
+    control_points: Vec<Point<D>>,
+    weights: Vec<Scalar>,
+    knots: Vec<Scalar>,
+}
+
+impl<const D: usize> Spline<D> {
+    /// Construct a spline
+    ///
+    /// # Panics
+    ///
+    /// Panics, if any of the following requirements are not met:
+    ///
+    /// - The degree must be at least `1`.
+    /// - There must be more control points than the degree.
+    /// - There must be one weight per control point, and all weights must be
+    ///   positive.
+    /// - The number of knots must be the number of control points, plus the
+    ///   degree, plus one.
+    /// - The knots must not be decreasing, and the domain must not be empty.
+    pub fn new(
+        degree: usize,
+        control_points: impl IntoIterator<Item = impl Into<Point<D>>>,
+        weights: impl IntoIterator<Item = impl Into<Scalar>>,
+        knots: impl IntoIterator<Item = impl Into<Scalar>>,
+    ) -> Self {
+        let control_points: Vec<_> =
+            control_points.into_iter().map(Into::into).collect();
+        let weights: Vec<_> = weights.into_iter().map(Into::into).collect();
+        let knots: Vec<_> = knots.into_iter().map(Into::into).collect();
+
+        assert!(degree >= 1, "Spline degree must be at least 1");
+        assert!(
+            control_points.len() > degree,
+            "Spline must have more control points than its degree"
+        );
+        assert_eq!(
+            weights.len(),
+            control_points.len(),
+            "Spline must have one weight per control point"
+        );
+        assert!(
+            weights.iter().all(|weight| weight.is_positive()),
+            "Spline weights must be positive"
+        );
+        assert_eq!(
+            knots.len(),
+            control_points.len() + degree + 1,
+            "Spline must have as many knots as control points plus degree \
+            plus one"
+        );
+        assert!(
+            knots.windows(2).all(|knots| knots[0] <= knots[1]),
+            "Spline knots must not be decreasing"
+        );
+
+        let self_ = Self {
+            degree,
+            control_points,
+            weights,
+            knots,
+        };
+
+        let [min, max] = self_.domain();
+        assert!(min < max, "Spline domain must not be empty");
+
+        self_
+    }
+
+    /// Construct a Bezier curve from its control points
+    ///
+    /// The degree of the curve is the number of control points minus one. Its
+    /// domain is `[0, 1]`.
+    pub fn bezier(
+        control_points: impl IntoIterator<Item = impl Into<Point<D>>>,
+    ) -> Self {
+        let control_points: Vec<Point<D>> =
+            control_points.into_iter().map(Into::into).collect();
+        let degree = control_points.len().saturating_sub(1);
+
+        let knots = iter::repeat(Scalar::ZERO)
+            .take(degree + 1)
+            .chain(iter::repeat(Scalar::ONE).take(degree + 1));
+        let weights = iter::repeat(Scalar::ONE).take(control_points.len());
+
+        Self::new(degree, control_points, weights, knots)
+    }
+
+    /// Construct a clamped, uniform B-spline from its control points
+    ///
+    /// The curve starts at the first control point and ends at the last one.
+    /// Its domain is `[0, 1]`.
+    pub fn clamped(
+        degree: usize,
+        control_points: impl IntoIterator<Item = impl Into<Point<D>>>,
+    ) -> Self {
+        let control_points: Vec<Point<D>> =
+            control_points.into_iter().map(Into::into).collect();
+        let num_segments = control_points.len().saturating_sub(degree).max(1);
+
+        let knots = iter::repeat(Scalar::ZERO)
+            .take(degree)
+            .chain((0..=num_segments).map(|i| {
+                Scalar::from_u64(i as u64)
+                    / Scalar::from_u64(num_segments as u64)
+            }))
+            .chain(iter::repeat(Scalar::ONE).take(degree));
+        let weights = iter::repeat(Scalar::ONE).take(control_points.len());
+
+        Self::new(degree, control_points, weights, knots)
+    }
+
+    /// Access the degree of the spline
+    pub fn degree(&self) -> usize {
+        self.degree
+    }
+
+    /// Access the control points of the spline
+    pub fn control_points(&self) -> &[Point<D>] {
+        &self.control_points
+    }
+
+    /// Access the weights of the spline's control points
+    pub fn weights(&self) -> &[Scalar] {
+        &self.weights
+    }
+
+    /// Access the knots of the spline
+    pub fn knots(&self) -> &[Scalar] {
+        &self.knots
+    }
+
+    /// Access the domain of the spline
+    ///
+    /// This is the range of coordinates, over which the spline is defined by
+    /// its control points.
+    pub fn domain(&self) -> [Scalar; 2] {
+        [
+            self.knots[self.degree],
+            self.knots[self.control_points.len()],
+        ]
+    }
+
+    /// Create a new instance that is reversed
+    ///
+    /// The reversed spline has the same domain. A coordinate `t` on the
+    /// reversed spline refers to the same point as `min + max - t` on the
+    /// original one.
+    #[must_use]
+    pub fn reverse(mut self) -> Self {
+        let [min, max] = self.domain();
+
+        self.control_points.reverse();
+        self.weights.reverse();
+        self.knots = self
+            .knots
+            .iter()
+            .rev()
+            .map(|knot| min + max - *knot)
+            .collect();
+
+        self
+    }
+
+    /// Convert a `D`-dimensional point into spline coordinates
+    ///
+    /// Returns the coordinate of the point on the spline that is closest to
+    /// the provided point, within the domain of the spline. The closest point
+    /// is found numerically, by sampling the spline and then refining the
+    /// closest sample.
+    pub fn point_to_spline_coords(
+        &self,
+        point: impl Into<Point<D>>,
+    ) -> Point<1> {
+        const SAMPLES_PER_SPAN: u64 = 16;
+        const ITERATIONS: usize = 16;
+
+        let point = point.into();
+        let [min, max] = self.domain();
+
+        let distance_to = |t: Scalar| {
+            (self.point_from_spline_coords([t]) - point).magnitude()
+        };
+
+        let mut t = min;
+        for span in self.knots.windows(2) {
+            let [a, b] = [span[0].max(min), span[1].min(max)];
+            if a >= b {
+                continue;
+            }
+
+            for i in 0..=SAMPLES_PER_SPAN {
+                let sample = a
+                    + (b - a) * Scalar::from_u64(i)
+                        / Scalar::from_u64(SAMPLES_PER_SPAN);
+                if distance_to(sample) < distance_to(t) {
+                    t = sample;
+                }
+            }
+        }
+
+        // Refine the sample, by moving along the tangent towards the point.
+        for _ in 0..ITERATIONS {
+            let tangent = self.tangent_at([t]);
+            let length_squared = tangent.dot(&tangent);
+            if length_squared == Scalar::ZERO {
+                break;
+            }
+
+            let offset = point - self.point_from_spline_coords([t]);
+            let next = (t + offset.dot(&tangent) / length_squared)
+                .max(min)
+                .min(max);
+
+            if distance_to(next) >= distance_to(t) {
+                break;
+            }
+            t = next;
+        }
+
+        Point::from([t])
+    }
+
+    /// Convert a point in spline coordinates into a `D`-dimensional point
+    pub fn point_from_spline_coords(
+        &self,
+        point: impl Into<Point<1>>,
+    ) -> Point<D> {
+        let t = point.into().t;
+
+        let (point, weight) =
+            de_boor(self.degree, &self.knots, &self.homogeneous(), t);
+        Point {
+            coords: point / weight,
+        }
+    }
+
+    /// Compute the tangent of the spline at the provided spline coordinates
+    ///
+    /// The tangent is the derivative of the spline with respect to its
+    /// coordinate, which means its magnitude is not normalized.
+    pub fn tangent_at(&self, point: impl Into<Point<1>>) -> Vector<D> {
+        let t = point.into().t;
+        let homogeneous = self.homogeneous();
+
+        let (point, weight) =
+            de_boor(self.degree, &self.knots, &homogeneous, t);
+
+        // The control points of the derivative are the scaled differences of
+        // the original ones. It is of one degree less, and lacks the first and
+        // last knot.
+        let degree = Scalar::from_u64(self.degree as u64);
+        let derivative = homogeneous
+            .windows(2)
+            .enumerate()
+            .map(|(i, points)| {
+                let span = self.knots[i + self.degree + 1] - self.knots[i + 1];
+                if span == Scalar::ZERO {
+                    return (Vector::from_component(0.), Scalar::ZERO);
+                }
+
+                let factor = degree / span;
+                (
+                    (points[1].0 - points[0].0) * factor,
+                    (points[1].1 - points[0].1) * factor,
+                )
+            })
+            .collect::<Vec<_>>();
+        let (d_point, d_weight) = de_boor(
+            self.degree - 1,
+            &self.knots[1..self.knots.len() - 1],
+            &derivative,
+            t,
+        );
+
+        // Apply the quotient rule to the rational curve.
+        (d_point - point / weight * d_weight) / weight
+    }
+
+    /// Calculate an AABB for the spline
+    ///
+    /// A spline is contained within the convex hull of its control points, so
+    /// their AABB also contains the spline.
+    pub fn aabb(&self) -> Aabb<D> {
+        let mut min = self.control_points[0];
+        let mut max = self.control_points[0];
+
+        for point in &self.control_points {
+            for i in 0..D {
+                min.coords.components[i] =
+                    min.coords.components[i].min(point.coords.components[i]);
+                max.coords.components[i] =
+                    max.coords.components[i].max(point.coords.components[i]);
+            }
+        }
+
+        Aabb { min, max }
+    }
+
+    /// Create a new spline by mapping the control points
+    ///
+    /// The spline is invariant under affine maps, which means that mapping its
+    /// control points with an affine map is equivalent to mapping each point
+    /// on the spline. This is not true for other maps.
+    pub fn map_control_points<const E: usize>(
+        &self,
+        f: impl FnMut(Point<D>) -> Point<E>,
+    ) -> Spline<E> {
+        Spline {
+            degree: self.degree,
+            control_points: self
+                .control_points
+                .iter()
+                .copied()
+                .map(f)
+                .collect(),
+            weights: self.weights.clone(),
+            knots: self.knots.clone(),
+        }
+    }
+
+    /// The control points in homogeneous coordinates
+    fn homogeneous(&self) -> Vec<(Vector<D>, Scalar)> {
+        self.control_points
+            .iter()
+            .zip(&self.weights)
+            .map(|(point, weight)| (point.coords * *weight, *weight))
+            .collect()
+    }
+}
+
+/// Evaluate a non-rational spline in homogeneous coordinates
+fn de_boor<const D: usize>(
+    degree: usize,
+    knots: &[Scalar],
+    points: &[(Vector<D>, Scalar)],
+    t: Scalar,
+) -> (Vector<D>, Scalar) {
+    if degree == 0 {
+        let span = find_span(0, knots, points.len(), t);
+        return points[span];
+    }
+
+    let span = find_span(degree, knots, points.len(), t);
+
+    let mut d = points[span - degree..=span].to_vec();
+    for r in 1..=degree {
+        for j in (r..=degree).rev() {
+            let i = j + span - degree;
+            let alpha = (t - knots[i]) / (knots[i + degree + 1 - r] - knots[i]);
+
+            d[j] = (
+                d[j - 1].0 * (Scalar::ONE - alpha) + d[j].0 * alpha,
+                d[j - 1].1 * (Scalar::ONE - alpha) + d[j].1 * alpha,
+            );
+        }
+    }
+
+    d[degree]
+}
+
+/// Find the index of the knot span that contains `t`
+///
+/// Only non-empty spans within the domain are considered. Coordinates outside
+/// of the domain are attributed to the first or last span.
+fn find_span(
+    degree: usize,
+    knots: &[Scalar],
+    num_points: usize,
+    t: Scalar,
+) -> usize {
+    let spans = (degree..num_points).filter(|&i| knots[i] < knots[i + 1]);
+
+    let mut result = None;
+    for span in spans {
+        if result.is_none() || knots[span] <= t {
+            result = Some(span);
+        }
+    }
+
+    result.expect("Spline domain must not be empty")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::FRAC_1_SQRT_2;
+
+    use crate::{Point, Scalar, Vector};
+
+    use super::Spline;
+
+    #[test]
+    fn bezier() {
+        let spline = Spline::bezier([[0., 0.], [1., 2.], [3., 2.], [4., 0.]]);
+
+        assert_eq!(spline.degree(), 3);
+        assert_eq!(spline.domain(), [Scalar::ZERO, Scalar::ONE]);
+
+        assert_eq!(
+            spline.point_from_spline_coords([0.]),
+            Point::from([0., 0.])
+        );
+        assert_eq!(
+            spline.point_from_spline_coords([1.]),
+            Point::from([4., 0.])
+        );
+        assert_eq!(
+            spline.point_from_spline_coords([0.5]),
+            Point::from([2., 1.5])
+        );
+
+        assert_eq!(spline.tangent_at([0.]), Vector::from([3., 6.]));
+        assert_eq!(spline.tangent_at([0.5]), Vector::from([4.5, 0.]));
+    }
+
+    #[test]
+    fn clamped() {
+        let spline = Spline::clamped(
+            2,
+            [[0., 0., 0.], [1., 1., 0.], [2., 0., 0.], [3., 1., 0.]],
+        );
+
+        assert_eq!(spline.domain(), [Scalar::ZERO, Scalar::ONE]);
+
+        assert_eq!(
+            spline.point_from_spline_coords([0.]),
+            Point::from([0., 0., 0.])
+        );
+        assert_eq!(
+            spline.point_from_spline_coords([1.]),
+            Point::from([3., 1., 0.])
+        );
+
+        // At the interior knot, the curve is at the midpoint between the two
+        // middle control points.
+        assert_eq!(
+            spline.point_from_spline_coords([0.5]),
+            Point::from([1.5, 0.5, 0.])
+        );
+    }
+
+    #[test]
+    fn rational() {
+        // A quarter circle, which can be represented exactly by a rational
+        // quadratic spline.
+        let spline = Spline::new(
+            2,
+            [[1., 0.], [1., 1.], [0., 1.]],
+            [1., FRAC_1_SQRT_2, 1.],
+            [0., 0., 0., 1., 1., 1.],
+        );
+
+        for i in 0..=10 {
+            let t = Scalar::from_u64(i) / Scalar::from_u64(10);
+
+            let point = spline.point_from_spline_coords([t]);
+            let radius = point.coords.magnitude();
+            assert!((radius - Scalar::ONE).abs() < Scalar::from(1e-12));
+
+            let tangent = spline.tangent_at([t]);
+            assert!(tangent.dot(&point.coords).abs() < Scalar::from(1e-12));
+        }
+    }
+
+    #[test]
+    fn point_to_spline_coords() {
+        let spline = Spline::clamped(
+            3,
+            [[0., 0.], [1., 2.], [2., -1.], [3., 1.], [4., 0.], [5., 1.]],
+        );
+
+        for i in 0..=10 {
+            let t = Scalar::from_u64(i) / Scalar::from_u64(10);
+            let point = spline.point_from_spline_coords([t]);
+
+            let coords = spline.point_to_spline_coords(point);
+            assert!((coords.t - t).abs() < Scalar::from(1e-9));
+        }
+    }
+
+    #[test]
+    fn reverse() {
+        let spline = Spline::clamped(
+            3,
+            [[0., 0.], [1., 2.], [2., -1.], [3., 1.], [4., 0.], [5., 1.]],
+        );
+        let reversed = spline.clone().reverse();
+
+        for i in 0..=10 {
+            let t = Scalar::from_u64(i) / Scalar::from_u64(10);
+
+            let a = spline.point_from_spline_coords([t]);
+            let b = reversed.point_from_spline_coords([Scalar::ONE - t]);
+            assert!((a - b).magnitude() < Scalar::from(1e-12));
+        }
+    }
+}
@@ -2,7 +2,7 @@ use std::ops;
 
 use nalgebra::Perspective3;
 
-use crate::{Circle, Line, Scalar};
+use crate::{Circle, Line, Scalar, Spline};
 
 use super::{Aabb, Point, Segment, Triangle, Vector};
 
@@ -95,6 +95,11 @@ impl Transform {
         )
     }
 
+    /// Transform the given spline
+    pub fn transform_spline(&self, spline: &Spline<3>) -> Spline<3> {
+        spline.map_control_points(|point| self.transform_point(&point))
+    }
+
     /// Inverse transform
     pub fn inverse(&self) -> Self {
         Self(self.0.inverse())
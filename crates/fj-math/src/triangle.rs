@@ -1,4 +1,4 @@
-use parry3d_f64::query::{Ray, RayCast as _};
+use parry3d_f64::query::{PointQuery as _, Ray, RayCast as _};
 
 use crate::Vector;
 
@@ -102,6 +102,24 @@ impl Triangle<3> {
             .map(Into::into)
     }
 
+    /// Compute the point on the triangle that is closest to the provided point
+    ///
+    /// Returns the point itself, if it lies on the triangle.
+    pub fn closest_point(&self, point: impl Into<Point<3>>) -> Point<3> {
+        self.to_parry()
+            .project_local_point(&point.into().to_na(), true)
+            .point
+            .into()
+    }
+
+    /// Compute the distance between the triangle and a point
+    ///
+    /// Returns zero, if the point lies on the triangle.
+    pub fn distance_to_point(&self, point: impl Into<Point<3>>) -> Scalar {
+        let point = point.into();
+        self.closest_point(point).distance_to(&point)
+    }
+
     /// Compute the triangle's normal
     pub fn normal(&self) -> Vector<3> {
         self.to_parry()
@@ -195,4 +213,34 @@ mod tests {
             Triangle::from([[0.0, 0.0, 0.0], [2.0, 1.0, 0.0], [2.0, 0.0, 0.0]]);
         assert_eq!(triangle.normal(), Vector::from([0.0, 0.0, -1.0]));
     }
+
+    #[test]
+    fn distance_to_point() {
+        let triangle =
+            Triangle::from([[0.0, 0.0, 0.0], [2.0, 0.0, 0.0], [0.0, 2.0, 0.0]]);
+
+        assert_eq!(triangle.distance_to_point([0.5, 0.5, 0.0]), 0.0.into());
+        assert_eq!(triangle.distance_to_point([0.5, 0.5, 3.0]), 3.0.into());
+        assert_eq!(triangle.distance_to_point([-3.0, 0.0, 4.0]), 5.0.into());
+    }
+
+    #[test]
+    fn closest_point() {
+        let triangle =
+            Triangle::from([[0.0, 0.0, 0.0], [2.0, 0.0, 0.0], [0.0, 2.0, 0.0]]);
+
+        // Above the interior, a vertex, and an edge of the triangle.
+        assert_eq!(
+            triangle.closest_point([0.5, 0.5, 3.0]),
+            Point::from([0.5, 0.5, 0.0])
+        );
+        assert_eq!(
+            triangle.closest_point([-3.0, -1.0, 4.0]),
+            Point::from([0.0, 0.0, 0.0])
+        );
+        assert_eq!(
+            triangle.closest_point([2.0, 2.0, 1.0]),
+            Point::from([1.0, 1.0, 0.0])
+        );
+    }
 }
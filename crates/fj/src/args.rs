@@ -1,6 +1,9 @@
 use std::{num::ParseFloatError, path::PathBuf, str::FromStr};
 
 use fj_core::algorithms::approx::{InvalidTolerance, Tolerance};
+use fj_export::{
+    ExportOptions, ObjNormals, ObjOptions, StlFormat, StlOptions, Units,
+};
 use fj_math::Scalar;
 
 /// Standardized CLI for Fornjot models
@@ -13,13 +16,61 @@ use fj_math::Scalar;
 /// You might not want to use this struct directly. [`Instance::process_model`]
 /// provides a more high-level and convenient interface.
 ///
+/// Some arguments require access to the model's b-rep, which
+/// [`Instance::process_model`] doesn't have. Those return an error at runtime,
+/// unless the model is processed by [`Instance::process_brep_model`] instead.
+/// This is noted in the documentation of each argument.
+///
 /// [`Instance::process_model`]: crate::Instance::process_model
+/// [`Instance::process_brep_model`]: crate::Instance::process_brep_model
 #[derive(clap::Parser)]
 pub struct Args {
     /// Export model to this path
+    ///
+    /// STEP files (`.step` or `.stp`) contain the exact b-rep. All other
+    /// formats contain a triangle mesh.
+    ///
+    /// Exporting to STEP requires access to the model's b-rep, which not all
+    /// models provide. It results in an error for those.
     #[arg(short, long, value_name = "PATH")]
     pub export: Option<PathBuf>,
 
+    /// Write STL files in ASCII instead of binary format
+    #[arg(long)]
+    pub stl_ascii: bool,
+
+    /// Unit that coordinates are written in, when exporting to STL
+    ///
+    /// Supported units are `um`, `mm`, `cm`, `m`, and `in`. Defaults to `mm`.
+    #[arg(long, value_name = "UNITS", value_parser = parse_units)]
+    pub stl_units: Option<Units>,
+
+    /// Name of the model, written into exported STL files
+    ///
+    /// Defaults to the name of the exported file.
+    #[arg(long, value_name = "NAME")]
+    pub stl_name: Option<String>,
+
+    /// Text for the header of exported binary STL files
+    ///
+    /// Defaults to a header that mentions model name and units.
+    #[arg(long, value_name = "TEXT")]
+    pub stl_header: Option<String>,
+
+    /// Normals to write into exported OBJ files
+    ///
+    /// Supported values are `none`, `face`, and `smooth`. Defaults to
+    /// `smooth`.
+    #[arg(long, value_name = "NORMALS", value_parser = parse_obj_normals)]
+    pub obj_normals: Option<ObjNormals>,
+
+    /// Print the mass properties of the model, instead of displaying it
+    ///
+    /// Can't be combined with `--export`. Requires access to the model's b-rep,
+    /// which not all models provide. Results in an error for those.
+    #[arg(long, conflicts_with = "export")]
+    pub mass_properties: bool,
+
     /// How much the export can deviate from the original model
     #[arg(short, long, value_parser = parse_tolerance)]
     pub tolerance: Option<Tolerance>,
@@ -27,6 +78,13 @@ pub struct Args {
     /// Ignore validation errors
     #[arg(short, long)]
     pub ignore_validation: bool,
+
+    /// Write a report of all validation errors to this path, as JSON
+    ///
+    /// The report is written, even if there are no validation errors. Use `-`
+    /// to write it to stdout.
+    #[arg(long, value_name = "PATH")]
+    pub validation_report: Option<PathBuf>,
 }
 
 impl Args {
@@ -37,6 +95,26 @@ impl Args {
     pub fn parse() -> Self {
         <Self as clap::Parser>::parse()
     }
+
+    /// Assemble the export options from the command-line arguments
+    pub fn export_options(&self) -> ExportOptions {
+        ExportOptions {
+            stl: StlOptions {
+                format: if self.stl_ascii {
+                    StlFormat::Ascii
+                } else {
+                    StlFormat::Binary
+                },
+                units: self.stl_units.unwrap_or_default(),
+                name: self.stl_name.clone(),
+                header: self.stl_header.clone(),
+            },
+            obj: ObjOptions {
+                normals: self.obj_normals.unwrap_or_default(),
+                mtllib: None,
+            },
+        }
+    }
 }
 
 fn parse_tolerance(input: &str) -> Result<Tolerance, ArgsError> {
@@ -47,6 +125,14 @@ fn parse_tolerance(input: &str) -> Result<Tolerance, ArgsError> {
     Ok(tolerance)
 }
 
+fn parse_units(input: &str) -> Result<Units, ArgsError> {
+    Ok(Units::from_str(input)?)
+}
+
+fn parse_obj_normals(input: &str) -> Result<ObjNormals, ArgsError> {
+    Ok(ObjNormals::from_str(input)?)
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum ArgsError {
     #[error("Error parsing tolerance")]
@@ -54,4 +140,7 @@ pub enum ArgsError {
 
     #[error(transparent)]
     InvalidTolerance(#[from] InvalidTolerance),
+
+    #[error(transparent)]
+    InvalidExportOption(#[from] fj_export::Error),
 }
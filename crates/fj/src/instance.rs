@@ -1,19 +1,30 @@
-use std::{error::Error as _, fmt};
+use std::{
+    error::Error as _,
+    fmt,
+    fs::File,
+    io::{self, Write},
+    path::Path,
+};
 
 use fj_core::{
     algorithms::{
         approx::{InvalidTolerance, Tolerance},
         bounding_volume::BoundingVolume,
+        mass_properties::{ComputeMassProperties, MassProperties},
         triangulate::Triangulate,
     },
-    validation::{ValidationConfig, ValidationErrors},
+    geometry::Geometry,
+    validation::{
+        ValidationConfig, ValidationErrorReport, ValidationErrors,
+        ValidationReport,
+    },
     Core,
 };
 use fj_interop::Model;
 use fj_math::{Aabb, Point, Scalar};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
-use crate::Args;
+use crate::{export::ExportStep, Args};
 
 /// An instance of Fornjot
 ///
@@ -36,6 +47,27 @@ impl Instance {
         Self { core }
     }
 
+    /// Compute the mass properties of a model
+    ///
+    /// Curved faces are approximated using the provided tolerance. If no
+    /// tolerance is provided, a default is derived from the size of the model,
+    /// the same way as for [`Instance::process_model`].
+    pub fn mass_properties<M>(
+        &self,
+        model: &M,
+        tolerance: Option<Tolerance>,
+    ) -> std::result::Result<MassProperties, InvalidTolerance>
+    where
+        for<'r> &'r M: BoundingVolume<3> + ComputeMassProperties,
+    {
+        let tolerance = match tolerance {
+            Some(tolerance) => tolerance,
+            None => default_tolerance(&self.aabb(model))?,
+        };
+
+        Ok(model.mass_properties(tolerance, &self.core.layers.geometry))
+    }
+
     /// Export or display a model, according to CLI arguments
     ///
     /// This function is intended to be called by applications that define a
@@ -44,7 +76,31 @@ impl Instance {
     ///
     /// This function is used by Fornjot's own testing infrastructure, but is
     /// useful beyond that, when using Fornjot directly to define a model.
+    ///
+    /// Exporting to STEP and computing mass properties require access to the
+    /// model's b-rep, and return an error here. Use
+    /// [`Instance::process_brep_model`] for models that support them.
     pub fn process_model<M>(&mut self, model: &M) -> Result
+    where
+        for<'r> (&'r M, Tolerance): Triangulate,
+        for<'r> &'r M: BoundingVolume<3>,
+    {
+        self.process(model, None)
+    }
+
+    /// Export or display a model, according to CLI arguments
+    ///
+    /// Like [`Instance::process_model`], but also supports exporting the
+    /// model's b-rep to STEP, and printing its mass properties.
+    pub fn process_brep_model<M>(&mut self, model: &M) -> Result
+    where
+        for<'r> (&'r M, Tolerance): Triangulate,
+        for<'r> &'r M: BoundingVolume<3> + ComputeMassProperties + ExportStep,
+    {
+        self.process(model, Some(model))
+    }
+
+    fn process<M>(&mut self, model: &M, brep: Option<&dyn BRep>) -> Result
     where
         for<'r> (&'r M, Tolerance): Triangulate,
         for<'r> &'r M: BoundingVolume<3>,
@@ -56,38 +112,69 @@ impl Instance {
 
         let args = Args::parse();
 
-        if !args.ignore_validation {
+        let warnings = self.core.layers.validation.take_warnings();
+        for warning in &warnings {
+            tracing::warn!("{warning}");
+        }
+
+        if let Some(path) = &args.validation_report {
+            let validation = self.core.layers.validation.take_errors();
+
+            let mut report = match &validation {
+                Ok(()) => ValidationReport::default(),
+                Err(errors) => errors.report(),
+            };
+            report.warnings =
+                warnings.iter().map(ValidationErrorReport::new).collect();
+
+            write_validation_report(&report, path)
+                .map_err(Error::ValidationReport)?;
+
+            if !args.ignore_validation {
+                validation?;
+            }
+        } else if !args.ignore_validation {
             self.core.layers.validation.take_errors()?;
         }
 
-        let aabb = model.aabb(&self.core.layers.geometry).unwrap_or(Aabb {
-            min: Point::origin(),
-            max: Point::origin(),
-        });
+        if let Some(path) = &args.export {
+            // STEP files contain the exact b-rep, so there's no need to
+            // triangulate the model.
+            let is_step = path.extension().is_some_and(|extension| {
+                let extension = extension.to_ascii_uppercase();
+                extension == "STEP" || extension == "STP"
+            });
 
-        let tolerance = match args.tolerance {
-            None => {
-                // Compute a reasonable default for the tolerance value. To do
-                // this, we just look at the smallest non-zero extent of the
-                // bounding box and divide that by some value.
-
-                let mut min_extent = Scalar::MAX;
-                for extent in aabb.size().components {
-                    if extent > Scalar::ZERO && extent < min_extent {
-                        min_extent = extent;
-                    }
-                }
-
-                let tolerance = min_extent / Scalar::from_f64(1000.);
-                Tolerance::from_scalar(tolerance)?
+            if is_step {
+                let brep = brep.ok_or(Error::Unsupported("STEP export"))?;
+
+                let file =
+                    File::create(path).map_err(crate::export::Error::Io)?;
+                brep.export_step(&self.core.layers.geometry, file)?;
+                return Ok(());
             }
+        }
+
+        let aabb = self.aabb(model);
+
+        let tolerance = match args.tolerance {
+            None => default_tolerance(&aabb)?,
             Some(user_defined_tolerance) => user_defined_tolerance,
         };
 
+        if args.mass_properties {
+            let brep = brep.ok_or(Error::Unsupported("Mass properties"))?;
+
+            let properties =
+                brep.mass_properties(tolerance, &self.core.layers.geometry);
+            print_mass_properties(&properties);
+            return Ok(());
+        }
+
         let mesh = (model, tolerance).triangulate(&mut self.core);
 
-        if let Some(path) = args.export {
-            crate::export::export(&mesh, &path)?;
+        if let Some(path) = &args.export {
+            crate::export::export(&mesh, path, &args.export_options())?;
             return Ok(());
         }
 
@@ -97,6 +184,116 @@ impl Instance {
 
         Ok(())
     }
+
+    fn aabb<M>(&self, model: &M) -> Aabb<3>
+    where
+        for<'r> &'r M: BoundingVolume<3>,
+    {
+        model.aabb(&self.core.layers.geometry).unwrap_or(Aabb {
+            min: Point::origin(),
+            max: Point::origin(),
+        })
+    }
+}
+
+/// Access to a model's b-rep, which not all models provide
+///
+/// Used by [`Instance::process_brep_model`].
+trait BRep {
+    fn export_step(
+        &self,
+        geometry: &Geometry,
+        file: File,
+    ) -> std::result::Result<(), crate::export::Error>;
+
+    fn mass_properties(
+        &self,
+        tolerance: Tolerance,
+        geometry: &Geometry,
+    ) -> MassProperties;
+}
+
+impl<M> BRep for M
+where
+    for<'r> &'r M: ComputeMassProperties + ExportStep,
+{
+    fn export_step(
+        &self,
+        geometry: &Geometry,
+        file: File,
+    ) -> std::result::Result<(), crate::export::Error> {
+        ExportStep::export_step(self, geometry, file)
+    }
+
+    fn mass_properties(
+        &self,
+        tolerance: Tolerance,
+        geometry: &Geometry,
+    ) -> MassProperties {
+        ComputeMassProperties::mass_properties(self, tolerance, geometry)
+    }
+}
+
+/// Compute a reasonable default for the tolerance value
+///
+/// To do this, we just look at the smallest non-zero extent of the bounding box
+/// and divide that by some value.
+fn default_tolerance(
+    aabb: &Aabb<3>,
+) -> std::result::Result<Tolerance, InvalidTolerance> {
+    let mut min_extent = Scalar::MAX;
+    for extent in aabb.size().components {
+        if extent > Scalar::ZERO && extent < min_extent {
+            min_extent = extent;
+        }
+    }
+
+    let tolerance = min_extent / Scalar::from_f64(1000.);
+    Tolerance::from_scalar(tolerance)
+}
+
+fn write_validation_report(
+    report: &ValidationReport,
+    path: &Path,
+) -> io::Result<()> {
+    let mut write: Box<dyn Write> = if path == Path::new("-") {
+        Box::new(io::stdout())
+    } else {
+        Box::new(File::create(path)?)
+    };
+
+    serde_json::to_writer_pretty(&mut write, report)?;
+    writeln!(write)?;
+
+    Ok(())
+}
+
+fn print_mass_properties(properties: &MassProperties) {
+    // The values are approximations anyway. Limiting their precision hides
+    // noise, like tiny non-zero off-diagonal elements of the inertia tensor.
+    let number = |value: Scalar| {
+        let value = format!("{:.6}", value.into_f64());
+        match value.strip_prefix('-') {
+            Some(abs) if abs.trim_matches(['0', '.']).is_empty() => {
+                abs.to_string()
+            }
+            _ => value,
+        }
+    };
+    let vector = |[a, b, c]: [Scalar; 3]| {
+        format!("[{}, {}, {}]", number(a), number(b), number(c))
+    };
+
+    println!("Volume:       {}", number(properties.volume));
+    println!("Surface area: {}", number(properties.surface_area));
+    println!(
+        "Centroid:     {}",
+        vector(properties.centroid.coords.components)
+    );
+    for (i, &row) in properties.inertia.iter().enumerate() {
+        let label = if i == 0 { "Inertia:" } else { "" };
+        println!("{label:<14}{}", vector(row));
+    }
 }
 
 /// Return value of [`Instance::process_model`]
@@ -121,9 +318,20 @@ pub enum Error {
     #[error(transparent)]
     Tolerance(#[from] InvalidTolerance),
 
+    /// Model doesn't support the requested operation
+    ///
+    /// Returned by [`Instance::process_model`], if the operation requires
+    /// access to the model's b-rep. See [`Instance::process_brep_model`].
+    #[error("{0} is not supported for this model")]
+    Unsupported(&'static str),
+
     /// Unhandled validation errors
     #[error(transparent)]
     Validation(#[from] ValidationErrors),
+
+    /// Error writing validation report
+    #[error("Error writing validation report")]
+    ValidationReport(#[source] io::Error),
 }
 
 impl fmt::Debug for Error {
@@ -19,6 +19,7 @@ pub use self::{
 
 pub use fj_core as core;
 pub use fj_export as export;
+pub use fj_import as import;
 pub use fj_interop as interop;
 pub use fj_math as math;
 pub use fj_viewer as viewer;
@@ -1,6 +1,6 @@
 fn main() -> fj::Result {
     let mut fj = fj::Instance::new();
     let model = all::model(&mut fj.core);
-    fj.process_model(&model)?;
+    fj.process_brep_model(&model)?;
     Ok(())
 }